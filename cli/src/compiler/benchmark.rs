@@ -0,0 +1,162 @@
+// NOTE: 大きな仕様での変換速度を確認するための合成 fixture
+//       cargo test -p cli --release -- --ignored --nocapture bench_synthetic_tree
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::compiler::{compile, CompilerOptions};
+
+const SCHEMA_COUNT: usize = 4000;
+const PATH_COUNT: usize = 1000;
+
+fn write_file(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn build_schema(index: usize) -> String {
+    let reference = if index > 0 {
+        format!(
+            "
+  parent:
+    $ref: ./Model{}.yaml",
+            index - 1
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "type: object
+required:
+  - id
+properties:
+  id:
+    type: string
+    format: uuid
+  name:
+    type: string
+    maxLength: 100
+  status:
+    type: string
+    enum: [active, inactive]
+  tags:
+    type: array
+    items:
+      type: string{}
+",
+        reference
+    )
+}
+
+fn build_path(index: usize) -> String {
+    format!(
+        "parameters:
+  - name: id
+    in: path
+    required: true
+    schema:
+      type: string
+get:
+  operationId: getResource{index}
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: ../components/schemas/Model{schema}.yaml
+put:
+  operationId: updateResource{index}
+  requestBody:
+    content:
+      application/json:
+        schema:
+          $ref: ../components/schemas/Model{schema}.yaml
+  responses:
+    '200':
+      description: ok
+",
+        index = index,
+        schema = index * SCHEMA_COUNT / PATH_COUNT
+    )
+}
+
+// NOTE: schema 4000 ファイル + path 1000 ファイルの計 5000 ファイルと、それらを参照するルートのファイル
+fn generate_synthetic_tree(root: &Path) {
+    let paths = (0..PATH_COUNT)
+        .map(|i| {
+            format!(
+                "  /resources{}/{{id}}:\n    $ref: ./paths/resources{}.yaml\n",
+                i, i
+            )
+        })
+        .collect::<String>();
+    write_file(
+        &root.join("openapi.yaml"),
+        &format!(
+            "openapi: 3.0.3\ninfo:\n  title: Synthetic\n  version: 1.0.0\npaths:\n{}",
+            paths
+        ),
+    );
+
+    for i in 0..SCHEMA_COUNT {
+        write_file(
+            &root.join(format!("components/schemas/Model{}.yaml", i)),
+            &build_schema(i),
+        );
+    }
+    for i in 0..PATH_COUNT {
+        write_file(
+            &root.join(format!("paths/resources{}.yaml", i)),
+            &build_path(i),
+        );
+    }
+}
+
+#[test]
+#[ignore]
+fn bench_synthetic_tree() {
+    let root: PathBuf =
+        std::env::temp_dir().join(format!("openapi2tsp-bench-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    generate_synthetic_tree(&root);
+    let root = fs::canonicalize(&root).unwrap();
+    let options = CompilerOptions {
+        log_dir: None,
+        ..CompilerOptions::default()
+    };
+
+    let file_count = SCHEMA_COUNT + PATH_COUNT + 1;
+    let started_at = Instant::now();
+    compile(&root, &options);
+    let elapsed = started_at.elapsed();
+
+    let output_count = walk_tsp_files(&root);
+    fs::remove_dir_all(&root).unwrap();
+
+    println!(
+        "compiled {} files in {:.2?} ({:.0} files/s, {} threads)",
+        file_count,
+        elapsed,
+        file_count as f64 / elapsed.as_secs_f64(),
+        rayon::current_num_threads()
+    );
+    assert_eq!(output_count, file_count);
+}
+
+fn walk_tsp_files(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .map(|path| {
+            if path.is_dir() {
+                walk_tsp_files(&path)
+            } else if path.extension().map(|e| e == "tsp").unwrap_or(false) {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
+
+use crate::compiler::{check_diagnostics, compile_files, CompilerOptions};
+use crate::file_source::DiskFileSource;
+
+#[derive(Debug, PartialEq)]
+enum FileStatus {
+    UpToDate,
+    // NOTE: 生成されるはずのファイルが存在しない
+    Missing,
+    // NOTE: ファイルの内容が生成結果と異なる (手で編集された、または再生成し忘れた)
+    Stale(String),
+}
+
+fn relative_path(root_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(root_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn build_unified_diff(name: &str, actual: &str, expected: &str) -> String {
+    TextDiff::from_lines(actual, expected)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", name), &format!("b/{}", name))
+        .to_string()
+}
+
+fn check_file(root_dir: &Path, path: &Path, expected: &str) -> FileStatus {
+    match fs::read_to_string(path) {
+        Ok(actual) if actual == expected => FileStatus::UpToDate,
+        Ok(actual) => FileStatus::Stale(build_unified_diff(
+            &relative_path(root_dir, path),
+            &actual,
+            expected,
+        )),
+        Err(_) => FileStatus::Missing,
+    }
+}
+
+// NOTE: node_modules や隠しディレクトリにある .tsp は変換の出力ではないので対象にしない
+fn list_tsp_files(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.expect("failed to read_entry").path())
+            .collect::<Vec<_>>(),
+        Err(_) => return vec![],
+    };
+    paths.sort();
+
+    paths
+        .into_iter()
+        .flat_map(|path| {
+            let file_name = path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            if file_name.starts_with('.') || file_name == "node_modules" {
+                vec![]
+            } else if path.is_dir() {
+                list_tsp_files(&path)
+            } else if file_name.ends_with(".tsp") {
+                vec![path]
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+// NOTE: ファイルを書き出さずに、生成結果とディスク上の .tsp を比較する。差分がなければ true を返す
+//       CI で使うので、ログも書き出さない
+pub fn check(root_dir: &Path, options: &CompilerOptions) -> bool {
+    let options = &CompilerOptions {
+        log_dir: None,
+        ..options.clone()
+    };
+    let output = compile_files(&DiskFileSource, root_dir, options);
+    if !check_diagnostics(options, &output.diagnostics) {
+        return false;
+    }
+    let output_dir = if root_dir.is_file() {
+        root_dir.parent().expect("Failed to get parent dir")
+    } else {
+        root_dir
+    };
+
+    let mut stale = vec![];
+    let mut missing = vec![];
+    for file in output.files.iter() {
+        match check_file(output_dir, &file.path, &file.content) {
+            FileStatus::UpToDate => {}
+            FileStatus::Missing => missing.push(&file.path),
+            FileStatus::Stale(diff) => {
+                print!("{}", diff);
+                stale.push(&file.path);
+            }
+        }
+    }
+
+    let generated = output
+        .files
+        .iter()
+        .map(|file| &file.path)
+        .collect::<HashSet<_>>();
+    let orphaned = list_tsp_files(output_dir)
+        .into_iter()
+        .filter(|path| !generated.contains(path))
+        .collect::<Vec<_>>();
+
+    let print_paths = |label: &str, paths: &[&PathBuf]| {
+        paths
+            .iter()
+            .for_each(|path| println!("{}: {}", label, relative_path(output_dir, path)));
+    };
+    print_paths("stale", &stale);
+    print_paths("missing", &missing);
+    print_paths("orphaned", &orphaned.iter().collect::<Vec<_>>());
+
+    let drift_count = stale.len() + missing.len() + orphaned.len();
+    if drift_count > 0 {
+        eprintln!(
+            "error: {} TypeSpec file(s) are out of date ({} stale, {} missing, {} orphaned)",
+            drift_count,
+            stale.len(),
+            missing.len(),
+            orphaned.len()
+        );
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn list_files(dir: &Path) -> Vec<PathBuf> {
+        let mut paths = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .flat_map(|path| {
+                if path.is_dir() {
+                    list_files(&path)
+                } else {
+                    vec![path]
+                }
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        paths
+    }
+
+    #[test]
+    fn test_check_writes_no_files() {
+        let root = std::env::temp_dir().join(format!("openapi2tsp-check-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("openapi.yaml"),
+            "openapi: 3.0.3\ninfo:\n  title: Pets\n  version: 1.0.0\npaths: {}\n",
+        )
+        .unwrap();
+        let root = fs::canonicalize(&root).unwrap();
+        let options = CompilerOptions {
+            log_dir: Some(root.join("logs")),
+            ..CompilerOptions::default()
+        };
+
+        let result = check(&root, &options);
+        let files = list_files(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        // NOTE: 出力先の .tsp がないので missing になるが、ログも .tsp も書き出さない
+        assert!(!result);
+        assert_eq!(files, vec![root.join("openapi.yaml")]);
+    }
+
+    #[test]
+    fn test_build_unified_diff() {
+        let diff = build_unified_diff(
+            "models/Pet.tsp",
+            "model Pet {\n  id: int32;\n  name: string;\n}\n",
+            "model Pet {\n  id: int64;\n  name: string;\n}\n",
+        );
+
+        assert_eq!(
+            diff,
+            "--- a/models/Pet.tsp\n+++ b/models/Pet.tsp\n@@ -1,4 +1,4 @@\n model Pet {\n-  id: int32;\n+  id: int64;\n   name: string;\n }\n"
+        );
+    }
+}
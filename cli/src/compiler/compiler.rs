@@ -1,15 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::fs::{self, File};
-use std::io::Write;
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
+use rayon::prelude::*;
+
 use crate::compiler::parse_postprocess;
+use crate::compiler::project::build_project_files;
+use crate::compiler::report::{
+    add_fallback_names, build_conversion_report, ConversionReport, ReportFormat,
+};
+use crate::compiler::validate::{validate_type_spec_file_nodes, ValidationIssue};
+use crate::file_source::{DiskFileSource, FileSource, MemoryFileSource};
+use crate::logger::{self, Diagnostic, DiagnosticCollector};
 use crate::openapi_parser::node::*;
-use crate::openapi_parser::parse_yaml_files;
-use crate::type_spec::node::TypeSpecFileNode;
-use crate::type_spec::node_builder::build_type_spec_file_node;
-use crate::yaml_loader::load_yaml;
+use crate::openapi_parser::{parse_yaml_files, upconvert_yaml_files};
+use crate::type_spec::node::{sanitize_identifier, IdentifierNode, TypeSpecFileNode};
+use crate::type_spec::node_builder::{
+    build_component_model_name, build_type_spec_file_node, is_redundant_component_ref,
+    merge_type_spec_file_nodes, to_pascal_case_with_hyphens,
+};
+use crate::type_spec::printer::PrintOptions;
+use crate::yaml_loader::load_yaml_files;
 
 pub type FilePathObjectMap = HashMap<String, DataModelNode>;
 
@@ -17,8 +30,14 @@ pub type ObjectFilePathMap = HashMap<String, String>;
 
 type PathFileMap = HashMap<String, String>;
 
-fn write_log<T: Debug>(log_file_name: &str, content: &T) {
-    let _ = fs::remove_file(log_file_name);
+fn write_log<T: Debug>(log_dir: &Option<PathBuf>, log_file_name: &str, content: &T) {
+    let log_dir = match log_dir {
+        Some(log_dir) => log_dir,
+        None => return,
+    };
+    fs::create_dir_all(log_dir).expect("failed to create log dir");
+    let log_file_name = log_dir.join(log_file_name);
+    let _ = fs::remove_file(&log_file_name);
 
     let mut log_file = File::create(log_file_name).unwrap();
 
@@ -27,7 +46,7 @@ fn write_log<T: Debug>(log_file_name: &str, content: &T) {
         .unwrap();
 }
 
-fn build_namespace(path: &PathBuf) -> String {
+fn build_namespace(path: &Path) -> String {
     let mut namespace = path
         .file_name()
         .and_then(|s| s.to_str())
@@ -36,31 +55,64 @@ fn build_namespace(path: &PathBuf) -> String {
         .collect::<Vec<_>>();
     namespace[0] = namespace[0].to_ascii_uppercase();
 
-    format!("{}Service", namespace.into_iter().collect::<String>())
+    format!(
+        "{}Service",
+        sanitize_identifier(&namespace.into_iter().collect::<String>())
+    )
 }
 
-fn build_path_file_map(file_nodes: &Vec<OpenAPIFileNode>) -> PathFileMap {
-    let paths_node = file_nodes
+fn build_openapi_version(file_nodes: &[OpenAPIFileNode]) -> OpenAPIVersion {
+    file_nodes
         .iter()
         .flat_map(|node| node.contents.iter())
-        .find(|node| {
-            if let OpenAPINode::Paths(_) = node {
-                true
+        .find_map(|node| {
+            if let OpenAPINode::Metadata(metadata) = node {
+                Some(metadata.version())
             } else {
-                false
+                None
             }
         })
-        .and_then(|node| {
-            if let OpenAPINode::Paths(paths) = node {
-                Some(paths)
+        .unwrap_or(OpenAPIVersion::V3_0)
+}
+
+fn build_webhook_file_map(file_nodes: &[OpenAPIFileNode]) -> PathFileMap {
+    file_nodes
+        .iter()
+        .flat_map(|node| node.contents.iter())
+        .filter_map(|node| {
+            if let OpenAPINode::Webhooks(webhooks) = node {
+                Some(webhooks)
             } else {
                 None
             }
         })
-        .expect("failed to find paths node");
+        .flat_map(|webhooks| webhooks.iter())
+        .map(|node| {
+            let file_name = node
+                .ref_file_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_owned())
+                .expect("invalid file path");
 
-    paths_node
+            (file_name, node.path.clone())
+        })
+        .collect()
+}
+
+// NOTE: OpenAPI 3.1 では paths を省略できる (webhooks だけの文書など) ので、その場合は空にする
+fn build_path_file_map(file_nodes: &[OpenAPIFileNode]) -> PathFileMap {
+    let paths_node = file_nodes
         .iter()
+        .flat_map(|node| node.contents.iter())
+        .find_map(|node| match node {
+            OpenAPINode::Paths(paths) => Some(paths),
+            _ => None,
+        });
+
+    paths_node
+        .into_iter()
+        .flat_map(|paths| paths.iter())
         .map(|node| {
             let file_name = node
                 .ref_file_path
@@ -75,12 +127,17 @@ fn build_path_file_map(file_nodes: &Vec<OpenAPIFileNode>) -> PathFileMap {
         .collect()
 }
 
-fn build_file_path_object_map(nodes: &Vec<OpenAPIFileNode>) -> FilePathObjectMap {
+fn build_file_path_object_map(nodes: &[OpenAPIFileNode]) -> FilePathObjectMap {
     let mut map = HashMap::new();
     for node in nodes.iter() {
         let path = node.path.to_str().unwrap().to_string();
-        if node.contents.len() == 1 {
-            if let Some(OpenAPINode::DataModel(data_model_node)) = node.contents.get(0) {
+        // NOTE: $defs を持つファイルは後続に定義が並ぶので、先頭のスキーマをファイルの実体とみなす
+        let is_schema_file = node
+            .contents
+            .iter()
+            .all(|content| matches!(content, OpenAPINode::DataModel(_)));
+        if is_schema_file {
+            if let Some(OpenAPINode::DataModel(data_model_node)) = node.contents.first() {
                 map.insert(path, data_model_node.clone());
             }
         }
@@ -89,73 +146,1884 @@ fn build_file_path_object_map(nodes: &Vec<OpenAPIFileNode>) -> FilePathObjectMap
     map
 }
 
-fn build_object_file_path_map(nodes: &Vec<OpenAPIFileNode>) -> ObjectFilePathMap {
-    let mut map = HashMap::new();
+// NOTE: 同じ名前空間に同じ名前のモデルが複数ある場合は最初のものを使う
+//       重複は変換した後の validate_type_spec_file_nodes でエラーとして報告する
+fn build_object_file_path_map(nodes: &[OpenAPIFileNode], env: &CompilerEnv) -> ObjectFilePathMap {
+    let mut map: ObjectFilePathMap = HashMap::new();
     for node in nodes.iter() {
         let path = node.path.to_str().unwrap().to_string();
 
-        for content in node.contents.iter() {
-            if let OpenAPINode::DataModel(data_model_node) = content {
-                if let Some(title) = &data_model_node.title() {
-                    map.insert(title.clone(), path.clone());
-                }
+        let names = node.contents.iter().filter_map(|content| match content {
+            OpenAPINode::DataModel(data_model_node) => data_model_node.title(),
+            OpenAPINode::Component(component) if !is_redundant_component_ref(component) => {
+                Some(build_component_model_name(&component.kind, &component.name))
             }
+            _ => None,
+        });
+        for name in names {
+            map.entry(env.build_object_key(&node.path, &name))
+                .or_insert_with(|| path.clone());
         }
     }
 
     map
 }
 
-fn write_type_spec_file(file_node: &TypeSpecFileNode) {
-    let file_name = file_node.path.to_str().unwrap();
-    if Path::new(file_name).exists() {
-        fs::remove_file(file_name).expect("failed to remove file");
+// NOTE: ルートの spec のディレクトリからの相対ディレクトリを名前空間にする (models/billing -> Models.Billing)
+//       ルートの spec と同じディレクトリや、その外にあるファイルは None
+fn build_relative_namespace(namespace_root_dir: &Path, path: &Path) -> Option<String> {
+    let relative_dir = path.parent()?.strip_prefix(namespace_root_dir).ok()?;
+    let segments = relative_dir
+        .components()
+        .filter_map(|component| component.as_os_str().to_str())
+        .map(|segment| sanitize_identifier(&to_pascal_case_with_hyphens(segment)))
+        .collect::<Vec<_>>();
+
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("."))
     }
+}
 
-    let mut file = File::create(file_name).unwrap();
-    file.write_all(file_node.to_string().as_bytes()).unwrap();
+fn build_namespace_root_dir(root_dir: &Path, file_nodes: &[OpenAPIFileNode]) -> PathBuf {
+    file_nodes
+        .iter()
+        .find(|node| {
+            node.contents
+                .iter()
+                .any(|content| matches!(content, OpenAPINode::Info(_)))
+        })
+        .and_then(|node| node.path.parent())
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_else(|| root_dir.to_path_buf())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum NamedResponses {
+    /// Emit the response models next to the operations that use them
+    SameFile,
+    /// Emit the response models into a shared responses.tsp
+    SharedFile,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExtensionHandling {
+    /// Emit OpenAPI data without a TypeSpec counterpart (e.g. links) with @extension
+    Emit,
+    /// Drop OpenAPI data without a TypeSpec counterpart
+    Drop,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperationNames {
+    pub get: String,
+    pub post: String,
+    pub put: String,
+    pub patch: String,
+    pub delete: String,
+}
+
+impl OperationNames {
+    pub fn get_name(&self, operation: &Operation) -> &str {
+        match operation {
+            Operation::Get => &self.get,
+            Operation::Post => &self.post,
+            Operation::Put => &self.put,
+            Operation::Patch => &self.patch,
+            Operation::Delete => &self.delete,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NamingOptions {
+    pub operations: OperationNames,
+    pub fallback_model: String,
+    pub fallback_model_alias: String,
+    pub fallback_component_ref: String,
+}
+
+impl Default for NamingOptions {
+    fn default() -> Self {
+        NamingOptions {
+            operations: OperationNames {
+                get: "list".to_string(),
+                post: "create".to_string(),
+                put: "update".to_string(),
+                patch: "update".to_string(),
+                delete: "delete".to_string(),
+            },
+            fallback_model: "UnknownModel".to_string(),
+            fallback_model_alias: "UnknownModelAlias".to_string(),
+            fallback_component_ref: "AnonymousComponentRef".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompilerOptions {
+    pub hoist_inline_models: bool,
+    // NOTE: サブディレクトリごとに名前空間を分ける (models/billing -> {namespace}.Models.Billing)
+    pub nested_namespaces: bool,
+    // NOTE: 同じ名前空間で重複している宣言に連番を付けて、エラーにせずに出力する
+    pub disambiguate_names: bool,
+    // NOTE: main.tsp, tspconfig.yaml, package.json も出力して、そのままコンパイルできるプロジェクトにする
+    pub emit_project: bool,
+    pub open_enums: bool,
+    pub named_responses: Option<NamedResponses>,
+    pub print_options: PrintOptions,
+    pub namespace: Option<String>,
+    pub naming: NamingOptions,
+    // NOTE: string の format から TypeSpec の型への対応
+    pub type_mappings: HashMap<String, String>,
+    pub extensions: ExtensionHandling,
+    // NOTE: None の場合はログを出力しない
+    pub log_dir: Option<PathBuf>,
+    pub strict: bool,
+    pub report: bool,
+    // NOTE: compile と compile_stdin で出力するレポートの形式 (ライブラリとして使う場合は CompileOutput::report を使う)
+    pub report_format: ReportFormat,
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        CompilerOptions {
+            hoist_inline_models: false,
+            nested_namespaces: false,
+            disambiguate_names: false,
+            emit_project: false,
+            open_enums: false,
+            named_responses: None,
+            print_options: PrintOptions::default(),
+            namespace: None,
+            naming: NamingOptions::default(),
+            type_mappings: HashMap::new(),
+            extensions: ExtensionHandling::Emit,
+            log_dir: Some(PathBuf::new()),
+            strict: false,
+            report: false,
+            report_format: ReportFormat::Human,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct CompilerEnv {
+    pub options: CompilerOptions,
     pub namespace: String,
+    pub openapi_version: OpenAPIVersion,
     pub path_file_map: PathFileMap,
+    pub webhook_file_map: PathFileMap,
     pub file_path_object_map: FilePathObjectMap,
     pub object_file_path_map: ObjectFilePathMap,
+    pub error_model_names: HashSet<String>,
+    pub namespace_root_dir: PathBuf,
 }
 
 impl CompilerEnv {
-    fn build(root_dir: &PathBuf, openapi_file_nodes: &Vec<OpenAPIFileNode>) -> Self {
-        Self {
-            namespace: build_namespace(root_dir),
+    fn build(
+        root_dir: &Path,
+        openapi_file_nodes: &[OpenAPIFileNode],
+        options: &CompilerOptions,
+    ) -> Self {
+        let mut env = Self {
+            options: options.clone(),
+            namespace: options
+                .namespace
+                .clone()
+                .unwrap_or_else(|| build_namespace(root_dir)),
+            openapi_version: build_openapi_version(openapi_file_nodes),
             path_file_map: build_path_file_map(openapi_file_nodes),
+            webhook_file_map: build_webhook_file_map(openapi_file_nodes),
             file_path_object_map: build_file_path_object_map(openapi_file_nodes),
-            object_file_path_map: build_object_file_path_map(openapi_file_nodes),
+            object_file_path_map: HashMap::new(),
+            error_model_names: HashSet::new(),
+            namespace_root_dir: build_namespace_root_dir(root_dir, openapi_file_nodes),
+        };
+        env.object_file_path_map = build_object_file_path_map(openapi_file_nodes, &env);
+
+        env
+    }
+
+    // NOTE: nested_namespaces でない場合は、全てのファイルがサービスの名前空間になる
+    pub fn get_relative_namespace(&self, path: &Path) -> Option<String> {
+        if self.options.nested_namespaces {
+            build_relative_namespace(&self.namespace_root_dir, path)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_file_namespace(&self, path: &Path) -> String {
+        match self.get_relative_namespace(path) {
+            Some(relative_namespace) => format!("{}.{}", self.namespace, relative_namespace),
+            None => self.namespace.clone(),
         }
     }
-}
 
-pub fn compile(root_dir: &PathBuf) {
-    let yaml_files = load_yaml(&root_dir).flatten_files();
+    // NOTE: object_file_path_map のキー。名前空間が異なれば同じ名前のモデルも区別する
+    pub fn build_object_key(&self, path: &Path, name: &str) -> String {
+        match self.get_relative_namespace(path) {
+            Some(relative_namespace) => format!("{}.{}", relative_namespace, name),
+            None => name.to_string(),
+        }
+    }
+
+    // NOTE: 名前空間を除いたモデル名の一覧。新しく作るモデルの名前が重複しないようにするために使う
+    pub fn build_object_names(&self) -> HashSet<String> {
+        self.object_file_path_map
+            .iter()
+            .map(
+                |(key, path)| match self.get_relative_namespace(Path::new(path)) {
+                    Some(relative_namespace) => key
+                        .strip_prefix(&format!("{}.", relative_namespace))
+                        .unwrap_or(key)
+                        .to_string(),
+                    None => key.clone(),
+                },
+            )
+            .collect()
+    }
 
-    let mut openapi_file_nodes = parse_yaml_files(&yaml_files);
+    // NOTE: 修飾されていない参照は、同じ名前空間のモデルかルートのファイルのモデルを指す
+    pub fn find_object_file_path(
+        &self,
+        identifier: &IdentifierNode,
+        current_file_path: &Path,
+    ) -> Option<&String> {
+        let key = match &identifier.namespace {
+            Some(namespace) if namespace == &self.namespace => identifier.name.clone(),
+            Some(namespace) => namespace
+                .strip_prefix(&format!("{}.", self.namespace))
+                .map(|relative_namespace| format!("{}.{}", relative_namespace, identifier.name))
+                .unwrap_or_else(|| identifier.name.clone()),
+            None => self.build_object_key(current_file_path, &identifier.name),
+        };
+
+        self.object_file_path_map
+            .get(&key)
+            .or_else(|| self.object_file_path_map.get(&identifier.name))
+    }
+
+    // NOTE: 修飾されていない参照を名前で探し、別の名前空間のモデルを指している場合はその名前空間を返す
+    //       同じ名前のモデルが複数の名前空間にある場合は決められないので None
+    pub fn resolve_object_namespace(&self, name: &str, current_file_path: &Path) -> Option<String> {
+        if !self.options.nested_namespaces
+            || self
+                .object_file_path_map
+                .contains_key(&self.build_object_key(current_file_path, name))
+        {
+            return None;
+        }
+
+        let namespaces = self
+            .object_file_path_map
+            .iter()
+            .filter(|(key, path)| key == &&self.build_object_key(Path::new(path), name))
+            .map(|(_, path)| self.get_file_namespace(Path::new(path)))
+            .collect::<HashSet<_>>();
+        if namespaces.len() == 1 {
+            namespaces.into_iter().next()
+        } else {
+            None
+        }
+    }
+
+    fn extend_object_file_path_map(&mut self, map: ObjectFilePathMap) {
+        for (name, path) in map.into_iter() {
+            let key = self.build_object_key(Path::new(&path), &name);
+            self.object_file_path_map.insert(key, path);
+        }
+    }
+}
+
+// NOTE: parse した後の後処理と CompilerEnv の構築。watch モードでも同じ処理を使う
+pub(crate) fn prepare_openapi_file_nodes(
+    root_dir: &Path,
+    mut openapi_file_nodes: Vec<OpenAPIFileNode>,
+    options: &CompilerOptions,
+) -> (Vec<OpenAPIFileNode>, CompilerEnv) {
     parse_postprocess::remove_examples(&mut openapi_file_nodes);
+    parse_postprocess::remove_empty_files(&mut openapi_file_nodes);
     parse_postprocess::merge_parameter_nodes(&mut openapi_file_nodes);
+    if options.extensions == ExtensionHandling::Drop {
+        parse_postprocess::remove_links(&mut openapi_file_nodes);
+    }
+    if !options.type_mappings.is_empty() {
+        parse_postprocess::apply_type_mappings(&mut openapi_file_nodes, &options.type_mappings);
+    }
 
-    let env = CompilerEnv::build(root_dir, &openapi_file_nodes);
-    write_log("compiler_env.log", &env);
+    let mut env = CompilerEnv::build(root_dir, &openapi_file_nodes, options);
+    if options.disambiguate_names {
+        let renamed = parse_postprocess::disambiguate_model_titles(&mut openapi_file_nodes, &env);
+        if !renamed.is_empty() {
+            for (name, new_name, path) in renamed.iter() {
+                logger::warning!(
+                    "renamed duplicate model `{}` in {} to `{}`",
+                    name,
+                    display_path(&env.namespace_root_dir, path),
+                    new_name
+                );
+            }
+            // NOTE: 変更した名前で参照を解決するために作り直す
+            env = CompilerEnv::build(root_dir, &openapi_file_nodes, options);
+        }
+    }
 
-    parse_postprocess::replace_file_ref_to_component_ref(
+    parse_postprocess::replace_file_ref_to_component_ref(&mut openapi_file_nodes, &env);
+    parse_postprocess::resolve_header_refs(&mut openapi_file_nodes);
+    let shared_responses = parse_postprocess::extract_shared_error_responses(
         &mut openapi_file_nodes,
-        &env.file_path_object_map,
+        &env.build_object_names(),
     );
-    write_log("openapi_node.log", &openapi_file_nodes);
+    env.extend_object_file_path_map(shared_responses);
+    if let Some(named_responses) = options.named_responses {
+        let named = parse_postprocess::extract_named_responses(
+            &mut openapi_file_nodes,
+            &env.build_object_names(),
+            named_responses == NamedResponses::SharedFile,
+        );
+        env.extend_object_file_path_map(named);
+    } else {
+        let defaults = parse_postprocess::extract_default_responses(
+            &mut openapi_file_nodes,
+            &env.build_object_names(),
+        );
+        env.extend_object_file_path_map(defaults);
+    }
+    if options.hoist_inline_models {
+        let hoisted = parse_postprocess::hoist_inline_models(
+            &mut openapi_file_nodes,
+            &env.build_object_names(),
+        );
+        env.extend_object_file_path_map(hoisted);
+    }
+    if options.nested_namespaces {
+        parse_postprocess::qualify_component_refs(&mut openapi_file_nodes, &env);
+    }
+    env.error_model_names = parse_postprocess::build_error_model_names(&openapi_file_nodes);
 
-    let type_spec_file_nodes = openapi_file_nodes
-        .into_iter()
-        .map(|node| build_type_spec_file_node(node, &env))
+    (openapi_file_nodes, env)
+}
+
+// NOTE: エラーが 1 つでもあればファイルを書き出さない
+//       strict の場合は変換しきれなかった箇所 (警告) が 1 つでもあればファイルを書き出さない
+pub(crate) fn check_diagnostics(options: &CompilerOptions, diagnostics: &[Diagnostic]) -> bool {
+    let error_count = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == log::Level::Error)
+        .count();
+    let warning_count = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == log::Level::Warn)
+        .count();
+    if error_count > 0 {
+        eprintln!(
+            "error: {} error(s) were reported, no files were written",
+            error_count
+        );
+        return false;
+    }
+    if options.strict && warning_count > 0 {
+        eprintln!(
+            "error: {} warning(s) were reported in strict mode, no files were written",
+            warning_count
+        );
+        return false;
+    }
+
+    true
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompileOutput {
+    pub files: Vec<GeneratedFile>,
+    pub diagnostics: Vec<Diagnostic>,
+    // NOTE: options.report が true の場合だけ作る
+    pub report: Option<ConversionReport>,
+}
+
+impl CompileOutput {
+    pub fn warning_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == log::Level::Warn)
+            .count()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == log::Level::Error)
+            .count()
+    }
+}
+
+fn display_path(root_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(root_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+// NOTE: TypeSpec のファイルに対応する yaml ファイルを示す。後処理で追加したファイル (responses.tsp など) はそのまま示す
+fn display_source_path(root_dir: &Path, source_paths: &HashSet<PathBuf>, path: &Path) -> String {
+    let yaml_path = path.with_extension("yaml");
+    if source_paths.contains(&yaml_path) {
+        display_path(root_dir, &yaml_path)
+    } else {
+        format!("{} (generated)", display_path(root_dir, path))
+    }
+}
+
+pub(crate) fn report_validation_issues(
+    issues: &[ValidationIssue],
+    root_dir: &Path,
+    source_paths: &HashSet<PathBuf>,
+) {
+    let display = |path: &PathBuf| display_source_path(root_dir, source_paths, path);
+    for issue in issues.iter() {
+        match issue {
+            ValidationIssue::DuplicateDeclaration { name, paths } => logger::error!(
+                "duplicate declaration `{}` in {} (use --disambiguate-names to rename them)",
+                name,
+                paths.iter().map(display).collect::<Vec<_>>().join(", ")
+            ),
+            ValidationIssue::UnresolvedReference { name, path } => {
+                logger::error!("unresolved reference `{}` in {}", name, display(path))
+            }
+            ValidationIssue::Renamed {
+                name,
+                new_name,
+                path,
+            } => logger::warning!(
+                "renamed duplicate declaration `{}` in {} to `{}`",
+                name,
+                display(path),
+                new_name
+            ),
+        }
+    }
+}
+
+// NOTE: 読み込めない yaml がある場合は、エラーを diagnostics に記録して変換しない
+fn build_type_spec_file_nodes(
+    source: &dyn FileSource,
+    root_dir: &Path,
+    options: &CompilerOptions,
+) -> Option<(Vec<TypeSpecFileNode>, Option<ConversionReport>, CompilerEnv)> {
+    let yaml_files = match load_yaml_files(source, root_dir) {
+        Ok(yaml_files) => yaml_files,
+        Err(error) => {
+            logger::error!("{}", error);
+            return None;
+        }
+    };
+    let source_paths = yaml_files
+        .iter()
+        .map(|yaml_file| yaml_file.path.clone())
+        .collect::<HashSet<_>>();
+
+    // NOTE: 後処理で取り除かれるものも報告できるように、後処理の前に作る
+    let report_hashes = if options.report {
+        Some(upconvert_yaml_files(&yaml_files))
+    } else {
+        None
+    };
+
+    let openapi_file_nodes = parse_yaml_files(yaml_files);
+    let mut report =
+        report_hashes.map(|hashes| build_conversion_report(&hashes, &openapi_file_nodes, options));
+    let (openapi_file_nodes, env) =
+        prepare_openapi_file_nodes(root_dir, openapi_file_nodes, options);
+    write_log(&options.log_dir, "compiler_env.log", &env);
+    write_log(&options.log_dir, "openapi_node.log", &openapi_file_nodes);
+
+    // NOTE: ファイルごとの変換は互いに独立しているので並列に行う
+    let mut type_spec_file_nodes = openapi_file_nodes
+        .into_par_iter()
+        .map(logger::propagate(|node| {
+            build_type_spec_file_node(node, &env)
+        }))
         .collect::<Vec<_>>();
-    write_log("type_spec_node.log", &type_spec_file_nodes);
+    if let Some(report) = report.as_mut() {
+        add_fallback_names(report, &type_spec_file_nodes, &options.naming);
+    }
+    let issues =
+        validate_type_spec_file_nodes(&mut type_spec_file_nodes, options.disambiguate_names);
+    report_validation_issues(&issues, &env.namespace_root_dir, &source_paths);
+    write_log(
+        &options.log_dir,
+        "type_spec_node.log",
+        &type_spec_file_nodes,
+    );
+
+    Some((type_spec_file_nodes, report, env))
+}
+
+// NOTE: ライブラリとしての入口。ファイルの書き出しは行わず、生成したファイルの内容を返す
+pub fn compile_files(
+    source: &dyn FileSource,
+    root_dir: &Path,
+    options: &CompilerOptions,
+) -> CompileOutput {
+    let collector = DiagnosticCollector::new();
+    let (files, report) = collector.scope(|| {
+        let Some((type_spec_file_nodes, report, env)) =
+            build_type_spec_file_nodes(source, root_dir, options)
+        else {
+            return (vec![], None);
+        };
+        let mut files = type_spec_file_nodes
+            .par_iter()
+            .map(logger::propagate(|node: &TypeSpecFileNode| GeneratedFile {
+                path: node.path.clone(),
+                content: node.print(&options.print_options),
+            }))
+            .collect::<Vec<_>>();
+        if options.emit_project {
+            files.extend(build_project_files(&type_spec_file_nodes, &env));
+        }
+
+        (files, report)
+    });
+
+    CompileOutput {
+        files,
+        diagnostics: collector.take(),
+        report,
+    }
+}
 
-    type_spec_file_nodes.iter().for_each(write_type_spec_file);
+// NOTE: 全てのファイルを 1 つの TypeSpec の文書にまとめて返す (stdin/stdout で使う)
+pub fn compile_bundle(
+    source: &dyn FileSource,
+    root_dir: &Path,
+    bundle_path: PathBuf,
+    options: &CompilerOptions,
+) -> CompileOutput {
+    let collector = DiagnosticCollector::new();
+    let (files, report) = collector.scope(|| {
+        let Some((type_spec_file_nodes, report, _)) =
+            build_type_spec_file_nodes(source, root_dir, options)
+        else {
+            return (vec![], None);
+        };
+        let bundle = merge_type_spec_file_nodes(bundle_path, type_spec_file_nodes);
+        let file = GeneratedFile {
+            path: bundle.path.clone(),
+            content: bundle.print(&options.print_options),
+        };
+
+        (vec![file], report)
+    });
+
+    CompileOutput {
+        files,
+        diagnostics: collector.take(),
+        report,
+    }
+}
+
+fn write_generated_file(file: &GeneratedFile) {
+    if file.path.exists() {
+        fs::remove_file(&file.path).expect("failed to remove file");
+    }
+
+    let mut output = File::create(&file.path).unwrap();
+    output.write_all(file.content.as_bytes()).unwrap();
+}
+
+fn print_report(output: &CompileOutput, root_dir: &Path, options: &CompilerOptions) -> String {
+    let root_dir = if root_dir.is_file() {
+        root_dir.parent().expect("Failed to get parent dir")
+    } else {
+        root_dir
+    };
+
+    output
+        .report
+        .as_ref()
+        .map(|report| report.print(options.report_format, root_dir))
+        .unwrap_or_default()
+}
+
+pub fn compile(root_dir: &Path, options: &CompilerOptions) {
+    let output = compile_files(&DiskFileSource, root_dir, options);
+    print!("{}", print_report(&output, root_dir, options));
+    if !check_diagnostics(options, &output.diagnostics) {
+        std::process::exit(1);
+    }
+
+    output.files.par_iter().for_each(write_generated_file);
+}
+
+// NOTE: stdin から 1 つの文書を読み、document_path に置いたものとして $ref を解決して、まとめた TypeSpec を stdout に書き出す
+pub fn compile_stdin(document_path: &Path, options: &CompilerOptions) {
+    let mut content = String::new();
+    io::stdin()
+        .read_to_string(&mut content)
+        .expect("failed to read stdin");
+    let base_dir = document_path.parent().expect("Failed to get parent dir");
+    let source = match MemoryFileSource::from_document(document_path, content) {
+        Ok(source) => source,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            std::process::exit(2);
+        }
+    };
+
+    // NOTE: base_dir の外 (../ など) を参照していても読み込めるように、全てのファイルの共通の親を root にする
+    let root_dir = source
+        .paths()
+        .map(|path| {
+            path.parent()
+                .expect("Failed to get parent dir")
+                .to_path_buf()
+        })
+        .reduce(|root, dir| {
+            root.ancestors()
+                .find(|ancestor| dir.starts_with(ancestor))
+                .expect("Failed to find common dir")
+                .to_path_buf()
+        })
+        .expect("Failed to find root dir");
+    let options = CompilerOptions {
+        namespace: options
+            .namespace
+            .clone()
+            .or_else(|| Some(build_namespace(base_dir))),
+        ..options.clone()
+    };
+    let output = compile_bundle(
+        &source,
+        &root_dir,
+        document_path.with_extension("tsp"),
+        &options,
+    );
+    // NOTE: stdout には TypeSpec を出力するので、レポートは stderr に出力する
+    eprint!("{}", print_report(&output, &root_dir, &options));
+    if !check_diagnostics(&options, &output.diagnostics) {
+        std::process::exit(1);
+    }
+
+    let mut stdout = io::stdout().lock();
+    output
+        .files
+        .iter()
+        .for_each(|file| stdout.write_all(file.content.as_bytes()).unwrap());
+}
+
+#[cfg(test)]
+mod test {
+    use crate::file_source::MemoryFileSource;
+
+    use super::*;
+
+    fn build_petstore_source() -> MemoryFileSource {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/petstore/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+",
+        );
+        source.insert(
+            "/petstore/paths/pets.yaml",
+            "get:
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: ../components/schemas/Pet.yaml
+",
+        );
+        source.insert(
+            "/petstore/components/schemas/Pet.yaml",
+            "type: object
+required:
+  - id
+properties:
+  id:
+    type: integer
+    format: int64
+",
+        );
+        source
+    }
+
+    #[test]
+    fn test_compile_files_from_memory() {
+        let source = build_petstore_source();
+        let options = CompilerOptions {
+            log_dir: None,
+            report: true,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/petstore"), &options);
+
+        assert_eq!(
+            output
+                .files
+                .iter()
+                .map(|file| file.path.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                "/petstore/components/schemas/Pet.tsp",
+                "/petstore/openapi.tsp",
+                "/petstore/paths/pets.tsp",
+            ]
+        );
+        assert_eq!(output.files[0].content, "model Pet {\n  id: int64;\n}\n");
+        assert!(output.files[2]
+            .content
+            .contains("import \"./../components/schemas/Pet.tsp\";\n"));
+        assert!(output.files[2].content.contains("      body: Pet;\n"));
+        let report = output.report.unwrap();
+        assert_eq!(report.files.len(), 3);
+        let pets_report = report
+            .files
+            .iter()
+            .find(|file| file.source == Path::new("/petstore/paths/pets.yaml"))
+            .unwrap();
+        assert_eq!(
+            pets_report.dropped_keys,
+            vec!["get.responses.200.description"]
+        );
+        assert!(pets_report.unknown_keys.is_empty());
+        assert!(pets_report.fallback_names.is_empty());
+    }
+
+    #[test]
+    fn test_compile_files_with_invalid_yaml() {
+        let mut source = build_petstore_source();
+        source.insert("/petstore/paths/pets.yaml", "get:\n  responses: [\n");
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/petstore"), &options);
+
+        assert!(output.files.is_empty());
+        assert_eq!(output.diagnostics.len(), 1);
+        assert_eq!(output.diagnostics[0].level, log::Level::Error);
+        assert!(output.diagnostics[0]
+            .message
+            .starts_with("failed to parse yaml /petstore/paths/pets.yaml: "));
+    }
+
+    #[test]
+    fn test_compile_files_with_webhooks_only() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/hooks/openapi.yaml",
+            "openapi: 3.1.0
+info:
+  title: Hooks
+  version: 1.0.0
+webhooks:
+  newPet:
+    post:
+      responses:
+        '200':
+          description: ok
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/hooks"), &options);
+
+        assert_eq!(output.files.len(), 1);
+        assert!(output.files[0]
+            .content
+            .contains("    @route(\"/newPet\")\n    interface NewPetWebhook {\n"));
+    }
+
+    #[test]
+    fn test_compile_files_with_invalid_schemas() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Shop
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      xml:
+        name: pet
+      properties:
+        id:
+          type: integer
+        tags: true
+    Broken: true
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            report: true,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+
+        assert!(output.files[0]
+            .content
+            .contains("model Pet {\n  id?: int32;\n}"));
+        let messages = output
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>();
+        assert!(messages.contains(&"unexpected key `xml` is ignored"));
+        assert!(messages.contains(&"invalid property `tags` is ignored"));
+        assert!(messages.contains(
+            &"invalid component `components.schemas.Broken` in /shop/openapi.yaml is ignored"
+        ));
+        let report = output.report.unwrap();
+        assert_eq!(
+            report.files[0].unknown_keys,
+            vec!["components.schemas.Broken"]
+        );
+        assert!(report.files[0]
+            .dropped_keys
+            .contains(&"components.schemas.Pet.xml".to_string()));
+    }
+
+    #[test]
+    fn test_compile_files_with_openapi_31_schemas() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.1.0
+info:
+  title: Shop
+  version: 1.0.0
+components:
+  schemas:
+    Item:
+      type: object
+      properties:
+        size:
+          type: [string, \"null\"]
+          enum: [small, large, null]
+        price:
+          type: number
+          exclusiveMinimum: 0
+          maximum: 100
+        kind:
+          const: item
+          description: always item
+        tags:
+          type: [string, 1]
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+
+        assert!(output.files[0].content.ends_with(
+            "model Item {
+  size?: \"small\" | \"large\" | null;
+
+  @minValueExclusive(0)
+  @maxValue(100)
+  price?: int32;
+
+  @doc(\"always item\")
+  kind?: \"item\";
+}
+"
+        ));
+        let messages = output
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>();
+        assert!(messages.contains(&"invalid property `tags` is ignored"));
+    }
+
+    #[test]
+    fn test_compile_files_with_project() {
+        let source = build_petstore_source();
+        let options = CompilerOptions {
+            log_dir: None,
+            emit_project: true,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/petstore"), &options);
+
+        assert_eq!(
+            output.files[3..]
+                .iter()
+                .map(|file| file.path.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                "/petstore/main.tsp",
+                "/petstore/tspconfig.yaml",
+                "/petstore/package.json",
+            ]
+        );
+        assert_eq!(
+            output.files[3].content,
+            "import \"./components/schemas/Pet.tsp\";
+import \"./openapi.tsp\";
+import \"./paths/pets.tsp\";
+"
+        );
+        assert!(output.files[4].content.contains("      - 3.0.0\n"));
+        assert!(output.files[5].content.contains(
+            "  \"dependencies\": {
+    \"@typespec/http\": \"^1.0.0\",
+    \"@typespec/openapi\": \"^1.0.0\"
+  },
+"
+        ));
+    }
+
+    #[test]
+    fn test_compile_bundle_from_memory() {
+        let source = build_petstore_source();
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_bundle(
+            &source,
+            &PathBuf::from("/petstore"),
+            PathBuf::from("/petstore/openapi.tsp"),
+            &options,
+        );
+
+        assert_eq!(output.files.len(), 1);
+        let content = &output.files[0].content;
+        assert!(!content.contains("import \"./"));
+        assert!(content.contains("model Pet {\n  id: int64;\n}\n"));
+        assert!(content.contains("namespace PetstoreService {}\n"));
+        assert!(content.contains("      body: Pet;\n"));
+    }
+
+    #[test]
+    fn test_build_relative_namespace() {
+        let root = Path::new("/spec");
+        assert_eq!(
+            build_relative_namespace(root, Path::new("/spec/models/billing-info/Account.yaml")),
+            Some("Models.BillingInfo".to_string())
+        );
+        assert_eq!(
+            build_relative_namespace(root, Path::new("/spec/openapi.yaml")),
+            None
+        );
+        assert_eq!(
+            build_relative_namespace(root, Path::new("/shared/Error.yaml")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compile_files_with_nested_namespaces() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Shop
+  version: 1.0.0
+paths:
+  /orders:
+    $ref: ./paths/orders.yaml
+",
+        );
+        source.insert(
+            "/shop/paths/orders.yaml",
+            "get:
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: ../models/billing/Account.yaml
+",
+        );
+        source.insert(
+            "/shop/models/billing/Account.yaml",
+            "type: object
+properties:
+  owner:
+    $ref: ../store/Account.yaml
+",
+        );
+        source.insert(
+            "/shop/models/store/Account.yaml",
+            "type: object
+properties:
+  name:
+    type: string
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            nested_namespaces: true,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+
+        assert_eq!(
+            output.files[0].content,
+            "import \"./../store/Account.tsp\";
+
+namespace ShopService.Models.Billing {
+  model Account {
+    owner?: ShopService.Models.Store.Account;
+  }
+}
+"
+        );
+        assert!(output.files[1]
+            .content
+            .starts_with("namespace ShopService.Models.Store {\n  model Account {\n"));
+        assert!(output.files[3]
+            .content
+            .contains("namespace ShopService.Paths {\n"));
+        assert!(output.files[3]
+            .content
+            .contains("      body: ShopService.Models.Billing.Account;\n"));
+    }
+
+    #[test]
+    fn test_compile_files_with_disambiguated_model_names() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Shop
+  version: 1.0.0
+paths: {}
+",
+        );
+        source.insert(
+            "/shop/models/Account.yaml",
+            "type: object
+properties:
+  store:
+    $ref: './store/Account.yaml'
+",
+        );
+        source.insert("/shop/models/store/Account.yaml", "type: object\n");
+        let options = CompilerOptions {
+            log_dir: None,
+            disambiguate_names: true,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+
+        assert_eq!(
+            output.files[0].content,
+            "import \"./store/Account.tsp\";
+
+model Account {
+  store?: Account2;
+}
+"
+        );
+        assert!(output.files[1].content.contains("model Account2 {}"));
+    }
+
+    #[test]
+    fn test_compile_files_with_non_ascii_component_names() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Shop
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    ペット:
+      type: object
+      properties:
+        名前:
+          type: string
+    飼い主:
+      type: object
+      properties:
+        ペット:
+          $ref: '#/components/schemas/ペット'
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+
+        assert!(output.diagnostics.is_empty());
+        let content = &output.files[0].content;
+        assert!(content.contains("model ペット {\n  名前?: string;\n}\n"));
+        assert!(content.contains("model 飼い主 {\n  ペット?: ペット;\n}\n"));
+        assert!(!content.contains("model _"));
+    }
+
+    #[test]
+    fn test_compile_files_with_aliased_component_refs() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Shop
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      $ref: ./models/Pet.yaml
+    Animal:
+      $ref: ./models/Pet.yaml
+    Owner:
+      $ref: ./models/Person.yaml
+",
+        );
+        source.insert("/shop/models/Pet.yaml", "type: object\n");
+        source.insert("/shop/models/Person.yaml", "type: object\n");
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+
+        assert!(output.diagnostics.is_empty());
+        let content = &output.files[2].content;
+        assert!(content.contains("alias Animal = Pet;\n"));
+        assert!(content.contains("alias Owner = Person;\n"));
+        assert!(!content.contains("alias Pet"));
+        assert!(!content.contains("UnknownModelAlias"));
+    }
+
+    #[test]
+    fn test_compile_files_with_string_format_parameters() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/orders/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Orders
+  version: 1.0.0
+paths:
+  /orders/{orderId}:
+    $ref: ./paths/order.yaml
+",
+        );
+        source.insert(
+            "/orders/paths/order.yaml",
+            "get:
+  operationId: getOrder
+  parameters:
+    - name: orderId
+      in: path
+      required: true
+      schema:
+        type: string
+        format: uuid
+  responses:
+    '200':
+      description: ok
+      headers:
+        X-Request-Id:
+          schema:
+            type: string
+            format: uuid
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/orders"), &options);
+
+        let content = &output.files[1].content;
+        assert!(content.contains("@path @format(\"uuid\") orderId: string"));
+        assert!(content.contains(
+            "      @header(\"X-Request-Id\")
+      @format(\"uuid\")
+      xRequestId?: string;
+"
+        ));
+    }
+
+    fn build_multi_example_source() -> MemoryFileSource {
+        let not_found = "    '404':
+      description: not found
+      content:
+        application/json:
+          schema:
+            type: object
+            properties:
+              message:
+                type: string
+          examples:
+            missing:
+              value:
+                message: missing
+            deleted:
+              value:
+                message: deleted
+            expired:
+              value:
+                message: expired
+";
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/shop/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Shop
+  version: 1.0.0
+paths:
+  /orders:
+    $ref: ./paths/orders.yaml
+  /items:
+    $ref: ./paths/items.yaml
+",
+        );
+        source.insert(
+            "/shop/paths/orders.yaml",
+            format!(
+                "get:
+  operationId: listOrders
+  responses:
+    '200':
+      description: ok
+{}",
+                not_found
+            ),
+        );
+        source.insert(
+            "/shop/paths/items.yaml",
+            format!(
+                "get:
+  operationId: listItems
+  responses:
+    '200':
+      description: ok
+{}",
+                not_found
+            ),
+        );
+        source
+    }
+
+    // NOTE: examples の順序に関係なく、毎回同じレスポンスが共通の model になる
+    #[test]
+    fn test_compile_files_with_shared_multi_example_responses() {
+        let source = build_multi_example_source();
+
+        for named_responses in [
+            None,
+            Some(NamedResponses::SameFile),
+            Some(NamedResponses::SharedFile),
+        ] {
+            let options = CompilerOptions {
+                log_dir: None,
+                named_responses,
+                ..CompilerOptions::default()
+            };
+            let expected = compile_files(&source, &PathBuf::from("/shop"), &options);
+            assert!(expected
+                .files
+                .iter()
+                .any(|file| file.content.contains("NotFoundResponse")));
+
+            for _ in 0..10 {
+                let output = compile_files(&source, &PathBuf::from("/shop"), &options);
+                assert_eq!(
+                    output
+                        .files
+                        .iter()
+                        .map(|file| (&file.path, &file.content))
+                        .collect::<Vec<_>>(),
+                    expected
+                        .files
+                        .iter()
+                        .map(|file| (&file.path, &file.content))
+                        .collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_compile_files_with_error_responses() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/pets/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+  /owners:
+    $ref: ./paths/owners.yaml
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+    Problem:
+      type: object
+      properties:
+        message:
+          type: string
+",
+        );
+        source.insert(
+            "/pets/paths/pets.yaml",
+            "get:
+  operationId: listPets
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Pet'
+    '404':
+      description: not found
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Problem'
+    '409':
+      description: conflict
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Pet'
+    '429':
+      description: too many requests
+    '503':
+      description: unavailable
+    4XX:
+      description: client error
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Problem'
+    default:
+      description: unexpected
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Problem'
+",
+        );
+        source.insert(
+            "/pets/paths/owners.yaml",
+            "get:
+  operationId: listOwners
+  responses:
+    '200':
+      description: ok
+    '404':
+      description: not found
+      content:
+        application/json:
+          schema:
+            $ref: '../openapi.yaml#/components/schemas/Problem'
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/pets"), &options);
+
+        assert!(output.diagnostics.is_empty());
+        let root = &output.files[0].content;
+        assert!(root.contains("model Pet {"));
+        assert!(!root.contains("@error\nmodel Pet {"));
+        assert!(root.contains("@error\nmodel Problem {"));
+        assert!(root.contains("model NotFoundResponse {\n  @statusCode\n  statusCode: 404;"));
+        let owners = &output.files[1].content;
+        assert!(owners.contains("| NotFoundResponse;"));
+        let pets = &output.files[2].content;
+        assert!(pets.contains("| NotFoundResponse\n"));
+        assert!(pets.contains("statusCode: 409;"));
+        assert!(pets.contains("statusCode: 429;"));
+        assert!(pets.contains("statusCode: 503;"));
+        assert!(pets.contains(
+            "@statusCode\n          @minValue(400)\n          @maxValue(499)\n          statusCode: int32;"
+        ));
+        // NOTE: default はステータスコードを持たないので @error を付けた model に切り出す
+        assert!(pets.contains("| PetsGetDefaultResponse;"));
+        assert!(pets.contains(
+            "@doc(\"unexpected\")\n@error\nmodel PetsGetDefaultResponse {\n  @header\n  contentType: \"application/json\";\n\n  @body\n  body: Problem;\n}"
+        ));
+
+        let options = CompilerOptions {
+            named_responses: Some(NamedResponses::SameFile),
+            ..options
+        };
+        let output = compile_files(&source, &PathBuf::from("/pets"), &options);
+
+        assert!(output.diagnostics.is_empty());
+        let pets = &output.files[2].content;
+        assert!(pets.contains("@error\nmodel PetsGetDefaultResponse {"));
+        assert!(!pets.contains("@error\nmodel PetsGetOkResponse {"));
+    }
+
+    #[test]
+    fn test_compile_files_with_split_swagger2() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/pets/swagger.yaml",
+            "swagger: '2.0'
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+securityDefinitions:
+  api_key:
+    type: apiKey
+    in: header
+    name: X-API-Key
+",
+        );
+        source.insert(
+            "/pets/paths/pets.yaml",
+            "get:
+  operationId: listPets
+  parameters:
+    - $ref: ../parameters/limit.yaml
+  responses:
+    200:
+      $ref: ../responses/PetList.yaml
+post:
+  operationId: createPet
+  parameters:
+    - $ref: ../parameters/pet_body.yaml
+  responses:
+    201:
+      description: created
+",
+        );
+        source.insert(
+            "/pets/parameters/limit.yaml",
+            "in: query
+name: limit
+type: integer
+",
+        );
+        source.insert(
+            "/pets/parameters/pet_body.yaml",
+            "in: body
+name: pet
+required: true
+schema:
+  $ref: ../definitions/Pet.yaml
+",
+        );
+        source.insert(
+            "/pets/responses/PetList.yaml",
+            "description: ok
+schema:
+  type: array
+  items:
+    $ref: ../definitions/Pet.yaml
+",
+        );
+        source.insert(
+            "/pets/definitions/Pet.yaml",
+            "type: object
+properties:
+  name:
+    type: string
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/pets"), &options);
+
+        assert_eq!(
+            output
+                .diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["securityDefinitions are not converted, no @useAuth is emitted"]
+        );
+        assert_eq!(
+            output
+                .files
+                .iter()
+                .map(|file| file.path.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                "/pets/definitions/Pet.tsp",
+                "/pets/parameters/limit.tsp",
+                "/pets/paths/pets.tsp",
+                "/pets/responses/PetList.tsp",
+                "/pets/swagger.tsp",
+            ]
+        );
+        assert!(output.files[1].content.contains("@query\n  limit?: int32;"));
+        let paths = &output.files[2].content;
+        assert!(paths.contains("list(...LimitParameter)"));
+        assert!(paths.contains("create(@body body: Pet)"));
+        assert!(output.files[3]
+            .content
+            .contains("contentType: \"application/json\";\n\n  @body\n  body: Pet[];"));
+    }
+
+    #[test]
+    fn test_compile_files_with_request_bodies() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/pets/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+components:
+  requestBodies:
+    NewPet:
+      description: new pet
+      required: true
+      content:
+        application/json:
+          schema:
+            type: object
+            properties:
+              name:
+                type: string
+",
+        );
+        source.insert(
+            "/pets/paths/pets.yaml",
+            "post:
+  operationId: createPet
+  requestBody:
+    required: true
+    content:
+      application/json:
+        schema:
+          type: object
+          properties:
+            name:
+              type: string
+  responses:
+    '204':
+      description: created
+put:
+  operationId: uploadPhoto
+  requestBody:
+    content:
+      multipart/form-data:
+        schema:
+          type: object
+          required:
+            - photo
+          properties:
+            photo:
+              type: string
+              format: binary
+            caption:
+              type: string
+  responses:
+    '204':
+      description: uploaded
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            report: true,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/pets"), &options);
+
+        assert!(output.diagnostics.is_empty());
+        assert!(output.files[0]
+            .content
+            .contains("model NewPetRequestBody {\n  @body\n  body: {\n"));
+        let content = &output.files[1].content;
+        assert!(content
+            .contains("create(\n      @body body: {\n        name?: string;\n      },\n    )"));
+        assert!(content.contains(
+            "      @header contentType: \"multipart/form-data\",
+      @multipartBody body?: {
+        photo: HttpPart<File>;
+        caption?: HttpPart<string>;
+      },
+"
+        ));
+        // NOTE: required は body が省略可能かどうかとして出力されるので失われない
+        let report = output.report.unwrap();
+        assert!(report
+            .files
+            .iter()
+            .flat_map(|file| file.dropped_keys.iter())
+            .all(|key| !key.ends_with(".required")));
+    }
+
+    #[test]
+    fn test_compile_files_with_split_component_files() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/pets/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+",
+        );
+        source.insert(
+            "/pets/paths/pets.yaml",
+            "post:
+  operationId: createPet
+  requestBody:
+    $ref: ../bodies/NewPet.yaml
+  responses:
+    '200':
+      $ref: ../bodies/Pet.yaml
+",
+        );
+        source.insert(
+            "/pets/bodies/NewPet.yaml",
+            "description: new pet
+content:
+  application/json:
+    schema:
+      type: object
+      properties:
+        name:
+          type: string
+",
+        );
+        source.insert(
+            "/pets/bodies/Pet.yaml",
+            "description: pet
+content:
+  application/json:
+    schema:
+      type: object
+      properties:
+        id:
+          type: string
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/pets"), &options);
+
+        // NOTE: 同じ形のファイルでも、requestBody から参照されたものは request body になる
+        assert!(output.diagnostics.is_empty());
+        assert!(output.files[0]
+            .content
+            .contains("model NewPetRequestBody {\n  @body\n  body?: {"));
+        assert!(output.files[1].content.contains("model PetResponse {"));
+        assert!(output.files[3]
+            .content
+            .contains("create(...NewPetRequestBody)"));
+    }
+
+    #[test]
+    fn test_compile_files_with_hoist_inline_models() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/pets/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner:
+          type: object
+          properties:
+            address:
+              type: object
+              properties:
+                city:
+                  type: string
+",
+        );
+        let compile = |hoist_inline_models: bool| {
+            let options = CompilerOptions {
+                log_dir: None,
+                hoist_inline_models,
+                ..CompilerOptions::default()
+            };
+            compile_files(&source, &PathBuf::from("/pets"), &options)
+        };
+
+        // NOTE: 切り出さない場合は inline のまま出力する
+        assert!(compile(false).files[0].content.ends_with(
+            "model Pet {
+  owner?: {
+    address?: {
+      city?: string;
+    };
+  };
+}
+"
+        ));
+        assert!(compile(true).files[0].content.ends_with(
+            "model Pet {
+  owner?: PetOwner;
+}
+
+model PetOwner {
+  address?: PetOwnerAddress;
+}
+
+model PetOwnerAddress {
+  city?: string;
+}
+"
+        ));
+    }
+
+    #[test]
+    fn test_compile_files_with_response_headers() {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/pets/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+components:
+  headers:
+    RateLimitRemaining:
+      description: remaining requests
+      required: true
+      schema:
+        type: integer
+    RateLimitPolicy:
+      schema:
+        type: array
+        items:
+          type: string
+",
+        );
+        source.insert(
+            "/pets/paths/pets.yaml",
+            "get:
+  operationId: listPets
+  responses:
+    '200':
+      description: ok
+      headers:
+        ETag:
+          required: true
+          schema:
+            type: string
+        Link:
+          description: next page
+          schema:
+            type: string
+        X-RateLimit-Remaining:
+          $ref: '../openapi.yaml#/components/headers/RateLimitRemaining'
+        X-RateLimit-Policy:
+          $ref: '../openapi.yaml#/components/headers/RateLimitPolicy'
+",
+        );
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+
+        let output = compile_files(&source, &PathBuf::from("/pets"), &options);
+
+        // NOTE: $ref の header はレスポンスの header 名で展開する
+        assert!(output.diagnostics.is_empty());
+        // NOTE: header の component には型と説明だけを出力し、component のキーをヘッダー名にしない
+        assert!(output.files[0].content.ends_with(
+            "@doc(\"remaining requests\")
+scalar RateLimitRemainingHeader extends int32;
+
+alias RateLimitPolicyHeader = string[];
+"
+        ));
+        assert!(!output.files[0].content.contains("@header"));
+        assert!(output.files[1].content.contains(
+            "      @header(\"ETag\")
+      eTag: string;
+
+      @doc(\"next page\")
+      @header(\"Link\")
+      link?: string;
+
+      @doc(\"remaining requests\")
+      @header(\"X-RateLimit-Remaining\")
+      xRateLimitRemaining: int32;
+
+      @header(\"X-RateLimit-Policy\")
+      xRateLimitPolicy?: string[];
+"
+        ));
+    }
 }
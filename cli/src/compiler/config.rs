@@ -0,0 +1,475 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use yaml_rust::{yaml, Yaml, YamlLoader};
+
+use crate::compiler::{CompilerOptions, ExtensionHandling, NamedResponses, TSP_CONFIG_FILE_NAME};
+use crate::type_spec::node::is_valid_identifier;
+
+// NOTE: ルートの spec と同じディレクトリに置く設定ファイル
+//       変換対象の YAML と区別するため、yaml_loader はこの名前のファイルを読み飛ばす
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["openapi2tsp.yaml", "openapi2tsp.yml"];
+
+// NOTE: emit_project で出力する tspconfig.yaml も変換対象の YAML ではないので読み飛ばす
+pub fn is_config_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| CONFIG_FILE_NAMES.contains(&name) || name == TSP_CONFIG_FILE_NAME)
+        .unwrap_or(false)
+}
+
+pub fn find_config_file(root: &Path) -> Option<PathBuf> {
+    let dir = if root.is_file() { root.parent()? } else { root };
+
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub messages: Vec<String>,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid config file {}:", self.path.display())?;
+        for message in self.messages.iter() {
+            write!(f, "\n  - {}", message)?;
+        }
+
+        Ok(())
+    }
+}
+
+const ROOT_KEYS: [&str; 6] = [
+    "namespace",
+    "naming",
+    "type_mappings",
+    "extensions",
+    "output",
+    "strict",
+];
+
+const NAMING_KEYS: [&str; 4] = [
+    "operations",
+    "fallback_model",
+    "fallback_model_alias",
+    "fallback_component_ref",
+];
+
+const OPERATION_KEYS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+const OUTPUT_KEYS: [&str; 11] = [
+    "hoist_inline_models",
+    "nested_namespaces",
+    "disambiguate_names",
+    "emit_project",
+    "open_enums",
+    "named_responses",
+    "indent_width",
+    "max_line_width",
+    "trailing_newline",
+    "logs",
+    "log_dir",
+];
+
+fn build_key_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn check_keys(hash: &yaml::Hash, keys: &[&str], parent: &str, errors: &mut Vec<String>) {
+    for key in hash.keys() {
+        match key.as_str() {
+            Some(key) if keys.contains(&key) => {}
+            Some(key) => errors.push(format!(
+                "unknown key `{}` (expected one of: {})",
+                build_key_path(parent, key),
+                keys.join(", ")
+            )),
+            None => errors.push(format!("`{}` has a non-string key: {:?}", parent, key)),
+        }
+    }
+}
+
+fn get_yaml<'a>(hash: &'a yaml::Hash, key: &str) -> Option<&'a Yaml> {
+    hash.get(&Yaml::String(key.to_string()))
+}
+
+fn read_string(
+    hash: &yaml::Hash,
+    parent: &str,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<String> {
+    match get_yaml(hash, key)? {
+        Yaml::String(s) if !s.is_empty() => Some(s.clone()),
+        _ => {
+            errors.push(format!(
+                "`{}` must be a non-empty string",
+                build_key_path(parent, key)
+            ));
+            None
+        }
+    }
+}
+
+fn read_identifier(
+    hash: &yaml::Hash,
+    parent: &str,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<String> {
+    let value = read_string(hash, parent, key, errors)?;
+    if is_valid_identifier(&value) {
+        Some(value)
+    } else {
+        errors.push(format!(
+            "`{}` must be a valid TypeSpec identifier, got `{}`",
+            build_key_path(parent, key),
+            value
+        ));
+        None
+    }
+}
+
+fn read_bool(hash: &yaml::Hash, parent: &str, key: &str, errors: &mut Vec<String>) -> Option<bool> {
+    match get_yaml(hash, key)? {
+        Yaml::Boolean(b) => Some(*b),
+        _ => {
+            errors.push(format!(
+                "`{}` must be a boolean",
+                build_key_path(parent, key)
+            ));
+            None
+        }
+    }
+}
+
+fn read_positive_integer(
+    hash: &yaml::Hash,
+    parent: &str,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<usize> {
+    match get_yaml(hash, key)? {
+        Yaml::Integer(i) if *i > 0 => Some(*i as usize),
+        _ => {
+            errors.push(format!(
+                "`{}` must be a positive integer",
+                build_key_path(parent, key)
+            ));
+            None
+        }
+    }
+}
+
+fn read_hash<'a>(
+    hash: &'a yaml::Hash,
+    parent: &str,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<&'a yaml::Hash> {
+    match get_yaml(hash, key)? {
+        Yaml::Hash(hash) => Some(hash),
+        _ => {
+            errors.push(format!(
+                "`{}` must be a mapping",
+                build_key_path(parent, key)
+            ));
+            None
+        }
+    }
+}
+
+fn read_value_enum<T: ValueEnum>(
+    hash: &yaml::Hash,
+    parent: &str,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<T> {
+    let value = read_string(hash, parent, key, errors)?;
+    match T::from_str(&value, false) {
+        Ok(value) => Some(value),
+        Err(_) => {
+            let variants = T::value_variants()
+                .iter()
+                .filter_map(|variant| variant.to_possible_value())
+                .map(|variant| variant.get_name().to_string())
+                .collect::<Vec<_>>();
+            errors.push(format!(
+                "`{}` must be one of: {}, got `{}`",
+                build_key_path(parent, key),
+                variants.join(", "),
+                value
+            ));
+            None
+        }
+    }
+}
+
+// NOTE: TypeSpec の名前空間は `.` 区切りで入れ子にできる
+fn apply_namespace(hash: &yaml::Hash, options: &mut CompilerOptions, errors: &mut Vec<String>) {
+    if let Some(namespace) = read_string(hash, "", "namespace", errors) {
+        if namespace.split('.').all(is_valid_identifier) {
+            options.namespace = Some(namespace);
+        } else {
+            errors.push(format!(
+                "`namespace` must be a valid TypeSpec namespace, got `{}`",
+                namespace
+            ));
+        }
+    }
+}
+
+fn apply_naming(hash: &yaml::Hash, options: &mut CompilerOptions, errors: &mut Vec<String>) {
+    let naming = match read_hash(hash, "", "naming", errors) {
+        Some(naming) => naming,
+        None => return,
+    };
+    check_keys(naming, &NAMING_KEYS, "naming", errors);
+
+    if let Some(operations) = read_hash(naming, "naming", "operations", errors) {
+        let parent = "naming.operations";
+        check_keys(operations, &OPERATION_KEYS, parent, errors);
+        let names = &mut options.naming.operations;
+        for (key, name) in [
+            ("get", &mut names.get),
+            ("post", &mut names.post),
+            ("put", &mut names.put),
+            ("patch", &mut names.patch),
+            ("delete", &mut names.delete),
+        ] {
+            if let Some(value) = read_identifier(operations, parent, key, errors) {
+                *name = value;
+            }
+        }
+    }
+    let naming_options = &mut options.naming;
+    for (key, name) in [
+        ("fallback_model", &mut naming_options.fallback_model),
+        (
+            "fallback_model_alias",
+            &mut naming_options.fallback_model_alias,
+        ),
+        (
+            "fallback_component_ref",
+            &mut naming_options.fallback_component_ref,
+        ),
+    ] {
+        if let Some(value) = read_identifier(naming, "naming", key, errors) {
+            *name = value;
+        }
+    }
+}
+
+fn apply_type_mappings(hash: &yaml::Hash, options: &mut CompilerOptions, errors: &mut Vec<String>) {
+    let type_mappings = match read_hash(hash, "", "type_mappings", errors) {
+        Some(type_mappings) => type_mappings,
+        None => return,
+    };
+
+    for key in type_mappings.keys() {
+        let format = match key.as_str() {
+            Some(format) => format,
+            None => {
+                errors.push(format!("`type_mappings` has a non-string key: {:?}", key));
+                continue;
+            }
+        };
+        if let Some(type_name) = read_string(type_mappings, "type_mappings", format, errors) {
+            if type_name.split('.').all(is_valid_identifier) {
+                options.type_mappings.insert(format.to_string(), type_name);
+            } else {
+                errors.push(format!(
+                    "`type_mappings.{}` must be a TypeSpec type name, got `{}`",
+                    format, type_name
+                ));
+            }
+        }
+    }
+}
+
+fn apply_output(
+    hash: &yaml::Hash,
+    config_dir: &Path,
+    options: &mut CompilerOptions,
+    errors: &mut Vec<String>,
+) {
+    let output = match read_hash(hash, "", "output", errors) {
+        Some(output) => output,
+        None => return,
+    };
+    let parent = "output";
+    check_keys(output, &OUTPUT_KEYS, parent, errors);
+
+    if let Some(value) = read_bool(output, parent, "hoist_inline_models", errors) {
+        options.hoist_inline_models = value;
+    }
+    if let Some(value) = read_bool(output, parent, "nested_namespaces", errors) {
+        options.nested_namespaces = value;
+    }
+    if let Some(value) = read_bool(output, parent, "disambiguate_names", errors) {
+        options.disambiguate_names = value;
+    }
+    if let Some(value) = read_bool(output, parent, "emit_project", errors) {
+        options.emit_project = value;
+    }
+    if let Some(value) = read_bool(output, parent, "open_enums", errors) {
+        options.open_enums = value;
+    }
+    if let Some(value) =
+        read_value_enum::<NamedResponses>(output, parent, "named_responses", errors)
+    {
+        options.named_responses = Some(value);
+    }
+    if let Some(value) = read_positive_integer(output, parent, "indent_width", errors) {
+        options.print_options.indent_width = value;
+    }
+    if let Some(value) = read_positive_integer(output, parent, "max_line_width", errors) {
+        options.print_options.max_line_width = value;
+    }
+    if let Some(value) = read_bool(output, parent, "trailing_newline", errors) {
+        options.print_options.trailing_newline = value;
+    }
+    // NOTE: log_dir は設定ファイルからの相対パスとして扱う
+    if let Some(value) = read_string(output, parent, "log_dir", errors) {
+        options.log_dir = Some(config_dir.join(value));
+    }
+    if let Some(false) = read_bool(output, parent, "logs", errors) {
+        options.log_dir = None;
+    }
+}
+
+fn apply_config(
+    hash: &yaml::Hash,
+    config_dir: &Path,
+    options: &mut CompilerOptions,
+    errors: &mut Vec<String>,
+) {
+    check_keys(hash, &ROOT_KEYS, "", errors);
+
+    apply_namespace(hash, options, errors);
+    apply_naming(hash, options, errors);
+    apply_type_mappings(hash, options, errors);
+    if let Some(value) = read_value_enum::<ExtensionHandling>(hash, "", "extensions", errors) {
+        options.extensions = value;
+    }
+    apply_output(hash, config_dir, options, errors);
+    if let Some(value) = read_bool(hash, "", "strict", errors) {
+        options.strict = value;
+    }
+}
+
+pub fn apply_config_file(path: &Path, options: &mut CompilerOptions) -> Result<(), ConfigError> {
+    let build_error = |messages: Vec<String>| ConfigError {
+        path: path.to_path_buf(),
+        messages,
+    };
+    let content = fs::read_to_string(path).map_err(|e| build_error(vec![e.to_string()]))?;
+    let documents =
+        YamlLoader::load_from_str(&content).map_err(|e| build_error(vec![e.to_string()]))?;
+    let config_dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut errors = vec![];
+    match documents.first() {
+        // NOTE: 空の設定ファイルは既定値のまま使う
+        None | Some(Yaml::Null) => {}
+        Some(Yaml::Hash(hash)) => apply_config(hash, config_dir, options, &mut errors),
+        Some(_) => errors.push("the top level must be a mapping".to_string()),
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(build_error(errors))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn apply(content: &str) -> Result<CompilerOptions, Vec<String>> {
+        let documents = YamlLoader::load_from_str(content).unwrap();
+        let mut options = CompilerOptions::default();
+        let mut errors = vec![];
+        apply_config(
+            documents[0].as_hash().unwrap(),
+            Path::new("/project"),
+            &mut options,
+            &mut errors,
+        );
+
+        if errors.is_empty() {
+            Ok(options)
+        } else {
+            Err(errors)
+        }
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let options = apply(
+            r#"
+namespace: PetStore.V1
+naming:
+  operations:
+    get: read
+type_mappings:
+  uuid: uuid
+extensions: drop
+output:
+  named_responses: shared-file
+  max_line_width: 100
+  log_dir: logs
+strict: true
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(options.namespace, Some("PetStore.V1".to_string()));
+        assert_eq!(options.naming.operations.get, "read");
+        assert_eq!(options.naming.operations.post, "create");
+        assert_eq!(options.type_mappings.get("uuid"), Some(&"uuid".to_string()));
+        assert_eq!(options.extensions, ExtensionHandling::Drop);
+        assert_eq!(options.named_responses, Some(NamedResponses::SharedFile));
+        assert_eq!(options.print_options.max_line_width, 100);
+        assert_eq!(options.log_dir, Some(PathBuf::from("/project/logs")));
+        assert!(options.strict);
+    }
+
+    #[test]
+    fn test_apply_config_errors() {
+        let errors = apply(
+            r#"
+namespace: 1st
+naming:
+  operations:
+    head: read
+output:
+  named_responses: inline
+  indent_width: -1
+"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "`namespace` must be a valid TypeSpec namespace, got `1st`",
+                "unknown key `naming.operations.head` (expected one of: get, post, put, patch, delete)",
+                "`output.named_responses` must be one of: same-file, shared-file, got `inline`",
+                "`output.indent_width` must be a positive integer",
+            ]
+        );
+    }
+}
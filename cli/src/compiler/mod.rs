@@ -1,4 +1,19 @@
+#[cfg(test)]
+mod benchmark;
+mod check;
+#[allow(clippy::module_inception)]
 mod compiler;
+mod config;
 mod parse_postprocess;
+mod project;
+mod report;
+mod validate;
+mod watch;
 
+pub use check::*;
 pub use compiler::*;
+pub use config::*;
+pub use project::*;
+pub use report::*;
+pub use validate::*;
+pub use watch::*;
@@ -1,24 +1,28 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::compiler::FilePathObjectMap;
+use crate::compiler::{CompilerEnv, ObjectFilePathMap};
+use crate::file_source::normalize_path;
 use crate::openapi_parser::node::*;
+use crate::type_spec::node_builder::{build_component_model_name, to_pascal_case_with_hyphens};
 
 pub fn remove_examples(file_nodes: &mut Vec<OpenAPIFileNode>) {
     file_nodes.retain(|file_node| {
-        file_node.contents.iter().all(|node| {
-            if let OpenAPINode::Example(_) = node {
-                false
-            } else {
-                true
-            }
-        })
+        file_node
+            .contents
+            .iter()
+            .all(|node| !matches!(node, OpenAPINode::Example(_)))
     });
 }
 
+// NOTE: swagger 2.0 の body parameter のファイルのように、中身が何も残らなかったファイルは出力しない
+pub fn remove_empty_files(file_nodes: &mut Vec<OpenAPIFileNode>) {
+    file_nodes.retain(|file_node| !file_node.contents.is_empty());
+}
+
 // =================================================================================================
 
-fn list_parameter_nodes(file_node: &OpenAPIFileNode) -> Vec<ParameterNode> {
+fn list_parameter_nodes(file_node: &OpenAPIFileNode) -> Vec<ReferenceOr<ParameterNode>> {
     file_node
         .contents
         .iter()
@@ -33,9 +37,16 @@ fn list_parameter_nodes(file_node: &OpenAPIFileNode) -> Vec<ParameterNode> {
         .collect()
 }
 
+fn get_parameter_key(parameter: &ReferenceOr<ParameterNode>) -> &String {
+    match parameter {
+        ReferenceOr::Item(parameter) => &parameter.name,
+        ReferenceOr::Ref(ref_node) => &ref_node.component_name,
+    }
+}
+
 fn insert_parameters_to_operation_node(
     file_node: &mut OpenAPIFileNode,
-    parameters: Vec<ParameterNode>,
+    parameters: Vec<ReferenceOr<ParameterNode>>,
 ) {
     file_node
         .contents
@@ -51,11 +62,11 @@ fn insert_parameters_to_operation_node(
             let parameter_names = operation
                 .parameters
                 .iter()
-                .map(|p| p.name.clone())
+                .map(|p| get_parameter_key(p).clone())
                 .collect::<HashSet<_>>();
             parameters
                 .iter()
-                .filter(|p| !parameter_names.contains(&p.name))
+                .filter(|p| !parameter_names.contains(get_parameter_key(p)))
                 .for_each(|p| {
                     operation.parameters.push(p.clone());
                 });
@@ -63,18 +74,14 @@ fn insert_parameters_to_operation_node(
 }
 
 fn delete_parameters_node(file_node: &mut OpenAPIFileNode) {
-    file_node.contents.retain(|node| {
-        if let OpenAPINode::Parameters(_) = node {
-            false
-        } else {
-            true
-        }
-    });
+    file_node
+        .contents
+        .retain(|node| !matches!(node, OpenAPINode::Parameters(_)));
 }
 
-pub fn merge_parameter_nodes(file_nodes: &mut Vec<OpenAPIFileNode>) {
+pub fn merge_parameter_nodes(file_nodes: &mut [OpenAPIFileNode]) {
     file_nodes.iter_mut().for_each(|file_node| {
-        let parameters = list_parameter_nodes(&file_node);
+        let parameters = list_parameter_nodes(file_node);
         insert_parameters_to_operation_node(file_node, parameters);
         delete_parameters_node(file_node);
     });
@@ -82,69 +89,276 @@ pub fn merge_parameter_nodes(file_nodes: &mut Vec<OpenAPIFileNode>) {
 
 // =================================================================================================
 
+// NOTE: callback や webhook の operation も通常の operation と同じように後処理する
+fn for_each_nested_operation_node_mut(
+    operation: &mut OperationNode,
+    f: &mut dyn FnMut(&mut OperationNode),
+) {
+    f(operation);
+    operation
+        .callbacks
+        .iter_mut()
+        .flat_map(|callback| callback.operations.iter_mut())
+        .for_each(|operation| for_each_nested_operation_node_mut(operation, f));
+}
+
+fn for_each_operation_node_mut(node: &mut OpenAPINode, f: &mut dyn FnMut(&mut OperationNode)) {
+    match node {
+        OpenAPINode::Operation(operation) => for_each_nested_operation_node_mut(operation, f),
+        OpenAPINode::Webhook(webhook) => webhook
+            .operations
+            .iter_mut()
+            .for_each(|operation| for_each_nested_operation_node_mut(operation, f)),
+        _ => {}
+    }
+}
+
+fn for_each_nested_data_model_node_mut(
+    node: &mut DataModelNode,
+    f: &mut dyn FnMut(&mut DataModelNode),
+) {
+    f(node);
+    match node {
+        DataModelNode::Array(array) => for_each_nested_data_model_node_mut(&mut array.items, f),
+        DataModelNode::Object(object) => object
+            .properties
+            .iter_mut()
+            .for_each(|property| for_each_nested_data_model_node_mut(&mut property.value, f)),
+        DataModelNode::OneOf(one_of) => one_of
+            .items
+            .iter_mut()
+            .for_each(|item| for_each_nested_data_model_node_mut(item, f)),
+        DataModelNode::AllOf(all_of) => all_of
+            .items
+            .iter_mut()
+            .for_each(|item| for_each_nested_data_model_node_mut(item, f)),
+        DataModelNode::Tuple(tuple) => tuple
+            .items
+            .iter_mut()
+            .for_each(|item| for_each_nested_data_model_node_mut(item, f)),
+        _ => {}
+    }
+}
+
+fn for_each_response_data_model_node_mut(
+    response: &mut ResponseObjectNode,
+    f: &mut dyn FnMut(&mut DataModelNode),
+) {
+    if let Some(schema) = &mut response.schema {
+        for_each_nested_data_model_node_mut(schema, f);
+    }
+    response.headers.iter_mut().for_each(|header| {
+        if let ReferenceOr::Item(header) = &mut header.header {
+            for_each_nested_data_model_node_mut(&mut header.schema, f);
+        }
+    });
+}
+
+// NOTE: スキーマ、component、operation に含まれるすべてのスキーマを入れ子も含めて辿る
+fn for_each_data_model_node_mut(node: &mut OpenAPINode, f: &mut dyn FnMut(&mut DataModelNode)) {
+    match node {
+        OpenAPINode::DataModel(data) => for_each_nested_data_model_node_mut(data, f),
+        OpenAPINode::Component(component) => match &mut component.content {
+            ComponentContentNode::Parameter(parameter) => {
+                for_each_nested_data_model_node_mut(&mut parameter.schema, f)
+            }
+            ComponentContentNode::Response(response) => {
+                for_each_response_data_model_node_mut(response, f)
+            }
+            ComponentContentNode::Header(header) => {
+                for_each_nested_data_model_node_mut(&mut header.schema, f)
+            }
+            ComponentContentNode::RequestBody(request_body) => {
+                for_each_nested_data_model_node_mut(&mut request_body.schema, f)
+            }
+            ComponentContentNode::Ref(_) => {}
+        },
+        OpenAPINode::Operation(_) | OpenAPINode::Webhook(_) => {
+            for_each_operation_node_mut(node, &mut |operation| {
+                operation.parameters.iter_mut().for_each(|parameter| {
+                    if let ReferenceOr::Item(parameter) = parameter {
+                        for_each_nested_data_model_node_mut(&mut parameter.schema, f);
+                    }
+                });
+                if let Some(ReferenceOr::Item(request_body)) = &mut operation.request_body {
+                    for_each_nested_data_model_node_mut(&mut request_body.schema, f);
+                }
+                operation.responses.iter_mut().for_each(|response| {
+                    if let ReferenceOr::Item(response) = &mut response.response {
+                        for_each_response_data_model_node_mut(response, f);
+                    }
+                });
+            })
+        }
+        _ => {}
+    }
+}
+
+// =================================================================================================
+
+// NOTE: 設定の type_mappings に一致する format の string は、format の代わりに指定された型で出力する
+pub fn apply_type_mappings(
+    file_nodes: &mut [OpenAPIFileNode],
+    type_mappings: &HashMap<String, String>,
+) {
+    file_nodes
+        .iter_mut()
+        .flat_map(|file_node| file_node.contents.iter_mut())
+        .for_each(|node| {
+            for_each_data_model_node_mut(node, &mut |data_model| {
+                if let DataModelNode::String(string) = data_model {
+                    string.mapped_type = string
+                        .format
+                        .as_ref()
+                        .and_then(|format| type_mappings.get(&format.to_string()))
+                        .cloned();
+                }
+            })
+        });
+}
+
+// NOTE: links は TypeSpec に対応する構文がなく @extension でしか出力できないので、extensions: drop の場合は捨てる
+pub fn remove_links(file_nodes: &mut [OpenAPIFileNode]) {
+    file_nodes
+        .iter_mut()
+        .flat_map(|file_node| file_node.contents.iter_mut())
+        .for_each(|node| match node {
+            OpenAPINode::Component(component) => {
+                if let ComponentContentNode::Response(response) = &mut component.content {
+                    response.links.clear();
+                }
+            }
+            OpenAPINode::Operation(_) | OpenAPINode::Webhook(_) => {
+                for_each_operation_node_mut(node, &mut |operation| {
+                    operation.responses.iter_mut().for_each(|response| {
+                        if let ReferenceOr::Item(response) = &mut response.response {
+                            response.links.clear();
+                        }
+                    })
+                })
+            }
+            _ => {}
+        });
+}
+
+// =================================================================================================
+
 fn replace_file_ref_to_compiler_ref_in_ref_node(
     ref_node: &mut RefNode,
-    current_file_path: &PathBuf,
-    map: &FilePathObjectMap,
+    current_file_path: &Path,
+    env: &CompilerEnv,
 ) {
     if let RefNode::FileRef(file_ref) = ref_node {
         let base_dir = current_file_path
             .parent()
             .expect("Failed to get parent dir");
-        let target_path = base_dir
-            .join(Path::new(&file_ref.file_path))
-            .canonicalize()
-            .expect("Failed to canonicalize")
+        let target_path = normalize_path(&base_dir.join(Path::new(&file_ref.file_path)))
             .to_str()
             .map(|s| s.to_string())
             .expect("Failed to convert to string");
-        let component_name = map
+        let component_name = env
+            .file_path_object_map
             .get(&target_path)
             .and_then(|node| node.title())
-            .unwrap_or("AnonymousComponentRef".to_string());
+            .unwrap_or_else(|| env.options.naming.fallback_component_ref.clone());
+        // NOTE: 参照先のファイルが別の名前空間にある場合だけ完全修飾名で参照する
+        let namespace = Some(env.get_file_namespace(Path::new(&target_path)))
+            .filter(|namespace| namespace != &env.get_file_namespace(current_file_path));
 
-        *ref_node = RefNode::ComponentRef(ComponentRefNode { component_name })
+        *ref_node = RefNode::ComponentRef(ComponentRefNode {
+            component_name,
+            namespace,
+        })
     }
 }
 fn replace_file_ref_to_compiler_ref_in_operation_node(
     node: &mut OperationNode,
     current_file_path: &PathBuf,
-    map: &FilePathObjectMap,
+    env: &CompilerEnv,
 ) {
     node.parameters.iter_mut().for_each(|parameter| {
-        replace_file_ref_to_compiler_ref_in_data_model_node(
-            &mut parameter.schema,
-            current_file_path,
-            map,
-        );
+        if let ReferenceOr::Item(parameter) = parameter {
+            replace_file_ref_to_compiler_ref_in_data_model_node(
+                &mut parameter.schema,
+                current_file_path,
+                env,
+            );
+        }
     });
 
-    if let Some(request_body) = &mut node.request_body {
+    if let Some(ReferenceOr::Item(request_body)) = &mut node.request_body {
         replace_file_ref_to_compiler_ref_in_data_model_node(
             &mut request_body.schema,
             current_file_path,
-            map,
+            env,
         );
     }
 
     node.responses.iter_mut().for_each(|response| {
-        if let Some(schema) = &mut response.schema {
-            replace_file_ref_to_compiler_ref_in_data_model_node(schema, current_file_path, map);
+        if let ReferenceOr::Item(response) = &mut response.response {
+            replace_file_ref_to_compiler_ref_in_response_object_node(
+                response,
+                current_file_path,
+                env,
+            );
+        }
+    });
+}
+
+fn replace_file_ref_to_compiler_ref_in_response_object_node(
+    node: &mut ResponseObjectNode,
+    current_file_path: &PathBuf,
+    env: &CompilerEnv,
+) {
+    if let Some(schema) = &mut node.schema {
+        replace_file_ref_to_compiler_ref_in_data_model_node(schema, current_file_path, env);
+    }
+    node.headers.iter_mut().for_each(|header| {
+        if let ReferenceOr::Item(header) = &mut header.header {
+            replace_file_ref_to_compiler_ref_in_data_model_node(
+                &mut header.schema,
+                current_file_path,
+                env,
+            );
         }
     });
 }
 
+fn replace_file_ref_to_compiler_ref_in_component_node(
+    node: &mut ComponentNode,
+    current_file_path: &PathBuf,
+    env: &CompilerEnv,
+) {
+    let schema = match &mut node.content {
+        ComponentContentNode::Parameter(parameter) => Some(&mut parameter.schema),
+        ComponentContentNode::Response(response) => {
+            replace_file_ref_to_compiler_ref_in_response_object_node(
+                response,
+                current_file_path,
+                env,
+            );
+            None
+        }
+        ComponentContentNode::Header(header) => Some(&mut header.schema),
+        ComponentContentNode::RequestBody(request_body) => Some(&mut request_body.schema),
+        ComponentContentNode::Ref(_) => None,
+    };
+    if let Some(schema) = schema {
+        replace_file_ref_to_compiler_ref_in_data_model_node(schema, current_file_path, env);
+    }
+}
+
 fn replace_file_ref_to_compiler_ref_in_data_model_node(
     node: &mut DataModelNode,
     current_file_path: &PathBuf,
-    map: &FilePathObjectMap,
+    env: &CompilerEnv,
 ) {
     match node {
         DataModelNode::Array(array) => {
             replace_file_ref_to_compiler_ref_in_data_model_node(
                 &mut array.items,
                 current_file_path,
-                map,
+                env,
             );
         }
         DataModelNode::Object(object) => {
@@ -152,40 +366,1332 @@ fn replace_file_ref_to_compiler_ref_in_data_model_node(
                 replace_file_ref_to_compiler_ref_in_data_model_node(
                     &mut property_node.value,
                     current_file_path,
-                    map,
+                    env,
                 );
             });
         }
         DataModelNode::OneOf(one_of) => {
             one_of.items.iter_mut().for_each(|item| {
-                replace_file_ref_to_compiler_ref_in_data_model_node(item, current_file_path, map);
+                replace_file_ref_to_compiler_ref_in_data_model_node(item, current_file_path, env);
             });
         }
         DataModelNode::AllOf(all_of) => {
             all_of.items.iter_mut().for_each(|item| {
-                replace_file_ref_to_compiler_ref_in_data_model_node(item, current_file_path, map);
+                replace_file_ref_to_compiler_ref_in_data_model_node(item, current_file_path, env);
             });
         }
         DataModelNode::Ref(ref_node) => {
-            replace_file_ref_to_compiler_ref_in_ref_node(ref_node, current_file_path, map);
+            replace_file_ref_to_compiler_ref_in_ref_node(ref_node, current_file_path, env);
         }
         _ => {}
     }
 }
 
-pub fn replace_file_ref_to_component_ref(
-    file_nodes: &mut Vec<OpenAPIFileNode>,
-    map: &FilePathObjectMap,
-) {
+pub fn replace_file_ref_to_component_ref(file_nodes: &mut [OpenAPIFileNode], env: &CompilerEnv) {
     file_nodes.iter_mut().for_each(|file_node| {
         file_node.contents.iter_mut().for_each(|node| match node {
-            OpenAPINode::Operation(node) => {
-                replace_file_ref_to_compiler_ref_in_operation_node(node, &file_node.path, &map)
+            OpenAPINode::Operation(_) | OpenAPINode::Webhook(_) => {
+                for_each_operation_node_mut(node, &mut |operation| {
+                    replace_file_ref_to_compiler_ref_in_operation_node(
+                        operation,
+                        &file_node.path,
+                        env,
+                    )
+                })
             }
             OpenAPINode::DataModel(data) => {
-                replace_file_ref_to_compiler_ref_in_data_model_node(data, &file_node.path, &map)
+                replace_file_ref_to_compiler_ref_in_data_model_node(data, &file_node.path, env)
+            }
+            OpenAPINode::Component(component) => {
+                replace_file_ref_to_compiler_ref_in_component_node(component, &file_node.path, env)
+            }
+            _ => {}
+        });
+    });
+}
+
+// =================================================================================================
+
+fn qualify_component_ref_node(
+    ref_node: &mut ComponentRefNode,
+    kind: Option<&ComponentKind>,
+    current_file_path: &Path,
+    env: &CompilerEnv,
+) {
+    if ref_node.namespace.is_some() {
+        return;
+    }
+    let name = match kind {
+        Some(kind) => build_component_model_name(kind, &ref_node.component_name),
+        None => ref_node.component_name.clone(),
+    };
+    ref_node.namespace = env.resolve_object_namespace(&name, current_file_path);
+}
+
+fn qualify_response_header_refs(
+    response: &mut ResponseObjectNode,
+    current_file_path: &Path,
+    env: &CompilerEnv,
+) {
+    response.headers.iter_mut().for_each(|header| {
+        if let ReferenceOr::Ref(ref_node) = &mut header.header {
+            qualify_component_ref_node(
+                ref_node,
+                Some(&ComponentKind::Header),
+                current_file_path,
+                env,
+            );
+        }
+    });
+}
+
+// NOTE: nested_namespaces の場合に、component や `#/components/schemas/...` への参照を別の名前空間のモデルに解決する
+//       ファイルへの参照は replace_file_ref_to_component_ref で既に解決している
+pub fn qualify_component_refs(file_nodes: &mut [OpenAPIFileNode], env: &CompilerEnv) {
+    file_nodes.iter_mut().for_each(|file_node| {
+        let path = &file_node.path;
+        file_node.contents.iter_mut().for_each(|node| {
+            for_each_data_model_node_mut(node, &mut |data_model| {
+                if let DataModelNode::Ref(RefNode::ComponentRef(ref_node)) = data_model {
+                    qualify_component_ref_node(ref_node, None, path, env);
+                }
+            });
+            match node {
+                OpenAPINode::Operation(_) | OpenAPINode::Webhook(_) => {
+                    for_each_operation_node_mut(node, &mut |operation| {
+                        operation.parameters.iter_mut().for_each(|parameter| {
+                            if let ReferenceOr::Ref(ref_node) = parameter {
+                                let kind = Some(&ComponentKind::Parameter);
+                                qualify_component_ref_node(ref_node, kind, path, env);
+                            }
+                        });
+                        if let Some(ReferenceOr::Ref(ref_node)) = &mut operation.request_body {
+                            let kind = Some(&ComponentKind::RequestBody);
+                            qualify_component_ref_node(ref_node, kind, path, env);
+                        }
+                        operation.responses.iter_mut().for_each(|response| {
+                            match &mut response.response {
+                                ReferenceOr::Ref(ref_node) => {
+                                    let kind = Some(&ComponentKind::Response);
+                                    qualify_component_ref_node(ref_node, kind, path, env);
+                                }
+                                ReferenceOr::Item(response) => {
+                                    qualify_response_header_refs(response, path, env)
+                                }
+                            }
+                        });
+                    })
+                }
+                OpenAPINode::Component(component) => match &mut component.content {
+                    ComponentContentNode::Ref(ref_node) => {
+                        qualify_component_ref_node(ref_node, Some(&component.kind), path, env)
+                    }
+                    ComponentContentNode::Response(response) => {
+                        qualify_response_header_refs(response, path, env)
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        });
+    });
+}
+
+// =================================================================================================
+
+fn build_header_component_map(file_nodes: &[OpenAPIFileNode]) -> HashMap<String, HeaderNode> {
+    let components = file_nodes
+        .iter()
+        .flat_map(|file_node| file_node.contents.iter())
+        .filter_map(|node| match node {
+            OpenAPINode::Component(component) if component.kind == ComponentKind::Header => {
+                Some(component)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let find = |name: &String| {
+        components
+            .iter()
+            .find(|component| &component.name == name)
+            .map(|component| &component.content)
+    };
+
+    let mut map = HashMap::new();
+    for component in components.iter() {
+        // NOTE: component が別の component を参照している場合は参照先までたどる
+        let mut content = Some(&component.content);
+        let mut visited = HashSet::new();
+        while let Some(ComponentContentNode::Ref(ref_node)) = content {
+            if !visited.insert(&ref_node.component_name) {
+                break;
+            }
+            content = find(&ref_node.component_name);
+        }
+        if let Some(ComponentContentNode::Header(header)) = content {
+            map.insert(component.name.clone(), header.clone());
+        }
+    }
+
+    map
+}
+
+fn resolve_header_refs_in_response_object_node(
+    node: &mut ResponseObjectNode,
+    map: &HashMap<String, HeaderNode>,
+) {
+    node.headers.iter_mut().for_each(|header| {
+        if let ReferenceOr::Ref(ref_node) = &header.header {
+            let resolved = map
+                .get(&ref_node.component_name)
+                .cloned()
+                .expect("Failed to resolve header ref");
+            header.header = ReferenceOr::Item(resolved);
+        }
+    });
+}
+
+// NOTE: レスポンスのヘッダー名は参照元のキーで決まるので、header の component は型を展開して埋め込む
+pub fn resolve_header_refs(file_nodes: &mut [OpenAPIFileNode]) {
+    let map = build_header_component_map(file_nodes);
+
+    file_nodes.iter_mut().for_each(|file_node| {
+        file_node.contents.iter_mut().for_each(|node| match node {
+            OpenAPINode::Operation(_) | OpenAPINode::Webhook(_) => {
+                for_each_operation_node_mut(node, &mut |operation| {
+                    operation.responses.iter_mut().for_each(|response| {
+                        if let ReferenceOr::Item(response) = &mut response.response {
+                            resolve_header_refs_in_response_object_node(response, &map);
+                        }
+                    })
+                })
+            }
+            OpenAPINode::Component(ComponentNode {
+                content: ComponentContentNode::Response(response),
+                ..
+            }) => resolve_header_refs_in_response_object_node(response, &map),
+            _ => {}
+        });
+    });
+}
+
+// NOTE: 同じ名前空間に同じ名前のモデルが複数ある場合は、ルートに近いファイルのモデルに元の名前を残し、それ以外に連番を付ける
+//       変更したモデルの (元の名前, 新しい名前, ファイル) を返す
+pub fn disambiguate_model_titles(
+    file_nodes: &mut [OpenAPIFileNode],
+    env: &CompilerEnv,
+) -> Vec<(String, String, PathBuf)> {
+    let mut keys = HashSet::new();
+    let mut locations: BTreeMap<String, Vec<(usize, usize)>> = BTreeMap::new();
+    for (file_index, file_node) in file_nodes.iter().enumerate() {
+        for (content_index, content) in file_node.contents.iter().enumerate() {
+            match content {
+                OpenAPINode::DataModel(data_model_node) => {
+                    if let Some(title) = data_model_node.title() {
+                        let key = env.build_object_key(&file_node.path, &title);
+                        keys.insert(key.clone());
+                        locations
+                            .entry(key)
+                            .or_default()
+                            .push((file_index, content_index));
+                    }
+                }
+                OpenAPINode::Component(component) => {
+                    let name = build_component_model_name(&component.kind, &component.name);
+                    keys.insert(env.build_object_key(&file_node.path, &name));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut renamed = vec![];
+    for (_, mut locations) in locations.into_iter().filter(|(_, l)| l.len() > 1) {
+        locations.sort_by_key(|(file_index, content_index)| {
+            let path = &file_nodes[*file_index].path;
+            (path.components().count(), path.clone(), *content_index)
+        });
+        for (file_index, content_index) in locations.into_iter().skip(1) {
+            let file_node = &mut file_nodes[file_index];
+            if let OpenAPINode::DataModel(data_model_node) = &mut file_node.contents[content_index]
+            {
+                let title = data_model_node.title().expect("Failed to get title");
+                let new_title = (2..)
+                    .map(|i| format!("{}{}", title, i))
+                    .find(|name| !keys.contains(&env.build_object_key(&file_node.path, name)))
+                    .unwrap();
+                keys.insert(env.build_object_key(&file_node.path, &new_title));
+                data_model_node.set_title(new_title.clone());
+                renamed.push((title, new_title, file_node.path.clone()));
+            }
+        }
+    }
+
+    renamed
+}
+
+// =================================================================================================
+
+fn build_unique_model_name(name: String, names: &mut HashSet<String>) -> String {
+    let name = (1..)
+        .map(|i| {
+            if i == 1 {
+                name.clone()
+            } else {
+                format!("{}{}", name, i)
+            }
+        })
+        .find(|name| !names.contains(name))
+        .unwrap();
+    names.insert(name.clone());
+
+    name
+}
+
+fn hoist_inline_model(
+    node: &mut DataModelNode,
+    base_name: &str,
+    names: &mut HashSet<String>,
+    hoisted: &mut Vec<DataModelNode>,
+) {
+    let object = match node {
+        DataModelNode::Object(object) if !object.properties.is_empty() => object,
+        _ => {
+            hoist_inline_models_in_data_model_node(node, base_name, names, hoisted);
+            return;
+        }
+    };
+
+    let name =
+        build_unique_model_name(object.title.clone().unwrap_or(base_name.to_string()), names);
+    let mut object = object.clone();
+    object.title = Some(name.clone());
+    let nullable = object.nullable.take() == Some(true);
+
+    let mut model = DataModelNode::Object(object);
+    let mut children = vec![];
+    hoist_inline_models_in_data_model_node(&mut model, &name, names, &mut children);
+    hoisted.push(model);
+    hoisted.extend(children);
+
+    let ref_node = DataModelNode::Ref(RefNode::ComponentRef(ComponentRefNode {
+        component_name: name,
+        namespace: None,
+    }));
+    // NOTE: nullable は model 側ではなく参照している側の型に残す
+    *node = if nullable {
+        DataModelNode::OneOf(OneOfNode {
+            title: None,
+            items: vec![
+                ref_node,
+                DataModelNode::Const(ConstNode {
+                    title: None,
+                    value: ConstValue::Null,
+                    description: None,
+                }),
+            ],
+        })
+    } else {
+        ref_node
+    };
+}
+
+fn hoist_inline_models_in_data_model_node(
+    node: &mut DataModelNode,
+    base_name: &str,
+    names: &mut HashSet<String>,
+    hoisted: &mut Vec<DataModelNode>,
+) {
+    match node {
+        DataModelNode::Object(object) => {
+            object.properties.iter_mut().for_each(|property| {
+                let name = format!(
+                    "{}{}",
+                    base_name,
+                    to_pascal_case_with_hyphens(&property.key)
+                );
+                // NOTE: property の value の title はキーなので、スキーマに title があるときだけそれを名前にする
+                if let DataModelNode::Object(object) = &mut property.value {
+                    object.title = property.title.clone();
+                }
+                hoist_inline_model(&mut property.value, &name, names, hoisted);
+            });
+        }
+        DataModelNode::Array(array) => {
+            let name = format!("{}Item", base_name);
+            hoist_inline_model(&mut array.items, &name, names, hoisted);
+        }
+        DataModelNode::OneOf(one_of) => {
+            one_of.items.iter_mut().enumerate().for_each(|(i, item)| {
+                let name = format!("{}Option{}", base_name, i + 1);
+                hoist_inline_model(item, &name, names, hoisted);
+            });
+        }
+        DataModelNode::AllOf(all_of) => {
+            all_of.items.iter_mut().enumerate().for_each(|(i, item)| {
+                let name = format!("{}Part{}", base_name, i + 1);
+                hoist_inline_model(item, &name, names, hoisted);
+            });
+        }
+        DataModelNode::Tuple(tuple) => {
+            tuple.items.iter_mut().enumerate().for_each(|(i, item)| {
+                let name = format!("{}Item{}", base_name, i + 1);
+                hoist_inline_model(item, &name, names, hoisted);
+            });
+        }
+        _ => {}
+    }
+}
+
+// NOTE: multipart の body は各パートを HttpPart で包むので、body 自体は切り出さずにパートの中身だけを切り出す
+fn hoist_request_body_model(
+    request_body: &mut RequestBodyNode,
+    name: &str,
+    names: &mut HashSet<String>,
+    hoisted: &mut Vec<DataModelNode>,
+) {
+    if request_body.is_multipart() {
+        hoist_inline_models_in_data_model_node(&mut request_body.schema, name, names, hoisted);
+    } else {
+        hoist_inline_model(&mut request_body.schema, name, names, hoisted);
+    }
+}
+
+fn build_operation_base_name(operation: &OperationNode, current_file_path: &Path) -> String {
+    if let Some(operation_id) = &operation.operation_id {
+        return to_pascal_case_with_hyphens(operation_id);
+    }
+
+    build_operation_path_name(operation, current_file_path)
+}
+
+// NOTE: operationId は任意で付け方もまちまちなので、名前付きのレスポンスは必ずあるファイル名とメソッドから名前を付ける
+fn build_operation_path_name(operation: &OperationNode, current_file_path: &Path) -> String {
+    let file_name = current_file_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .expect("Failed to get file name");
+    let op = match operation.op {
+        Operation::Get => "Get",
+        Operation::Post => "Post",
+        Operation::Put => "Put",
+        Operation::Delete => "Delete",
+        Operation::Patch => "Patch",
+    };
+
+    format!("{}{}", to_pascal_case_with_hyphens(file_name), op)
+}
+
+fn hoist_inline_models_in_operation_node(
+    operation: &mut OperationNode,
+    current_file_path: &Path,
+    names: &mut HashSet<String>,
+    hoisted: &mut Vec<DataModelNode>,
+) {
+    let base_name = build_operation_base_name(operation, current_file_path);
+
+    if let Some(ReferenceOr::Item(request_body)) = &mut operation.request_body {
+        let name = format!("{}RequestBody", base_name);
+        hoist_request_body_model(request_body, &name, names, hoisted);
+    }
+    operation.responses.iter_mut().for_each(|response| {
+        let name = format!("{}{}Body", base_name, response.status.get_name());
+        if let ReferenceOr::Item(ResponseObjectNode {
+            schema: Some(schema),
+            ..
+        }) = &mut response.response
+        {
+            hoist_inline_model(schema, &name, names, hoisted);
+        }
+    });
+}
+
+fn hoist_inline_models_in_component_node(
+    component: &mut ComponentNode,
+    names: &mut HashSet<String>,
+    hoisted: &mut Vec<DataModelNode>,
+) {
+    let base_name = build_component_model_name(&component.kind, &component.name);
+    let name = format!("{}Body", base_name);
+    match &mut component.content {
+        ComponentContentNode::Response(ResponseObjectNode {
+            schema: Some(schema),
+            ..
+        }) => hoist_inline_model(schema, &name, names, hoisted),
+        ComponentContentNode::RequestBody(request_body) => {
+            hoist_request_body_model(request_body, &name, names, hoisted)
+        }
+        _ => {}
+    }
+}
+
+fn hoist_inline_models_in_file_node(
+    file_node: &mut OpenAPIFileNode,
+    names: &mut HashSet<String>,
+) -> Vec<String> {
+    let mut hoisted_names = vec![];
+    let mut operation_models = vec![];
+    let contents = std::mem::take(file_node.contents.as_mut());
+
+    for mut content in contents.into_iter() {
+        let mut hoisted = vec![];
+        match &mut content {
+            OpenAPINode::DataModel(node) => {
+                let base_name = node.title().unwrap_or_else(|| {
+                    let file_name = file_node.path.file_stem().and_then(|s| s.to_str());
+                    to_pascal_case_with_hyphens(file_name.expect("Failed to get file name"))
+                });
+                hoist_inline_models_in_data_model_node(node, &base_name, names, &mut hoisted);
+            }
+            OpenAPINode::Operation(_) | OpenAPINode::Webhook(_) => {
+                for_each_operation_node_mut(&mut content, &mut |operation| {
+                    hoist_inline_models_in_operation_node(
+                        operation,
+                        &file_node.path,
+                        names,
+                        &mut operation_models,
+                    )
+                });
+            }
+            OpenAPINode::Component(component) => {
+                hoist_inline_models_in_component_node(component, names, &mut hoisted);
             }
             _ => {}
+        }
+
+        // NOTE: 切り出した model は親の直後に並べる
+        file_node.contents.push(content);
+        hoisted.into_iter().for_each(|node| {
+            hoisted_names.extend(node.title());
+            file_node.contents.push(OpenAPINode::DataModel(node));
+        });
+    }
+    operation_models.into_iter().for_each(|node| {
+        hoisted_names.extend(node.title());
+        file_node.contents.push(OpenAPINode::DataModel(node));
+    });
+
+    hoisted_names
+}
+
+pub fn hoist_inline_models(
+    file_nodes: &mut [OpenAPIFileNode],
+    object_names: &HashSet<String>,
+) -> ObjectFilePathMap {
+    let mut names = object_names.clone();
+
+    file_nodes
+        .iter_mut()
+        .flat_map(|file_node| {
+            let path = file_node.path.to_str().unwrap().to_string();
+            hoist_inline_models_in_file_node(file_node, &mut names)
+                .into_iter()
+                .map(move |name| (name, path.clone()))
+        })
+        .collect()
+}
+
+// =================================================================================================
+
+fn for_each_nested_operation_node<'a>(
+    operation: &'a OperationNode,
+    f: &mut dyn FnMut(&'a OperationNode),
+) {
+    f(operation);
+    operation
+        .callbacks
+        .iter()
+        .flat_map(|callback| callback.operations.iter())
+        .for_each(|operation| for_each_nested_operation_node(operation, f));
+}
+
+fn for_each_operation_node<'a>(node: &'a OpenAPINode, f: &mut dyn FnMut(&'a OperationNode)) {
+    match node {
+        OpenAPINode::Operation(operation) => for_each_nested_operation_node(operation, f),
+        OpenAPINode::Webhook(webhook) => webhook
+            .operations
+            .iter()
+            .for_each(|operation| for_each_nested_operation_node(operation, f)),
+        _ => {}
+    }
+}
+
+fn collect_ref_names(node: &DataModelNode, names: &mut HashSet<String>) {
+    match node {
+        DataModelNode::Ref(RefNode::ComponentRef(ref_node)) => {
+            names.insert(ref_node.component_name.clone());
+        }
+        DataModelNode::Array(array) => collect_ref_names(&array.items, names),
+        DataModelNode::Object(object) => object
+            .properties
+            .iter()
+            .for_each(|property| collect_ref_names(&property.value, names)),
+        DataModelNode::OneOf(OneOfNode { items, .. })
+        | DataModelNode::AllOf(AllOfNode { items, .. })
+        | DataModelNode::Tuple(TupleNode { items, .. }) => {
+            items.iter().for_each(|item| collect_ref_names(item, names))
+        }
+        _ => {}
+    }
+}
+
+// NOTE: エラーのレスポンスで body そのものとして使われている model だけをエラーの候補にする
+fn collect_response_ref_names(
+    response: &ResponseObjectNode,
+    is_error: bool,
+    error_names: &mut HashSet<String>,
+    other_names: &mut HashSet<String>,
+) {
+    match &response.schema {
+        Some(DataModelNode::Ref(RefNode::ComponentRef(ref_node))) if is_error => {
+            error_names.insert(ref_node.component_name.clone());
+        }
+        Some(schema) => collect_ref_names(schema, other_names),
+        None => {}
+    }
+}
+
+pub fn build_error_model_names(file_nodes: &[OpenAPIFileNode]) -> HashSet<String> {
+    let mut error_names = HashSet::new();
+    let mut other_names = HashSet::new();
+    let mut component_response_statuses: HashMap<&String, Vec<bool>> = HashMap::new();
+
+    let contents = file_nodes
+        .iter()
+        .flat_map(|file_node| file_node.contents.iter())
+        .collect::<Vec<_>>();
+    contents.iter().for_each(|node| {
+        for_each_operation_node(node, &mut |operation| {
+            operation.parameters.iter().for_each(|parameter| {
+                if let ReferenceOr::Item(parameter) = parameter {
+                    collect_ref_names(&parameter.schema, &mut other_names);
+                }
+            });
+            if let Some(ReferenceOr::Item(request_body)) = &operation.request_body {
+                collect_ref_names(&request_body.schema, &mut other_names);
+            }
+            operation
+                .responses
+                .iter()
+                .for_each(|response| match &response.response {
+                    ReferenceOr::Item(response_object) => collect_response_ref_names(
+                        response_object,
+                        response.status.is_error(),
+                        &mut error_names,
+                        &mut other_names,
+                    ),
+                    ReferenceOr::Ref(ref_node) => {
+                        // NOTE: default で参照される response model は @error を付けてエラーとして扱う
+                        if response.status == ResponseStatus::Default {
+                            error_names.insert(build_component_model_name(
+                                &ComponentKind::Response,
+                                &ref_node.component_name,
+                            ));
+                        }
+                        component_response_statuses
+                            .entry(&ref_node.component_name)
+                            .or_default()
+                            .push(response.status.is_error())
+                    }
+                });
         });
     });
+
+    contents.iter().for_each(|node| match node {
+        OpenAPINode::DataModel(data_model) => collect_ref_names(data_model, &mut other_names),
+        OpenAPINode::Component(component) => match &component.content {
+            ComponentContentNode::Response(response) => {
+                let is_error = component_response_statuses
+                    .get(&component.name)
+                    .map(|statuses| statuses.iter().all(|is_error| *is_error))
+                    .unwrap_or(false);
+                collect_response_ref_names(response, is_error, &mut error_names, &mut other_names);
+            }
+            ComponentContentNode::Parameter(parameter) => {
+                collect_ref_names(&parameter.schema, &mut other_names)
+            }
+            ComponentContentNode::Header(header) => {
+                collect_ref_names(&header.schema, &mut other_names)
+            }
+            ComponentContentNode::RequestBody(request_body) => {
+                collect_ref_names(&request_body.schema, &mut other_names)
+            }
+            ComponentContentNode::Ref(_) => {}
+        },
+        _ => {}
+    });
+
+    error_names
+        .difference(&other_names)
+        .cloned()
+        .collect::<HashSet<_>>()
+}
+
+// =================================================================================================
+
+// NOTE: component の model 名には接尾辞が付くので、接尾辞を付けた名前で重複を確認する
+fn build_unique_component_name(
+    kind: &ComponentKind,
+    name: &str,
+    names: &mut HashSet<String>,
+) -> String {
+    let name = (1..)
+        .map(|i| {
+            if i == 1 {
+                name.to_string()
+            } else {
+                format!("{}{}", name, i)
+            }
+        })
+        .find(|name| !names.contains(&build_component_model_name(kind, name)))
+        .unwrap();
+    names.insert(build_component_model_name(kind, &name));
+
+    name
+}
+
+fn build_response_signature(status: &ResponseStatus, response: &ResponseObjectNode) -> String {
+    format!("{}:{}", status, response.build_signature())
+}
+
+fn find_root_file_index(file_nodes: &[OpenAPIFileNode]) -> Option<usize> {
+    file_nodes.iter().position(|file_node| {
+        file_node
+            .contents
+            .iter()
+            .any(|node| matches!(node, OpenAPINode::Info(_)))
+    })
+}
+
+// NOTE: 同じ内容のレスポンスは最初に切り出した response model を参照する
+fn replace_response_with_named_response(
+    response: &mut ResponseNode,
+    signature: String,
+    shared: &mut HashMap<String, String>,
+    components: &mut Vec<ComponentNode>,
+    build_name: impl FnOnce() -> String,
+) {
+    let component_name = shared.entry(signature).or_insert_with(|| {
+        let name = build_name();
+        let content = std::mem::replace(
+            &mut response.response,
+            ReferenceOr::Ref(ComponentRefNode {
+                component_name: name.clone(),
+                namespace: None,
+            }),
+        );
+        if let ReferenceOr::Item(mut response_object) = content {
+            response_object.status_code = Some(response.status.clone());
+            components.push(ComponentNode {
+                kind: ComponentKind::Response,
+                name: name.clone(),
+                content: ComponentContentNode::Response(response_object),
+            });
+        }
+
+        name
+    });
+    response.response = ReferenceOr::Ref(ComponentRefNode {
+        component_name: component_name.clone(),
+        namespace: None,
+    });
+    response.is_named_response = true;
+}
+
+// NOTE: 複数の operation で同じ内容のエラーレスポンスは、ルートのファイルに共通の response model として切り出す
+pub fn extract_shared_error_responses(
+    file_nodes: &mut [OpenAPIFileNode],
+    object_names: &HashSet<String>,
+) -> ObjectFilePathMap {
+    let root_index = match find_root_file_index(file_nodes) {
+        Some(index) => index,
+        None => return ObjectFilePathMap::new(),
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    file_nodes
+        .iter()
+        .flat_map(|file_node| file_node.contents.iter())
+        .for_each(|node| {
+            for_each_operation_node(node, &mut |operation| {
+                operation.responses.iter().for_each(|response| {
+                    if let ReferenceOr::Item(response_object) = &response.response {
+                        if response.status.is_error() {
+                            *counts
+                                .entry(build_response_signature(&response.status, response_object))
+                                .or_default() += 1;
+                        }
+                    }
+                });
+            });
+        });
+
+    let mut names = object_names.clone();
+    let mut shared: HashMap<String, String> = HashMap::new();
+    let mut components = vec![];
+    file_nodes
+        .iter_mut()
+        .flat_map(|file_node| file_node.contents.iter_mut())
+        .for_each(|node| {
+            for_each_operation_node_mut(node, &mut |operation| {
+                operation.responses.iter_mut().for_each(|response| {
+                    let signature = match &response.response {
+                        ReferenceOr::Item(response_object) if response.status.is_error() => {
+                            build_response_signature(&response.status, response_object)
+                        }
+                        _ => return,
+                    };
+                    if counts.get(&signature).copied().unwrap_or(0) < 2 {
+                        return;
+                    }
+
+                    let status_name = response.status.get_name();
+                    replace_response_with_named_response(
+                        response,
+                        signature,
+                        &mut shared,
+                        &mut components,
+                        || {
+                            build_unique_component_name(
+                                &ComponentKind::Response,
+                                &status_name,
+                                &mut names,
+                            )
+                        },
+                    );
+                });
+            });
+        });
+
+    let root_file_node = &mut file_nodes[root_index];
+    let path = root_file_node.path.to_str().unwrap().to_string();
+    components
+        .into_iter()
+        .map(|component| {
+            let name = build_component_model_name(&component.kind, &component.name);
+            root_file_node
+                .contents
+                .push(OpenAPINode::Component(component));
+
+            (name, path.clone())
+        })
+        .collect()
+}
+
+// =================================================================================================
+
+fn build_named_responses_file_path(file_nodes: &[OpenAPIFileNode], root_dir: &Path) -> PathBuf {
+    (1..)
+        .map(|i| {
+            if i == 1 {
+                root_dir.join("responses.yaml")
+            } else {
+                root_dir.join(format!("responses{}.yaml", i))
+            }
+        })
+        .find(|path| file_nodes.iter().all(|file_node| &file_node.path != path))
+        .unwrap()
+}
+
+// NOTE: operation のレスポンスを名前付きの response model に切り出し、同じ内容のものは 1 つにまとめる
+pub fn extract_named_responses(
+    file_nodes: &mut Vec<OpenAPIFileNode>,
+    object_names: &HashSet<String>,
+    shared_file: bool,
+) -> ObjectFilePathMap {
+    extract_responses(file_nodes, object_names, shared_file, |_| true)
+}
+
+// NOTE: default のレスポンスはステータスコードを持たないので、@error を付けた model にしないと 200 として扱われる
+pub fn extract_default_responses(
+    file_nodes: &mut Vec<OpenAPIFileNode>,
+    object_names: &HashSet<String>,
+) -> ObjectFilePathMap {
+    extract_responses(file_nodes, object_names, false, |status| {
+        matches!(status, ResponseStatus::Default)
+    })
+}
+
+fn extract_responses(
+    file_nodes: &mut Vec<OpenAPIFileNode>,
+    object_names: &HashSet<String>,
+    shared_file: bool,
+    is_target: impl Fn(&ResponseStatus) -> bool,
+) -> ObjectFilePathMap {
+    let mut names = object_names.clone();
+    let mut shared: HashMap<String, String> = HashMap::new();
+    let mut components: Vec<(usize, ComponentNode)> = vec![];
+
+    file_nodes
+        .iter_mut()
+        .enumerate()
+        .for_each(|(file_index, file_node)| {
+            let path = &file_node.path;
+            file_node.contents.iter_mut().for_each(|node| {
+                for_each_operation_node_mut(node, &mut |operation| {
+                    let base_name = build_operation_path_name(operation, path);
+                    operation.responses.iter_mut().for_each(|response| {
+                        let signature = match &response.response {
+                            ReferenceOr::Item(response_object) if is_target(&response.status) => {
+                                build_response_signature(&response.status, response_object)
+                            }
+                            _ => return,
+                        };
+                        let name = format!("{}{}", base_name, response.status.get_name());
+                        let mut file_components = vec![];
+                        replace_response_with_named_response(
+                            response,
+                            signature,
+                            &mut shared,
+                            &mut file_components,
+                            || {
+                                build_unique_component_name(
+                                    &ComponentKind::Response,
+                                    &name,
+                                    &mut names,
+                                )
+                            },
+                        );
+                        components.extend(
+                            file_components
+                                .into_iter()
+                                .map(|component| (file_index, component)),
+                        );
+                    });
+                });
+            });
+        });
+
+    let shared_file_index = if shared_file && !components.is_empty() {
+        let root_dir = find_root_file_index(file_nodes)
+            .and_then(|index| file_nodes[index].path.parent())
+            .map(|path| path.to_path_buf())
+            .expect("Failed to find root dir");
+        file_nodes.push(OpenAPIFileNode {
+            path: build_named_responses_file_path(file_nodes, &root_dir),
+            contents: Box::new(vec![]),
+        });
+        Some(file_nodes.len() - 1)
+    } else {
+        None
+    };
+
+    components
+        .into_iter()
+        .map(|(file_index, component)| {
+            let file_node = &mut file_nodes[shared_file_index.unwrap_or(file_index)];
+            let path = file_node.path.to_str().unwrap().to_string();
+            let name = build_component_model_name(&component.kind, &component.name);
+            file_node.contents.push(OpenAPINode::Component(component));
+
+            (name, path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use yaml_rust::YamlLoader;
+
+    use super::*;
+    use crate::openapi_parser::parse_yaml_files;
+    use crate::yaml_loader::YamlFile;
+
+    fn parse_files(files: &[(&str, &str)]) -> Vec<OpenAPIFileNode> {
+        parse_yaml_files(
+            files
+                .iter()
+                .map(|(path, content)| YamlFile {
+                    path: PathBuf::from(path),
+                    content: Box::new(YamlLoader::load_from_str(content).unwrap()),
+                })
+                .collect(),
+        )
+    }
+
+    fn list_responses(file_node: &OpenAPIFileNode) -> Vec<&ResponseNode> {
+        file_node
+            .contents
+            .iter()
+            .filter_map(|node| match node {
+                OpenAPINode::Operation(operation) => Some(operation.responses.iter()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    const ROOT: &str = "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths: {}
+";
+
+    #[test]
+    fn test_build_error_model_names() {
+        let file_nodes = parse_files(&[
+            ("/api/openapi.yaml", ROOT),
+            (
+                "/api/paths/pets.yaml",
+                "get:
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+    '404':
+      description: not found
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Problem'
+    '409':
+      description: conflict
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+    4XX:
+      description: client error
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/ClientError'
+    default:
+      description: unexpected
+      content:
+        application/json:
+          schema:
+            type: object
+            properties:
+              detail:
+                $ref: '#/components/schemas/Detail'
+    '503':
+      $ref: '#/components/responses/Unavailable'
+    '202':
+      $ref: '#/components/responses/Pending'
+",
+            ),
+            (
+                "/api/paths/owners.yaml",
+                "get:
+  responses:
+    '500':
+      $ref: '#/components/responses/Pending'
+",
+            ),
+            (
+                "/api/components.yaml",
+                "components:
+  responses:
+    Unavailable:
+      description: unavailable
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Maintenance'
+    Pending:
+      description: pending
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Job'
+",
+            ),
+        ]);
+
+        let names = build_error_model_names(&file_nodes);
+
+        // NOTE: 成功のレスポンスでも使われる Pet、エラーと成功の両方から参照される response の Job、
+        //       body の中で使われているだけの Detail はエラーにしない
+        assert_eq!(
+            names,
+            HashSet::from([
+                "Problem".to_string(),
+                "ClientError".to_string(),
+                "Maintenance".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_extract_shared_error_responses() {
+        let operation = |status: &str| {
+            format!(
+                "get:
+  responses:
+    '200':
+      description: ok
+    '{}':
+      description: not found
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Problem'
+    4XX:
+      description: client error
+",
+                status
+            )
+        };
+        let pets = operation("404");
+        let owners = operation("404");
+        let stores = operation("409");
+        let mut file_nodes = parse_files(&[
+            ("/api/openapi.yaml", ROOT),
+            ("/api/paths/pets.yaml", &pets),
+            ("/api/paths/owners.yaml", &owners),
+            ("/api/paths/stores.yaml", &stores),
+        ]);
+
+        let map = extract_shared_error_responses(
+            &mut file_nodes,
+            &HashSet::from(["NotFoundResponse".to_string()]),
+        );
+
+        // NOTE: 既存の model と名前が重なるので番号を付ける。ステータスが違う 409 と、
+        //       3 つの operation で共通の 4XX はそれぞれ別に切り出す
+        assert_eq!(
+            map,
+            ObjectFilePathMap::from([
+                (
+                    "NotFound2Response".to_string(),
+                    "/api/openapi.yaml".to_string()
+                ),
+                (
+                    "Status4XXResponse".to_string(),
+                    "/api/openapi.yaml".to_string()
+                ),
+            ])
+        );
+        let components = file_nodes[0]
+            .contents
+            .iter()
+            .filter_map(|node| match node {
+                OpenAPINode::Component(component) => Some(component),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(components.len(), 2);
+        assert!(components.iter().all(|component| matches!(
+            &component.content,
+            ComponentContentNode::Response(ResponseObjectNode {
+                status_code: Some(_),
+                ..
+            })
+        )));
+
+        let names = |file_node: &OpenAPIFileNode| {
+            list_responses(file_node)
+                .into_iter()
+                .map(|response| match &response.response {
+                    ReferenceOr::Ref(ref_node) => Some(ref_node.component_name.clone()),
+                    ReferenceOr::Item(_) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let shared = vec![
+            None,
+            Some("NotFound2".to_string()),
+            Some("Status4XX".to_string()),
+        ];
+        assert_eq!(names(&file_nodes[1]), shared);
+        assert_eq!(names(&file_nodes[2]), shared);
+        assert_eq!(
+            names(&file_nodes[3]),
+            vec![None, None, Some("Status4XX".to_string())]
+        );
+    }
+
+    fn list_data_model_titles(file_node: &OpenAPIFileNode) -> Vec<String> {
+        file_node
+            .contents
+            .iter()
+            .filter_map(|node| match node {
+                OpenAPINode::DataModel(data_model) => data_model.title(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn get_component_ref_name(node: &DataModelNode) -> Option<&str> {
+        match node {
+            DataModelNode::Ref(RefNode::ComponentRef(ref_node)) => {
+                Some(ref_node.component_name.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_hoist_inline_models() {
+        let mut file_nodes = parse_files(&[
+            (
+                "/api/openapi.yaml",
+                "openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner:
+          type: object
+          nullable: true
+          properties:
+            address:
+              type: object
+              properties:
+                city:
+                  type: string
+        tags:
+          type: array
+          items:
+            type: object
+            properties:
+              name:
+                type: string
+        name:
+          type: string
+",
+            ),
+            (
+                "/api/paths/pets.yaml",
+                "post:
+  operationId: createPet
+  requestBody:
+    content:
+      application/json:
+        schema:
+          type: object
+          properties:
+            name:
+              type: string
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            type: object
+            properties:
+              id:
+                type: string
+",
+            ),
+        ]);
+
+        // NOTE: 既存の model と名前が重なるものは番号を付け、その中の model も番号を付けた名前から作る
+        let map = hoist_inline_models(
+            &mut file_nodes,
+            &HashSet::from(["Pet".to_string(), "PetOwner".to_string()]),
+        );
+
+        assert_eq!(
+            list_data_model_titles(&file_nodes[0]),
+            vec!["Pet", "PetOwner2", "PetOwner2Address", "PetTagsItem"]
+        );
+        assert_eq!(
+            list_data_model_titles(&file_nodes[1]),
+            vec!["CreatePetRequestBody", "CreatePetOkBody"]
+        );
+        assert_eq!(
+            map,
+            ObjectFilePathMap::from([
+                ("PetOwner2".to_string(), "/api/openapi.yaml".to_string()),
+                (
+                    "PetOwner2Address".to_string(),
+                    "/api/openapi.yaml".to_string()
+                ),
+                ("PetTagsItem".to_string(), "/api/openapi.yaml".to_string()),
+                (
+                    "CreatePetRequestBody".to_string(),
+                    "/api/paths/pets.yaml".to_string()
+                ),
+                (
+                    "CreatePetOkBody".to_string(),
+                    "/api/paths/pets.yaml".to_string()
+                ),
+            ])
+        );
+
+        let pet = file_nodes[0]
+            .contents
+            .iter()
+            .find_map(|node| match node {
+                OpenAPINode::DataModel(DataModelNode::Object(object))
+                    if object.title.as_deref() == Some("Pet") =>
+                {
+                    Some(object)
+                }
+                _ => None,
+            })
+            .unwrap();
+        // NOTE: nullable は参照している側に残る
+        assert!(matches!(
+            &pet.properties[0].value,
+            DataModelNode::OneOf(OneOfNode { items, .. })
+                if get_component_ref_name(&items[0]) == Some("PetOwner2")
+                    && matches!(items[1], DataModelNode::Const(ConstNode { value: ConstValue::Null, .. }))
+        ));
+        assert!(matches!(
+            &pet.properties[1].value,
+            DataModelNode::Array(array) if get_component_ref_name(&array.items) == Some("PetTagsItem")
+        ));
+        assert!(matches!(&pet.properties[2].value, DataModelNode::String(_)));
+    }
+
+    #[test]
+    fn test_extract_named_responses() {
+        let operation = |method: &str, operation_id: &str| {
+            format!(
+                "{}:
+  operationId: {}
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+",
+                method, operation_id
+            )
+        };
+        let pets = operation("get", "listPets");
+        let pet = operation("put", "updatePet");
+        let mut file_nodes = parse_files(&[
+            ("/api/openapi.yaml", ROOT),
+            ("/api/paths/pets.yaml", &pets),
+            ("/api/paths/pet.yaml", &pet),
+        ]);
+
+        let map = extract_named_responses(&mut file_nodes, &HashSet::new(), true);
+
+        // NOTE: 同じ内容のレスポンスは最初の operation のファイル名とメソッドから名前を付けた 1 つにまとめる
+        assert_eq!(
+            map,
+            ObjectFilePathMap::from([(
+                "PetsGetOkResponse".to_string(),
+                "/api/responses.yaml".to_string()
+            )])
+        );
+        assert_eq!(file_nodes.len(), 4);
+        assert_eq!(file_nodes[3].contents.len(), 1);
+        for file_node in file_nodes[1..3].iter() {
+            let responses = list_responses(file_node);
+            assert_eq!(responses.len(), 1);
+            assert!(responses[0].is_named_response);
+            assert!(matches!(
+                &responses[0].response,
+                ReferenceOr::Ref(ref_node) if ref_node.component_name == "PetsGetOk"
+            ));
+        }
+    }
 }
@@ -0,0 +1,221 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use pathdiff::diff_paths;
+
+use crate::compiler::{CompilerEnv, GeneratedFile};
+use crate::logger;
+use crate::openapi_parser::node::OpenAPIVersion;
+use crate::type_spec::node::{ImportLibNode, TypeSpecFileNode};
+use crate::type_spec::printer::PrintOptions;
+
+pub const MAIN_FILE_NAME: &str = "main.tsp";
+
+pub const TSP_CONFIG_FILE_NAME: &str = "tspconfig.yaml";
+
+pub const PACKAGE_FILE_NAME: &str = "package.json";
+
+const EMITTER_NAME: &str = "@typespec/openapi3";
+
+// NOTE: 生成したファイルが import するライブラリとは別に、tsp の実行と OpenAPI の出力に必要なもの
+const TOOL_PACKAGE_NAMES: [&str; 2] = ["@typespec/compiler", EMITTER_NAME];
+
+const PACKAGE_VERSION: &str = "^1.0.0";
+
+pub(crate) fn json_string_literal(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+
+    result
+}
+
+// NOTE: npm のパッケージ名には小文字と一部の記号しか使えないので、それ以外は - に置き換える
+fn build_package_name(output_dir: &Path) -> String {
+    let name = output_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    let name = name.trim_matches(|c| c == '-' || c == '.' || c == '_');
+
+    if name.is_empty() {
+        "typespec-project".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+fn build_main_file(
+    main_path: PathBuf,
+    file_nodes: &[TypeSpecFileNode],
+    print_options: &PrintOptions,
+) -> GeneratedFile {
+    let output_dir = main_path.parent().expect("Failed to get parent dir");
+    let mut imports = file_nodes
+        .iter()
+        .map(|file_node| {
+            let path = diff_paths(&file_node.path, output_dir).expect("Cannot find relative path");
+            ImportLibNode::new(format!(
+                "./{}",
+                path.to_str().expect("Cannot convert to str")
+            ))
+        })
+        .collect::<Vec<_>>();
+    imports.sort();
+
+    GeneratedFile {
+        content: TypeSpecFileNode::new(main_path.clone(), imports, vec![], vec![])
+            .print(print_options),
+        path: main_path,
+    }
+}
+
+fn build_tsp_config_file(path: PathBuf, openapi_version: OpenAPIVersion) -> GeneratedFile {
+    let openapi_version = match openapi_version {
+        OpenAPIVersion::V3_0 => "3.0.0",
+        OpenAPIVersion::V3_1 => "3.1.0",
+    };
+
+    GeneratedFile {
+        path,
+        content: format!(
+            "emit:
+  - \"{emitter}\"
+options:
+  \"{emitter}\":
+    file-type: yaml
+    openapi-versions:
+      - {openapi_version}
+",
+            emitter = EMITTER_NAME,
+            openapi_version = openapi_version
+        ),
+    }
+}
+
+fn build_package_dependencies(package_names: &BTreeSet<String>) -> String {
+    if package_names.is_empty() {
+        return "{}".to_string();
+    }
+
+    let dependencies = package_names
+        .iter()
+        .map(|name| {
+            format!(
+                "    {}: {}",
+                json_string_literal(name),
+                json_string_literal(PACKAGE_VERSION)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!("{{\n{}\n  }}", dependencies)
+}
+
+// NOTE: dependencies には生成したファイルが import しているライブラリ (LibInfo::get_lib_name で決まるもの) だけを並べる
+fn build_package_file(path: PathBuf, file_nodes: &[TypeSpecFileNode]) -> GeneratedFile {
+    let output_dir = path.parent().expect("Failed to get parent dir");
+    let lib_names = file_nodes
+        .iter()
+        .flat_map(|file_node| file_node.imports.iter())
+        .filter(|import| !import.is_relative())
+        .map(|import| import.lib_name.clone())
+        .collect::<BTreeSet<_>>();
+    let tool_names = TOOL_PACKAGE_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<BTreeSet<_>>();
+
+    GeneratedFile {
+        content: format!(
+            "{{
+  \"name\": {},
+  \"version\": \"0.1.0\",
+  \"private\": true,
+  \"dependencies\": {},
+  \"devDependencies\": {}
+}}
+",
+            json_string_literal(&build_package_name(output_dir)),
+            build_package_dependencies(&lib_names),
+            build_package_dependencies(&tool_names)
+        ),
+        path,
+    }
+}
+
+// NOTE: ルートの spec と同じディレクトリに、tsp compile . でそのままコンパイルできるプロジェクトのファイルを出力する
+pub(crate) fn build_project_files(
+    file_nodes: &[TypeSpecFileNode],
+    env: &CompilerEnv,
+) -> Vec<GeneratedFile> {
+    let output_dir = &env.namespace_root_dir;
+    let mut files = vec![];
+
+    let main_path = output_dir.join(MAIN_FILE_NAME);
+    if file_nodes
+        .iter()
+        .any(|file_node| file_node.path == main_path)
+    {
+        logger::warning!(
+            "{} is generated from the spec, so it is not overwritten with the project entry point",
+            MAIN_FILE_NAME
+        );
+    } else {
+        files.push(build_main_file(
+            main_path,
+            file_nodes,
+            &env.options.print_options,
+        ));
+    }
+    files.push(build_tsp_config_file(
+        output_dir.join(TSP_CONFIG_FILE_NAME),
+        env.openapi_version,
+    ));
+    files.push(build_package_file(
+        output_dir.join(PACKAGE_FILE_NAME),
+        file_nodes,
+    ));
+
+    files
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_build_package_name() {
+        assert_eq!(
+            build_package_name(Path::new("/specs/Pet Store")),
+            "pet-store"
+        );
+        assert_eq!(build_package_name(Path::new("/specs/_api_")), "api");
+        assert_eq!(build_package_name(Path::new("/")), "typespec-project");
+    }
+
+    #[test]
+    fn test_json_string_literal() {
+        assert_eq!(json_string_literal("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+}
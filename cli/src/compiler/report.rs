@@ -0,0 +1,727 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use yaml_rust::{yaml, Yaml};
+
+use crate::compiler::project::json_string_literal;
+use crate::compiler::validate::{for_each_declaration, for_each_reference};
+use crate::compiler::{CompilerOptions, ExtensionHandling, NamingOptions};
+use crate::openapi_parser::node::{ComponentKind, OpenAPIFileNode, OpenAPINode};
+use crate::type_spec::node::TypeSpecFileNode;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Print the dropped data of each file as text
+    Human,
+    /// Print the report as JSON
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileReport {
+    pub source: PathBuf,
+    pub output: PathBuf,
+    // NOTE: parse したが TypeSpec に出力していないキーの YAML パス (properties.name.example など)
+    pub dropped_keys: Vec<String>,
+    // NOTE: どの parser にも解釈されずに Unknown として残ったキー
+    pub unknown_keys: Vec<String>,
+    // NOTE: 名前を決められずに naming の fallback_* を使った宣言や参照
+    pub fallback_names: Vec<String>,
+}
+
+impl FileReport {
+    pub fn is_lossless(&self) -> bool {
+        self.dropped_keys.is_empty()
+            && self.unknown_keys.is_empty()
+            && self.fallback_names.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConversionReport {
+    pub files: Vec<FileReport>,
+}
+
+// NOTE: 各 builder が TypeSpec に出力するキー。それ以外のキーは変換で失われる
+const SCHEMA_KEYS: [&str; 20] = [
+    "type",
+    "title",
+    "properties",
+    "required",
+    "items",
+    "enum",
+    "nullable",
+    "format",
+    "pattern",
+    "minLength",
+    "maxLength",
+    "oneOf",
+    "allOf",
+    "const",
+    "prefixItems",
+    "$ref",
+    "x-enum-varnames",
+    "x-enum-descriptions",
+    "contentEncoding",
+    "contentMediaType",
+];
+
+const PARAMETER_KEYS: [&str; 9] = [
+    "name",
+    "in",
+    "description",
+    "schema",
+    "content",
+    "required",
+    "style",
+    "explode",
+    "allowReserved",
+];
+
+const HEADER_KEYS: [&str; 3] = ["description", "required", "schema"];
+
+const ENCODING_KEYS: [&str; 1] = ["contentType"];
+
+const OPERATION_METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+const INFO_KEYS: [&str; 4] = ["title", "version", "contact", "termsOfService"];
+
+const PARSED_COMPONENT_SECTIONS: [&str; 5] = [
+    "schemas",
+    "parameters",
+    "responses",
+    "headers",
+    "requestBodies",
+];
+
+const CONTACT_KEYS: [&str; 3] = ["name", "url", "email"];
+
+fn key_to_string(key: &Yaml) -> String {
+    match key {
+        Yaml::String(s) => s.clone(),
+        Yaml::Integer(i) => i.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        _ => format!("{:?}", key),
+    }
+}
+
+// NOTE: キーに . を含む場合 (application/vnd.api+json など) は ["..."] で区切る
+fn join_yaml_path(parent: &str, key: &str) -> String {
+    if key.contains('.') {
+        format!("{}[{}]", parent, json_string_literal(key))
+    } else if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn is_ref(hash: &yaml::Hash) -> bool {
+    hash.contains_key(&Yaml::String("$ref".to_string()))
+}
+
+fn hash_entries(yaml: &Yaml) -> impl Iterator<Item = (String, &Yaml)> {
+    yaml.as_hash()
+        .into_iter()
+        .flat_map(|hash| hash.iter())
+        .map(|(key, value)| (key_to_string(key), value))
+}
+
+fn array_items(yaml: &Yaml) -> impl Iterator<Item = (usize, &Yaml)> {
+    yaml.as_vec()
+        .into_iter()
+        .flat_map(|items| items.iter())
+        .enumerate()
+}
+
+// NOTE: OpenAPI の構造に沿って yaml を辿り、出力されないキーの YAML パスを集める
+struct DroppedKeyCollector<'a> {
+    options: &'a CompilerOptions,
+    shared_error_responses: &'a HashSet<(String, Yaml)>,
+    dropped_keys: Vec<String>,
+}
+
+impl DroppedKeyCollector<'_> {
+    fn drop_key(&mut self, path: &str, key: &str) {
+        self.dropped_keys.push(join_yaml_path(path, key));
+    }
+
+    fn walk_keys(&mut self, yaml: &Yaml, path: &str, keys: &[&str]) {
+        for (key, _) in hash_entries(yaml) {
+            if !keys.contains(&key.as_str()) {
+                self.drop_key(path, &key);
+            }
+        }
+    }
+
+    // NOTE: $defs は最上位のスキーマ (とその $defs の中) でだけモデルとして出力する
+    fn walk_schema(&mut self, yaml: &Yaml, path: &str, is_root: bool) {
+        for (key, value) in hash_entries(yaml) {
+            let key_path = join_yaml_path(path, &key);
+            match key.as_str() {
+                "properties" => hash_entries(value).for_each(|(name, property)| {
+                    self.walk_schema(property, &join_yaml_path(&key_path, &name), false)
+                }),
+                "items" => self.walk_schema(value, &key_path, false),
+                "oneOf" | "allOf" | "prefixItems" => array_items(value).for_each(|(i, item)| {
+                    self.walk_schema(item, &format!("{}[{}]", key_path, i), false)
+                }),
+                "$defs" if is_root => hash_entries(value).for_each(|(name, def)| {
+                    self.walk_schema(def, &join_yaml_path(&key_path, &name), true)
+                }),
+                key if SCHEMA_KEYS.contains(&key) => {}
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    fn walk_content(&mut self, yaml: &Yaml, path: &str) {
+        for (media_type, media_type_object) in hash_entries(yaml) {
+            let media_type_path = join_yaml_path(path, &media_type);
+            for (key, value) in hash_entries(media_type_object) {
+                let key_path = join_yaml_path(&media_type_path, &key);
+                match key.as_str() {
+                    "schema" => self.walk_schema(value, &key_path, false),
+                    "encoding" => hash_entries(value).for_each(|(name, encoding)| {
+                        self.walk_keys(encoding, &join_yaml_path(&key_path, &name), &ENCODING_KEYS)
+                    }),
+                    _ => self.drop_key(&media_type_path, &key),
+                }
+            }
+        }
+    }
+
+    fn walk_parameter(&mut self, yaml: &Yaml, path: &str) {
+        if yaml.as_hash().map(is_ref).unwrap_or(true) {
+            return;
+        }
+        for (key, value) in hash_entries(yaml) {
+            match key.as_str() {
+                "schema" => self.walk_schema(value, &join_yaml_path(path, &key), false),
+                "content" => self.walk_content(value, &join_yaml_path(path, &key)),
+                key if PARAMETER_KEYS.contains(&key) => {}
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    fn walk_header(&mut self, yaml: &Yaml, path: &str) {
+        if yaml.as_hash().map(is_ref).unwrap_or(true) {
+            return;
+        }
+        for (key, value) in hash_entries(yaml) {
+            match key.as_str() {
+                "schema" => self.walk_schema(value, &join_yaml_path(path, &key), false),
+                key if HEADER_KEYS.contains(&key) => {}
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    // NOTE: required は body を省略可能にするかどうかとして出力する
+    //       description は component として model にする場合だけ @doc として出力する
+    fn walk_request_body(&mut self, yaml: &Yaml, path: &str, is_component: bool) {
+        if yaml.as_hash().map(is_ref).unwrap_or(true) {
+            return;
+        }
+        for (key, value) in hash_entries(yaml) {
+            match key.as_str() {
+                "content" => self.walk_content(value, &join_yaml_path(path, &key)),
+                "required" => {}
+                "description" if is_component => {}
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    // NOTE: description は response を model にする場合 (component や named_responses、default、共通のエラーレスポンス) だけ @doc として出力する
+    fn walk_response(&mut self, yaml: &Yaml, path: &str, is_component: bool) {
+        self.walk_response_with_status(yaml, path, is_component, None);
+    }
+
+    fn walk_response_with_status(
+        &mut self,
+        yaml: &Yaml,
+        path: &str,
+        is_component: bool,
+        status: Option<&str>,
+    ) {
+        if yaml.as_hash().map(is_ref).unwrap_or(true) {
+            return;
+        }
+        let is_model = is_component
+            || self.options.named_responses.is_some()
+            || status == Some("default")
+            || status
+                .map(|status| {
+                    self.shared_error_responses
+                        .contains(&(status.to_string(), strip_response_examples(yaml)))
+                })
+                .unwrap_or(false);
+        for (key, value) in hash_entries(yaml) {
+            let key_path = join_yaml_path(path, &key);
+            match key.as_str() {
+                "content" => self.walk_content(value, &key_path),
+                "headers" => hash_entries(value).for_each(|(name, header)| {
+                    self.walk_header(header, &join_yaml_path(&key_path, &name))
+                }),
+                "links" if self.options.extensions == ExtensionHandling::Emit => {}
+                "description" if is_model => {}
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    fn walk_operation(&mut self, yaml: &Yaml, path: &str) {
+        for (key, value) in hash_entries(yaml) {
+            let key_path = join_yaml_path(path, &key);
+            match key.as_str() {
+                "operationId" => {}
+                "parameters" => array_items(value).for_each(|(i, parameter)| {
+                    self.walk_parameter(parameter, &format!("{}[{}]", key_path, i))
+                }),
+                "requestBody" => self.walk_request_body(value, &key_path, false),
+                "responses" => hash_entries(value).for_each(|(status, response)| {
+                    self.walk_response_with_status(
+                        response,
+                        &join_yaml_path(&key_path, &status),
+                        false,
+                        Some(&status),
+                    )
+                }),
+                "callbacks" => hash_entries(value).for_each(|(name, callback)| {
+                    let callback_path = join_yaml_path(&key_path, &name);
+                    hash_entries(callback).for_each(|(expression, path_item)| {
+                        self.walk_path_item(path_item, &join_yaml_path(&callback_path, &expression))
+                    })
+                }),
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    fn walk_path_item(&mut self, yaml: &Yaml, path: &str) {
+        if yaml.as_hash().map(is_ref).unwrap_or(true) {
+            return;
+        }
+        for (key, value) in hash_entries(yaml) {
+            let key_path = join_yaml_path(path, &key);
+            match key.as_str() {
+                key if OPERATION_METHODS.contains(&key) => self.walk_operation(value, &key_path),
+                "parameters" => array_items(value).for_each(|(i, parameter)| {
+                    self.walk_parameter(parameter, &format!("{}[{}]", key_path, i))
+                }),
+                _ => self.drop_key(path, &key),
+            }
+        }
+    }
+
+    // NOTE: components の中で parse しないセクション (securitySchemes など) は Unknown として報告する
+    fn walk_components(&mut self, yaml: &Yaml, path: &str) {
+        for (section, components) in hash_entries(yaml) {
+            let section_path = join_yaml_path(path, &section);
+            for (name, component) in hash_entries(components) {
+                let component_path = join_yaml_path(&section_path, &name);
+                match section.as_str() {
+                    "schemas" => self.walk_schema(component, &component_path, true),
+                    "parameters" => self.walk_parameter(component, &component_path),
+                    "responses" => self.walk_response(component, &component_path, true),
+                    "headers" => self.walk_header(component, &component_path),
+                    "requestBodies" => self.walk_request_body(component, &component_path, true),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // NOTE: servers と tags は parse しているが、対応する出力がないのでまとめて失われる
+    fn walk_document(&mut self, yaml: &Yaml) {
+        for (key, value) in hash_entries(yaml) {
+            match key.as_str() {
+                "info" => {
+                    for (info_key, info_value) in hash_entries(value) {
+                        match info_key.as_str() {
+                            "contact" => self.walk_keys(info_value, "info.contact", &CONTACT_KEYS),
+                            info_key if INFO_KEYS.contains(&info_key) => {}
+                            _ => self.drop_key("info", &info_key),
+                        }
+                    }
+                }
+                "servers" | "tags" => self.drop_key("", &key),
+                "paths" | "webhooks" => hash_entries(value).for_each(|(name, path_item)| {
+                    self.walk_path_item(path_item, &join_yaml_path(&key, &name))
+                }),
+                "components" => self.walk_components(value, "components"),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn build_unknown_keys(file_node: &OpenAPIFileNode) -> Vec<String> {
+    file_node
+        .contents
+        .iter()
+        .filter_map(|node| {
+            if let OpenAPINode::Unknown(hash) = node {
+                Some(hash)
+            } else {
+                None
+            }
+        })
+        .flat_map(|hash| hash.iter())
+        .flat_map(|(key, value)| {
+            let key = key_to_string(key);
+            match (key.as_str(), value) {
+                // NOTE: parse するセクションの中で解釈できなかった component は、セクションではなく component 単位で報告する
+                ("components", Yaml::Hash(_)) => hash_entries(value)
+                    .flat_map(|(section, components)| {
+                        let section_path = join_yaml_path(&key, &section);
+                        if PARSED_COMPONENT_SECTIONS.contains(&section.as_str()) {
+                            hash_entries(components)
+                                .map(|(name, _)| join_yaml_path(&section_path, &name))
+                                .collect()
+                        } else {
+                            vec![section_path]
+                        }
+                    })
+                    .collect(),
+                _ => vec![key],
+            }
+        })
+        .collect()
+}
+
+// NOTE: 共通のレスポンスにまとめるときは examples を比較しないので、ここでも取り除いてから比べる
+fn strip_response_examples(response: &Yaml) -> Yaml {
+    let mut response = response.clone();
+    if let Yaml::Hash(response) = &mut response {
+        if let Some(Yaml::Hash(content)) = response.get_mut(&Yaml::String("content".to_string())) {
+            content.iter_mut().for_each(|(_, media_type)| {
+                if let Yaml::Hash(media_type) = media_type {
+                    media_type.remove(&Yaml::String("example".to_string()));
+                    media_type.remove(&Yaml::String("examples".to_string()));
+                }
+            });
+        }
+    }
+
+    response
+}
+
+fn count_error_responses(path_item: &Yaml, counts: &mut HashMap<(String, Yaml), usize>) {
+    for (method, operation) in hash_entries(path_item) {
+        if !OPERATION_METHODS.contains(&method.as_str()) {
+            continue;
+        }
+        for (status, response) in hash_entries(&operation["responses"]) {
+            let is_error = status
+                .parse::<u16>()
+                .map(|code| code >= 400)
+                .unwrap_or(false);
+            if is_error && !response.as_hash().map(is_ref).unwrap_or(true) {
+                *counts
+                    .entry((status, strip_response_examples(response)))
+                    .or_default() += 1;
+            }
+        }
+        for (_, callback) in hash_entries(&operation["callbacks"]) {
+            hash_entries(callback)
+                .for_each(|(_, path_item)| count_error_responses(path_item, counts));
+        }
+    }
+}
+
+// NOTE: extract_shared_error_responses と同じく、複数の operation に同じ内容で現れるエラーレスポンスは model になる
+fn build_shared_error_responses(hashes: &[(PathBuf, yaml::Hash)]) -> HashSet<(String, Yaml)> {
+    let mut counts = HashMap::new();
+    for (_, hash) in hashes {
+        let yaml = Yaml::Hash(hash.clone());
+        if yaml["paths"].is_badvalue() && yaml["webhooks"].is_badvalue() {
+            count_error_responses(&yaml, &mut counts);
+        } else {
+            hash_entries(&yaml["paths"])
+                .chain(hash_entries(&yaml["webhooks"]))
+                .for_each(|(_, path_item)| count_error_responses(path_item, &mut counts));
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count >= 2)
+        .map(|(response, _)| response)
+        .collect()
+}
+
+fn build_dropped_keys(
+    hash: &yaml::Hash,
+    file_node: &OpenAPIFileNode,
+    options: &CompilerOptions,
+    shared_error_responses: &HashSet<(String, Yaml)>,
+) -> Vec<String> {
+    let yaml = Yaml::Hash(hash.clone());
+    let has_node = |f: fn(&OpenAPINode) -> bool| file_node.contents.iter().any(f);
+    let mut collector = DroppedKeyCollector {
+        options,
+        shared_error_responses,
+        dropped_keys: vec![],
+    };
+
+    if has_node(|node| matches!(node, OpenAPINode::Metadata(_) | OpenAPINode::Info(_))) {
+        collector.walk_document(&yaml);
+    } else if has_node(|node| matches!(node, OpenAPINode::Example(_))) {
+        // NOTE: example のファイルは後処理で取り除くので、全体が失われる
+        hash_entries(&yaml).for_each(|(key, _)| collector.drop_key("", &key));
+    } else if hash.contains_key(&Yaml::String("components".to_string())) {
+        collector.walk_components(&yaml["components"], "components");
+    } else if let Some(kind) = file_node.contents.iter().find_map(|node| match node {
+        OpenAPINode::Component(component) => Some(&component.kind),
+        _ => None,
+    }) {
+        match kind {
+            ComponentKind::Parameter => collector.walk_parameter(&yaml, ""),
+            ComponentKind::Response => collector.walk_response(&yaml, "", true),
+            ComponentKind::Header => collector.walk_header(&yaml, ""),
+            ComponentKind::RequestBody => collector.walk_request_body(&yaml, "", true),
+        }
+    } else if has_node(|node| {
+        matches!(node, OpenAPINode::Operation(_) | OpenAPINode::Parameters(_))
+    }) {
+        collector.walk_path_item(&yaml, "");
+    } else if has_node(|node| matches!(node, OpenAPINode::DataModel(_))) {
+        collector.walk_schema(&yaml, "", true);
+    }
+
+    collector.dropped_keys
+}
+
+// NOTE: Unknown として報告するキーの中は、失われたキーとしては重複して報告しない
+fn is_under_yaml_path(path: &str, parent: &str) -> bool {
+    path == parent
+        || path
+            .strip_prefix(parent)
+            .map(|rest| rest.starts_with('.') || rest.starts_with('['))
+            .unwrap_or(false)
+}
+
+fn build_file_report(
+    hash: &yaml::Hash,
+    file_node: &OpenAPIFileNode,
+    options: &CompilerOptions,
+    shared_error_responses: &HashSet<(String, Yaml)>,
+) -> FileReport {
+    let unknown_keys = build_unknown_keys(file_node);
+    let dropped_keys = build_dropped_keys(hash, file_node, options, shared_error_responses)
+        .into_iter()
+        .filter(|path| {
+            !unknown_keys
+                .iter()
+                .any(|unknown| is_under_yaml_path(path, unknown))
+        })
+        .collect();
+
+    FileReport {
+        source: file_node.path.clone(),
+        output: file_node.path.with_extension("tsp"),
+        dropped_keys,
+        unknown_keys,
+        fallback_names: vec![],
+    }
+}
+
+// NOTE: hashes は parse する前の yaml (swagger 2.0 の場合は 3.0 に変換したもの)
+pub(crate) fn build_conversion_report(
+    hashes: &[(PathBuf, yaml::Hash)],
+    file_nodes: &[OpenAPIFileNode],
+    options: &CompilerOptions,
+) -> ConversionReport {
+    let shared_error_responses = build_shared_error_responses(hashes);
+    let hashes = hashes
+        .iter()
+        .map(|(path, hash)| (path, hash))
+        .collect::<HashMap<_, _>>();
+
+    ConversionReport {
+        files: file_nodes
+            .iter()
+            .map(|file_node| {
+                let hash = hashes
+                    .get(&file_node.path)
+                    .expect("Failed to find source yaml");
+                build_file_report(hash, file_node, options, &shared_error_responses)
+            })
+            .collect(),
+    }
+}
+
+fn build_fallback_names(file_node: &TypeSpecFileNode, naming: &NamingOptions) -> Vec<String> {
+    let mut names = vec![];
+    for_each_declaration(&file_node.contents, "", &mut |_, name| {
+        if name == naming.fallback_model || name == naming.fallback_model_alias {
+            names.push(name.to_string());
+        }
+    });
+    for_each_reference(&file_node.contents, "", &mut |_, identifier| {
+        if identifier.name == naming.fallback_component_ref {
+            names.push(identifier.to_string());
+        }
+    });
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+// NOTE: 名前の重複を解消する前の TypeSpec のファイルから、fallback の名前を使った箇所を集める
+pub(crate) fn add_fallback_names(
+    report: &mut ConversionReport,
+    file_nodes: &[TypeSpecFileNode],
+    naming: &NamingOptions,
+) {
+    for file_node in file_nodes.iter() {
+        let names = build_fallback_names(file_node, naming);
+        if names.is_empty() {
+            continue;
+        }
+        match report
+            .files
+            .iter_mut()
+            .find(|file| file.output == file_node.path)
+        {
+            Some(file) => file.fallback_names.extend(names),
+            None => report.files.push(FileReport {
+                source: file_node.path.clone(),
+                output: file_node.path.clone(),
+                dropped_keys: vec![],
+                unknown_keys: vec![],
+                fallback_names: names,
+            }),
+        }
+    }
+}
+
+fn display_path(root_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(root_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn json_string_array(items: &[String]) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+
+    let items = items
+        .iter()
+        .map(|item| format!("        {}", json_string_literal(item)))
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!("[\n{}\n      ]", items)
+}
+
+impl ConversionReport {
+    pub fn dropped_key_count(&self) -> usize {
+        self.files.iter().map(|file| file.dropped_keys.len()).sum()
+    }
+
+    pub fn unknown_key_count(&self) -> usize {
+        self.files.iter().map(|file| file.unknown_keys.len()).sum()
+    }
+
+    pub fn fallback_name_count(&self) -> usize {
+        self.files
+            .iter()
+            .map(|file| file.fallback_names.len())
+            .sum()
+    }
+
+    // NOTE: パスは root_dir からの相対パスで出力する
+    pub fn print(&self, format: ReportFormat, root_dir: &Path) -> String {
+        match format {
+            ReportFormat::Human => self.print_human(root_dir),
+            ReportFormat::Json => self.print_json(root_dir),
+        }
+    }
+
+    fn print_human(&self, root_dir: &Path) -> String {
+        let mut lines = vec![];
+        for file in self.files.iter().filter(|file| !file.is_lossless()) {
+            lines.push(display_path(root_dir, &file.source));
+            file.dropped_keys
+                .iter()
+                .for_each(|key| lines.push(format!("  dropped   {}", key)));
+            file.unknown_keys
+                .iter()
+                .for_each(|key| lines.push(format!("  unknown   {}", key)));
+            file.fallback_names
+                .iter()
+                .for_each(|name| lines.push(format!("  fallback  {}", name)));
+        }
+        let lossy_file_count = self.files.iter().filter(|file| !file.is_lossless()).count();
+        lines.push(format!(
+            "report: {} dropped key(s), {} unknown section(s), {} fallback name(s) in {} of {} file(s)",
+            self.dropped_key_count(),
+            self.unknown_key_count(),
+            self.fallback_name_count(),
+            lossy_file_count,
+            self.files.len()
+        ));
+
+        lines.join("\n") + "\n"
+    }
+
+    fn print_json(&self, root_dir: &Path) -> String {
+        let files = self
+            .files
+            .iter()
+            .map(|file| {
+                format!(
+                    "    {{
+      \"source\": {},
+      \"output\": {},
+      \"dropped_keys\": {},
+      \"unknown_keys\": {},
+      \"fallback_names\": {}
+    }}",
+                    json_string_literal(&display_path(root_dir, &file.source)),
+                    json_string_literal(&display_path(root_dir, &file.output)),
+                    json_string_array(&file.dropped_keys),
+                    json_string_array(&file.unknown_keys),
+                    json_string_array(&file.fallback_names)
+                )
+            })
+            .collect::<Vec<_>>();
+        let files = if files.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[\n{}\n  ]", files.join(",\n"))
+        };
+
+        format!("{{\n  \"files\": {}\n}}\n", files)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_join_yaml_path() {
+        assert_eq!(join_yaml_path("", "info"), "info");
+        assert_eq!(join_yaml_path("info", "description"), "info.description");
+        assert_eq!(
+            join_yaml_path("content", "application/vnd.api+json"),
+            "content[\"application/vnd.api+json\"]"
+        );
+        assert!(is_under_yaml_path(
+            "components.securitySchemes.api",
+            "components.securitySchemes"
+        ));
+        assert!(!is_under_yaml_path(
+            "components.schemas.Pet",
+            "components.schema"
+        ));
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::type_spec::node::{
+    sanitize_identifier, IdentifierNode, ModelContentNode, OperationParameterNode,
+    TypeSpecFileNode, TypeSpecNode,
+};
+
+// NOTE: 出力した TypeSpec を tsp でコンパイルする前に検査する
+//       同じ名前空間での宣言の重複と、どの宣言にも解決できない参照を見つける
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    DuplicateDeclaration {
+        name: String,
+        paths: Vec<PathBuf>,
+    },
+    UnresolvedReference {
+        name: String,
+        path: PathBuf,
+    },
+    // NOTE: disambiguate_names の場合に、重複していた宣言の名前を変えたもの
+    Renamed {
+        name: String,
+        new_name: String,
+        path: PathBuf,
+    },
+}
+
+fn join_namespace(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+fn parent_namespace(namespace: &str) -> Option<&str> {
+    if namespace.is_empty() {
+        None
+    } else {
+        Some(
+            namespace
+                .rsplit_once('.')
+                .map(|(parent, _)| parent)
+                .unwrap_or(""),
+        )
+    }
+}
+
+fn for_each_declaration_mut(
+    contents: &mut [TypeSpecNode],
+    namespace: &str,
+    f: &mut dyn FnMut(&str, &mut String),
+) {
+    for node in contents.iter_mut() {
+        match node {
+            TypeSpecNode::NameSpace(namespace_node) => for_each_declaration_mut(
+                &mut namespace_node.contents,
+                &join_namespace(namespace, &namespace_node.name),
+                f,
+            ),
+            TypeSpecNode::Interface(interface) => f(namespace, &mut interface.name),
+            TypeSpecNode::Model(model) => f(namespace, &mut model.name.name),
+            TypeSpecNode::Enum(enum_node) => f(namespace, &mut enum_node.title.name),
+            TypeSpecNode::Union(union) => f(namespace, &mut union.name.name),
+            TypeSpecNode::ModelAlias(alias) => f(namespace, &mut alias.identifier.name),
+            TypeSpecNode::Scalar(scalar) => f(namespace, &mut scalar.name.name),
+            TypeSpecNode::Imports(_) => {}
+        }
+    }
+}
+
+pub(crate) fn for_each_declaration(
+    contents: &[TypeSpecNode],
+    namespace: &str,
+    f: &mut dyn FnMut(&str, &str),
+) {
+    for node in contents.iter() {
+        match node {
+            TypeSpecNode::NameSpace(namespace_node) => for_each_declaration(
+                &namespace_node.contents,
+                &join_namespace(namespace, &namespace_node.name),
+                f,
+            ),
+            TypeSpecNode::Interface(interface) => f(namespace, &interface.name),
+            TypeSpecNode::Model(model) => f(namespace, &model.name.name),
+            TypeSpecNode::Enum(enum_node) => f(namespace, &enum_node.title.name),
+            TypeSpecNode::Union(union) => f(namespace, &union.name.name),
+            TypeSpecNode::ModelAlias(alias) => f(namespace, &alias.identifier.name),
+            TypeSpecNode::Scalar(scalar) => f(namespace, &scalar.name.name),
+            TypeSpecNode::Imports(_) => {}
+        }
+    }
+}
+
+fn for_each_model_ref(content: &ModelContentNode, f: &mut dyn FnMut(&IdentifierNode)) {
+    match content {
+        ModelContentNode::Record(record) => record
+            .properties
+            .iter()
+            .for_each(|property| for_each_model_ref(&property.value, f)),
+        ModelContentNode::Array(array) => for_each_model_ref(&array.item_type, f),
+        ModelContentNode::ModelRef(identifier) => f(identifier),
+        ModelContentNode::Union(items)
+        | ModelContentNode::Intersect(items)
+        | ModelContentNode::Tuple(items)
+        | ModelContentNode::Template(_, items) => {
+            items.iter().for_each(|item| for_each_model_ref(item, f))
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn for_each_reference(
+    contents: &[TypeSpecNode],
+    namespace: &str,
+    f: &mut dyn FnMut(&str, &IdentifierNode),
+) {
+    for node in contents.iter() {
+        match node {
+            TypeSpecNode::NameSpace(namespace_node) => for_each_reference(
+                &namespace_node.contents,
+                &join_namespace(namespace, &namespace_node.name),
+                f,
+            ),
+            TypeSpecNode::Interface(interface) => {
+                for operation in interface.operations.iter() {
+                    operation
+                        .parameters
+                        .iter()
+                        .for_each(|parameter| match parameter {
+                            OperationParameterNode::Parameter(parameter) => {
+                                for_each_model_ref(&parameter.type_model, &mut |id| {
+                                    f(namespace, id)
+                                })
+                            }
+                            OperationParameterNode::Spread(content) => {
+                                for_each_model_ref(content, &mut |id| f(namespace, id))
+                            }
+                        });
+                    operation.responses.iter().for_each(|response| {
+                        for_each_model_ref(response, &mut |id| f(namespace, id))
+                    });
+                }
+            }
+            TypeSpecNode::Model(model) => model.record.properties.iter().for_each(|property| {
+                for_each_model_ref(&property.value, &mut |id| f(namespace, id))
+            }),
+            TypeSpecNode::ModelAlias(alias) => {
+                for_each_model_ref(&alias.alias_type, &mut |id| f(namespace, id))
+            }
+            TypeSpecNode::Enum(_)
+            | TypeSpecNode::Union(_)
+            | TypeSpecNode::Scalar(_)
+            | TypeSpecNode::Imports(_) => {}
+        }
+    }
+}
+
+// NOTE: 修飾されていない参照は、今の名前空間から親の名前空間へ順に探す (TypeSpec の名前解決と同じ)
+fn is_resolved(
+    declarations: &HashSet<(String, String)>,
+    namespace: &str,
+    identifier: &IdentifierNode,
+) -> bool {
+    let name = sanitize_identifier(&identifier.name);
+    if let Some(qualified_namespace) = &identifier.namespace {
+        return declarations.contains(&(qualified_namespace.clone(), name));
+    }
+
+    let mut scope = Some(namespace);
+    while let Some(current) = scope {
+        if declarations.contains(&(current.to_string(), name.clone())) {
+            return true;
+        }
+        scope = parent_namespace(current);
+    }
+
+    false
+}
+
+// NOTE: (ファイルの番号, ファイル内での宣言の順番) ごとの宣言の名前空間と名前
+fn collect_declarations(
+    file_nodes: &mut [TypeSpecFileNode],
+) -> BTreeMap<(String, String), Vec<(usize, usize)>> {
+    let mut declarations: BTreeMap<(String, String), Vec<(usize, usize)>> = BTreeMap::new();
+    for (file_index, file_node) in file_nodes.iter_mut().enumerate() {
+        let mut ordinal = 0;
+        for_each_declaration_mut(&mut file_node.contents, "", &mut |namespace, name| {
+            declarations
+                .entry((namespace.to_string(), sanitize_identifier(name)))
+                .or_default()
+                .push((file_index, ordinal));
+            ordinal += 1;
+        });
+    }
+
+    declarations
+}
+
+// NOTE: ルートに近いファイルの宣言に元の名前を残し、それ以外に連番を付ける
+fn build_renames(
+    file_nodes: &[TypeSpecFileNode],
+    declarations: &BTreeMap<(String, String), Vec<(usize, usize)>>,
+) -> HashMap<(usize, usize), String> {
+    let mut names = declarations.keys().cloned().collect::<HashSet<_>>();
+    let mut renames = HashMap::new();
+
+    for ((namespace, name), locations) in declarations.iter() {
+        if locations.len() < 2 {
+            continue;
+        }
+        let mut locations = locations.clone();
+        locations.sort_by_key(|(file_index, ordinal)| {
+            let path = &file_nodes[*file_index].path;
+            (path.components().count(), path.clone(), *ordinal)
+        });
+        for location in locations.into_iter().skip(1) {
+            let new_name = (2..)
+                .map(|i| format!("{}{}", name, i))
+                .find(|new_name| !names.contains(&(namespace.clone(), new_name.clone())))
+                .unwrap();
+            names.insert((namespace.clone(), new_name.clone()));
+            renames.insert(location, new_name);
+        }
+    }
+
+    renames
+}
+
+pub fn validate_type_spec_file_nodes(
+    file_nodes: &mut [TypeSpecFileNode],
+    disambiguate: bool,
+) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    let declarations = collect_declarations(file_nodes);
+
+    if disambiguate {
+        let renames = build_renames(file_nodes, &declarations);
+        for (file_index, file_node) in file_nodes.iter_mut().enumerate() {
+            let path = file_node.path.clone();
+            let mut ordinal = 0;
+            for_each_declaration_mut(&mut file_node.contents, "", &mut |namespace, name| {
+                if let Some(new_name) = renames.get(&(file_index, ordinal)) {
+                    issues.push(ValidationIssue::Renamed {
+                        name: join_namespace(namespace, name),
+                        new_name: join_namespace(namespace, new_name),
+                        path: path.clone(),
+                    });
+                    *name = new_name.clone();
+                }
+                ordinal += 1;
+            });
+        }
+    } else {
+        for ((namespace, name), locations) in declarations.iter() {
+            if locations.len() < 2 {
+                continue;
+            }
+            let mut paths = locations
+                .iter()
+                .map(|(file_index, _)| file_nodes[*file_index].path.clone())
+                .collect::<Vec<_>>();
+            paths.dedup();
+            issues.push(ValidationIssue::DuplicateDeclaration {
+                name: join_namespace(namespace, name),
+                paths,
+            });
+        }
+    }
+
+    let declarations = collect_declarations(file_nodes)
+        .into_keys()
+        .collect::<HashSet<_>>();
+    for file_node in file_nodes.iter() {
+        let mut unresolved = vec![];
+        for_each_reference(&file_node.contents, "", &mut |namespace, identifier| {
+            if !is_resolved(&declarations, namespace, identifier) {
+                unresolved.push(identifier.to_string());
+            }
+        });
+        unresolved.sort();
+        unresolved.dedup();
+        issues.extend(
+            unresolved
+                .into_iter()
+                .map(|name| ValidationIssue::UnresolvedReference {
+                    name,
+                    path: file_node.path.clone(),
+                }),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::type_spec::node::{
+        InterfaceNode, ModelNode, NamespaceNode, RecordModelNode, RecordPropertyKey,
+        RecordPropertyNode,
+    };
+
+    fn build_model(name: &str, reference: Option<&str>) -> TypeSpecNode {
+        let properties = reference
+            .map(|reference| RecordPropertyNode {
+                decorators: Box::new(vec![]),
+                key: RecordPropertyKey::from(&"value".to_string()),
+                value: ModelContentNode::ModelRef(IdentifierNode::from(reference)),
+                required: true,
+            })
+            .into_iter()
+            .collect();
+
+        TypeSpecNode::Model(ModelNode {
+            decorators: Box::new(vec![]),
+            name: IdentifierNode::from(name),
+            record: RecordModelNode {
+                properties: Box::new(properties),
+            },
+        })
+    }
+
+    fn build_interface_file(path: &str, name: &str) -> TypeSpecFileNode {
+        let interface = TypeSpecNode::Interface(InterfaceNode {
+            name: name.to_string(),
+            decorators: Box::new(vec![]),
+            operations: Box::new(vec![]),
+        });
+        let namespace = TypeSpecNode::NameSpace(NamespaceNode {
+            decorators: Box::new(vec![]),
+            name: "PetService".to_string(),
+            contents: Box::new(vec![interface]),
+        });
+
+        TypeSpecFileNode::new(path.into(), vec![], vec![], vec![namespace])
+    }
+
+    #[test]
+    fn test_validate_type_spec_file_nodes() {
+        let mut file_nodes = vec![
+            TypeSpecFileNode::new(
+                "/spec/models/Pet.tsp".into(),
+                vec![],
+                vec![],
+                vec![build_model("Pet", Some("Owner"))],
+            ),
+            TypeSpecFileNode::new(
+                "/spec/models/store/Pet.tsp".into(),
+                vec![],
+                vec![],
+                vec![build_model("Pet", Some("Pet"))],
+            ),
+            build_interface_file("/spec/paths/pets_{id}.tsp", "PetsId"),
+            build_interface_file("/spec/paths/pets_id.tsp", "PetsId"),
+        ];
+
+        assert_eq!(
+            validate_type_spec_file_nodes(&mut file_nodes, false),
+            vec![
+                ValidationIssue::DuplicateDeclaration {
+                    name: "Pet".to_string(),
+                    paths: vec![
+                        "/spec/models/Pet.tsp".into(),
+                        "/spec/models/store/Pet.tsp".into(),
+                    ],
+                },
+                ValidationIssue::DuplicateDeclaration {
+                    name: "PetService.PetsId".to_string(),
+                    paths: vec![
+                        "/spec/paths/pets_{id}.tsp".into(),
+                        "/spec/paths/pets_id.tsp".into(),
+                    ],
+                },
+                ValidationIssue::UnresolvedReference {
+                    name: "Owner".to_string(),
+                    path: "/spec/models/Pet.tsp".into(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            validate_type_spec_file_nodes(&mut file_nodes, true)[..2],
+            vec![
+                ValidationIssue::Renamed {
+                    name: "Pet".to_string(),
+                    new_name: "Pet2".to_string(),
+                    path: "/spec/models/store/Pet.tsp".into(),
+                },
+                ValidationIssue::Renamed {
+                    name: "PetService.PetsId".to_string(),
+                    new_name: "PetService.PetsId2".to_string(),
+                    path: "/spec/paths/pets_{id}.tsp".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            validate_type_spec_file_nodes(&mut file_nodes, false),
+            vec![ValidationIssue::UnresolvedReference {
+                name: "Owner".to_string(),
+                path: "/spec/models/Pet.tsp".into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,654 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use rayon::prelude::*;
+use yaml_rust::Yaml;
+
+use crate::compiler::project::build_project_files;
+use crate::compiler::validate::validate_type_spec_file_nodes;
+use crate::compiler::{
+    check_diagnostics, prepare_openapi_file_nodes, report_validation_issues, CompilerEnv,
+    CompilerOptions,
+};
+use crate::file_source::{normalize_path, DiskFileSource, FileSource};
+use crate::logger::{self, DiagnosticCollector};
+use crate::openapi_parser::node::OpenAPIFileNode;
+use crate::openapi_parser::parse_yaml_files_partially;
+use crate::type_spec::node::TypeSpecFileNode;
+use crate::type_spec::node_builder::build_type_spec_file_node;
+use crate::yaml_loader::{collect_file_refs, list_yaml_files, read_yaml_file, YamlFile};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+#[derive(Debug)]
+struct WatchedFile {
+    modified: SystemTime,
+    yaml: YamlFile,
+    // NOTE: 後処理の前の状態を持っておき、毎回複製してから後処理する
+    node: OpenAPIFileNode,
+}
+
+#[derive(Debug, Default)]
+struct WatchState {
+    files: BTreeMap<PathBuf, WatchedFile>,
+    // NOTE: yaml ファイルから、そのファイルの出力が依存している yaml ファイルへの辺
+    dependencies: HashMap<PathBuf, HashSet<PathBuf>>,
+    env_fingerprint: Option<String>,
+    last_scan: HashMap<PathBuf, SystemTime>,
+    // NOTE: 宣言の重複などの検査は全てのファイルが必要なので、検査する前の出力を全て持っておく
+    outputs: BTreeMap<PathBuf, TypeSpecFileNode>,
+}
+
+#[derive(Debug, Default)]
+struct CycleReport {
+    changed: usize,
+    removed: Vec<PathBuf>,
+    rebuilt: Vec<PathBuf>,
+    written: Vec<PathBuf>,
+    full_rebuild: bool,
+}
+
+// NOTE: 出力の書き出し先。テストではメモリ上に書き出す
+trait OutputWriter {
+    fn read_to_string(&self, path: &Path) -> Option<String>;
+
+    fn write(&mut self, path: &Path, content: String);
+
+    fn remove(&mut self, path: &Path);
+}
+
+struct DiskOutputWriter;
+
+impl OutputWriter for DiskOutputWriter {
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+
+    fn write(&mut self, path: &Path, content: String) {
+        fs::write(path, content).expect("failed to write file");
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if path.exists() {
+            fs::remove_file(path).expect("failed to remove file");
+        }
+    }
+}
+
+fn scan_files(root_dir: &PathBuf) -> HashMap<PathBuf, SystemTime> {
+    list_yaml_files(root_dir)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+fn build_ref_dependencies(yaml_file: &YamlFile) -> HashSet<PathBuf> {
+    collect_file_refs(&yaml_file.path, &yaml_file.content)
+        .into_iter()
+        .collect()
+}
+
+// NOTE: object_file_path_map 経由で別ファイルのモデルを参照している場合は、出力の import に現れる
+fn build_import_dependencies(file_node: &TypeSpecFileNode) -> HashSet<PathBuf> {
+    let base_dir = file_node.path.parent().expect("Failed to get parent dir");
+
+    file_node
+        .imports
+        .iter()
+        .filter(|import| import.is_relative())
+        .map(|import| normalize_path(&base_dir.join(import.lib_name.replace(".tsp", ".yaml"))))
+        .filter(|path| path.with_extension("tsp") != file_node.path)
+        .collect()
+}
+
+// NOTE: ファイル全体で決まる情報が変わった場合は、どのファイルの出力も変わりうる
+fn build_env_fingerprint(env: &CompilerEnv) -> String {
+    format!(
+        "{:?}",
+        (
+            &env.namespace,
+            &env.openapi_version,
+            env.path_file_map.iter().collect::<BTreeMap<_, _>>(),
+            env.webhook_file_map.iter().collect::<BTreeMap<_, _>>(),
+            env.object_file_path_map.iter().collect::<BTreeMap<_, _>>(),
+            env.error_model_names.iter().collect::<BTreeSet<_>>(),
+        )
+    )
+}
+
+fn is_root_file(yaml_file: &YamlFile) -> bool {
+    yaml_file
+        .content
+        .first()
+        .and_then(|yaml| yaml.as_hash())
+        .map(|hash| {
+            hash.contains_key(&Yaml::String("openapi".to_string()))
+                || hash.contains_key(&Yaml::String("swagger".to_string()))
+        })
+        .unwrap_or(false)
+}
+
+impl WatchState {
+    fn collect_dependents(&self, changed: &HashSet<PathBuf>) -> HashSet<PathBuf> {
+        let mut affected = changed.clone();
+        let mut stack = changed.iter().cloned().collect::<Vec<_>>();
+
+        while let Some(path) = stack.pop() {
+            self.dependencies
+                .iter()
+                .filter(|(_, dependencies)| dependencies.contains(&path))
+                .for_each(|(dependent, _)| {
+                    if affected.insert(dependent.clone()) {
+                        stack.push(dependent.clone());
+                    }
+                });
+        }
+
+        affected
+    }
+
+    fn run_cycle(
+        &mut self,
+        source: &dyn FileSource,
+        root_dir: &Path,
+        scan: &HashMap<PathBuf, SystemTime>,
+        options: &CompilerOptions,
+        output: &mut dyn OutputWriter,
+    ) -> CycleReport {
+        let mut report = CycleReport::default();
+
+        // NOTE: 編集途中で yaml が壊れている場合は、状態を変えずに次の変更を待つ
+        let changed = scan
+            .iter()
+            .filter(|(path, modified)| {
+                self.files
+                    .get(*path)
+                    .map(|file| file.modified != **modified)
+                    .unwrap_or(true)
+            })
+            .map(|(path, modified)| read_yaml_file(source, path).map(|yaml| (yaml, *modified)))
+            .collect::<Result<Vec<_>, _>>();
+        let changed = match changed {
+            Ok(changed) => changed,
+            Err(error) => {
+                logger::error!("{}", error);
+                return report;
+            }
+        };
+        let removed = self
+            .files
+            .keys()
+            .filter(|path| !scan.contains_key(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+        let is_structure_changed = !removed.is_empty()
+            || changed
+                .iter()
+                .any(|(yaml, _)| !self.files.contains_key(&yaml.path) || is_root_file(yaml));
+
+        let mut yaml_files = self
+            .files
+            .iter()
+            .filter(|(path, _)| scan.contains_key(*path))
+            .map(|(path, file)| (path.clone(), file.yaml.clone()))
+            .collect::<BTreeMap<_, _>>();
+        changed.iter().for_each(|(yaml, _)| {
+            yaml_files.insert(yaml.path.clone(), yaml.clone());
+        });
+        let yaml_files = yaml_files.into_values().collect::<Vec<_>>();
+
+        // NOTE: ルートのファイルが変わった場合は swagger 2.0 の判定が変わりうるので全て parse し直す
+        let targets = if is_structure_changed {
+            yaml_files.iter().map(|yaml| yaml.path.clone()).collect()
+        } else {
+            changed
+                .iter()
+                .map(|(yaml, _)| yaml.path.clone())
+                .collect::<HashSet<_>>()
+        };
+        let mut parsed = parse_yaml_files_partially(&yaml_files, &targets)
+            .into_iter()
+            .map(|node| (node.path.clone(), node))
+            .collect::<HashMap<_, _>>();
+        let modified_map = changed
+            .iter()
+            .map(|(yaml, modified)| (yaml.path.clone(), *modified))
+            .collect::<HashMap<_, _>>();
+        let files = yaml_files
+            .into_iter()
+            .map(|yaml| {
+                let path = yaml.path.clone();
+                let previous = self.files.get(&path);
+                let modified = modified_map
+                    .get(&path)
+                    .or(previous.map(|file| &file.modified))
+                    .copied()
+                    .expect("Failed to get modified time");
+                let node = parsed
+                    .remove(&path)
+                    .or_else(|| previous.map(|file| file.node.clone()))
+                    .expect("Failed to find parsed file");
+                let watched = WatchedFile {
+                    modified,
+                    yaml,
+                    node,
+                };
+                (path, watched)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let openapi_file_nodes = files
+            .values()
+            .map(|file| file.node.clone())
+            .collect::<Vec<_>>();
+        let (openapi_file_nodes, env) =
+            prepare_openapi_file_nodes(root_dir, openapi_file_nodes, options);
+        let env_fingerprint = build_env_fingerprint(&env);
+
+        // NOTE: disambiguate_names で変える名前は全てのファイルの宣言で決まるので、毎回全て作り直す
+        report.full_rebuild = is_structure_changed
+            || options.disambiguate_names
+            || self.env_fingerprint.as_ref() != Some(&env_fingerprint);
+        let affected = if report.full_rebuild {
+            files.keys().cloned().collect()
+        } else {
+            self.collect_dependents(&targets)
+        };
+
+        // NOTE: 後処理で追加された responses.tsp などは入力ファイルを持たないので毎回作り直す
+        let output_paths = openapi_file_nodes
+            .iter()
+            .map(|node| node.path.with_extension("tsp"))
+            .collect::<HashSet<_>>();
+        let collector = DiagnosticCollector::new();
+        let type_spec_file_nodes = collector.scope(|| {
+            openapi_file_nodes
+                .into_par_iter()
+                .filter(|node| affected.contains(&node.path) || !files.contains_key(&node.path))
+                .map(logger::propagate(|node| {
+                    build_type_spec_file_node(node, &env)
+                }))
+                .collect::<Vec<_>>()
+        });
+        report.changed = changed.len();
+        report.rebuilt = type_spec_file_nodes
+            .iter()
+            .map(|node| node.path.clone())
+            .collect();
+
+        self.files = files;
+        self.env_fingerprint = Some(env_fingerprint);
+        self.dependencies
+            .retain(|path, _| self.files.contains_key(path));
+        for (path, file) in self.files.iter() {
+            if affected.contains(path) {
+                self.dependencies
+                    .insert(path.clone(), build_ref_dependencies(&file.yaml));
+            }
+        }
+        for file_node in type_spec_file_nodes.iter() {
+            let path = file_node.path.with_extension("yaml");
+            if let Some(dependencies) = self.dependencies.get_mut(&path) {
+                dependencies.extend(build_import_dependencies(file_node));
+            }
+        }
+        self.outputs.retain(|path, _| output_paths.contains(path));
+        for file_node in type_spec_file_nodes.into_iter() {
+            self.outputs.insert(file_node.path.clone(), file_node);
+        }
+
+        // NOTE: compile と同じく、作り直していないファイルも含めて宣言の重複などを検査する
+        let mut type_spec_file_nodes = std::mem::take(&mut self.outputs)
+            .into_values()
+            .collect::<Vec<_>>();
+        let source_paths = self.files.keys().cloned().collect::<HashSet<_>>();
+        let project_files = collector.scope(|| {
+            let issues = validate_type_spec_file_nodes(
+                &mut type_spec_file_nodes,
+                options.disambiguate_names,
+            );
+            report_validation_issues(&issues, &env.namespace_root_dir, &source_paths);
+            if options.emit_project {
+                build_project_files(&type_spec_file_nodes, &env)
+            } else {
+                vec![]
+            }
+        });
+        let is_valid = check_diagnostics(options, &collector.take());
+        let rebuilt = report.rebuilt.iter().collect::<HashSet<_>>();
+        let contents = if is_valid {
+            type_spec_file_nodes
+                .iter()
+                .filter(|file_node| rebuilt.contains(&file_node.path))
+                .map(|file_node| {
+                    (
+                        file_node.path.clone(),
+                        file_node.print(&options.print_options),
+                    )
+                })
+                .chain(
+                    project_files
+                        .into_iter()
+                        .map(|file| (file.path, file.content)),
+                )
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        self.outputs = type_spec_file_nodes
+            .into_iter()
+            .map(|file_node| (file_node.path.clone(), file_node))
+            .collect();
+        if !is_valid {
+            return report;
+        }
+
+        // NOTE: 内容が変わっていない出力は書き換えず、tsp 側の watcher が無駄に反応しないようにする
+        for (path, content) in contents {
+            if output.read_to_string(&path).as_ref() != Some(&content) {
+                output.write(&path, content);
+                report.written.push(path);
+            }
+        }
+        for path in removed.iter() {
+            output.remove(&path.with_extension("tsp"));
+        }
+        report.removed = removed;
+
+        report
+    }
+}
+
+fn print_report(root_dir: &Path, report: &CycleReport, elapsed: Duration) {
+    let relative = |path: &PathBuf| {
+        path.strip_prefix(root_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    eprintln!(
+        "watch: {} changed, {} removed, {} rebuilt{}, {} written ({} ms)",
+        report.changed,
+        report.removed.len(),
+        report.rebuilt.len(),
+        if report.full_rebuild { " (full)" } else { "" },
+        report.written.len(),
+        elapsed.as_millis()
+    );
+    report
+        .written
+        .iter()
+        .for_each(|path| eprintln!("  wrote {}", relative(path)));
+    report
+        .removed
+        .iter()
+        .for_each(|path| eprintln!("  removed {}", relative(&path.with_extension("tsp"))));
+}
+
+// NOTE: 失敗するたびにバックトレースが出ると読みにくいので、変換している間だけ panic hook を差し替えてメッセージだけを出力する
+//       panic hook はプロセス全体で共有されるので、終わったら元の hook に戻す
+fn catch_unwind_quietly<T>(f: impl FnOnce() -> T) -> thread::Result<T> {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let message = info
+            .payload()
+            .downcast_ref::<String>()
+            .map(|s| s.as_str())
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or("unknown error");
+        eprintln!("error: {}", message);
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(previous);
+
+    result
+}
+
+// NOTE: 入力のファイルを定期的に確認し、変更されたファイルとそれに依存するファイルだけを変換し直す
+//       設定ファイルの変更は反映しないので、その場合は再起動する
+pub fn watch(root_dir: &PathBuf, options: &CompilerOptions) {
+    let mut state = WatchState::default();
+    let options = CompilerOptions {
+        log_dir: None,
+        ..options.clone()
+    };
+    eprintln!("watch: watching {}", root_dir.display());
+
+    loop {
+        let scan = scan_files(root_dir);
+        if scan != state.last_scan {
+            let started_at = Instant::now();
+            // NOTE: 失敗しても watch は続ける (エラーの内容は panic hook が出力する)
+            //       失敗した場合は状態を更新しないので、次に変更されたときにもう一度変換する
+            let result = catch_unwind_quietly(|| {
+                state.run_cycle(
+                    &DiskFileSource,
+                    root_dir,
+                    &scan,
+                    &options,
+                    &mut DiskOutputWriter,
+                )
+            });
+            match result {
+                Ok(report) => print_report(root_dir, &report, started_at.elapsed()),
+                Err(_) => eprintln!("watch: build failed, waiting for changes"),
+            }
+            state.last_scan = scan;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::file_source::MemoryFileSource;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MemoryOutputWriter {
+        files: BTreeMap<PathBuf, String>,
+    }
+
+    impl OutputWriter for MemoryOutputWriter {
+        fn read_to_string(&self, path: &Path) -> Option<String> {
+            self.files.get(path).cloned()
+        }
+
+        fn write(&mut self, path: &Path, content: String) {
+            self.files.insert(path.to_path_buf(), content);
+        }
+
+        fn remove(&mut self, path: &Path) {
+            self.files.remove(path);
+        }
+    }
+
+    fn build_petstore_source() -> MemoryFileSource {
+        let mut source = MemoryFileSource::new();
+        source.insert(
+            "/petstore/openapi.yaml",
+            "openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    $ref: ./paths/pets.yaml
+  /users:
+    $ref: ./paths/users.yaml
+",
+        );
+        source.insert(
+            "/petstore/paths/pets.yaml",
+            "get:
+  responses:
+    '200':
+      description: ok
+      content:
+        application/json:
+          schema:
+            $ref: ../components/schemas/Pet.yaml
+",
+        );
+        source.insert(
+            "/petstore/paths/users.yaml",
+            "get:
+  responses:
+    '204':
+      description: no content
+",
+        );
+        source.insert(
+            "/petstore/components/schemas/Pet.yaml",
+            "type: object
+properties:
+  id:
+    type: integer
+",
+        );
+        source
+    }
+
+    fn build_scan(
+        source: &MemoryFileSource,
+        modified: &[(&str, u64)],
+    ) -> HashMap<PathBuf, SystemTime> {
+        source
+            .list_yaml_files(Path::new("/petstore"))
+            .into_iter()
+            .map(|path| {
+                let secs = modified
+                    .iter()
+                    .find(|(name, _)| path == Path::new(name))
+                    .map(|(_, secs)| *secs)
+                    .unwrap_or(0);
+                (path, SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            })
+            .collect()
+    }
+
+    fn tsp(path: &str) -> PathBuf {
+        PathBuf::from(path).with_extension("tsp")
+    }
+
+    #[test]
+    fn test_run_cycle_rebuilds_dependents() {
+        let root_dir = PathBuf::from("/petstore");
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+        let mut source = build_petstore_source();
+        let mut output = MemoryOutputWriter::default();
+        let mut state = WatchState::default();
+
+        let scan = build_scan(&source, &[]);
+        let report = state.run_cycle(&source, &root_dir, &scan, &options, &mut output);
+        assert!(report.full_rebuild);
+        assert!(report.written.contains(&tsp("/petstore/paths/users.yaml")));
+        let users = output.files[&tsp("/petstore/paths/users.yaml")].clone();
+
+        source.insert(
+            "/petstore/components/schemas/Pet.yaml",
+            "type: object
+properties:
+  id:
+    type: integer
+  name:
+    type: string
+",
+        );
+        let scan = build_scan(&source, &[("/petstore/components/schemas/Pet.yaml", 1)]);
+        let report = state.run_cycle(&source, &root_dir, &scan, &options, &mut output);
+
+        assert!(!report.full_rebuild);
+        assert_eq!(report.changed, 1);
+        assert!(report
+            .rebuilt
+            .contains(&tsp("/petstore/components/schemas/Pet.yaml")));
+        assert!(report.rebuilt.contains(&tsp("/petstore/paths/pets.yaml")));
+        assert!(!report.rebuilt.contains(&tsp("/petstore/paths/users.yaml")));
+        assert_eq!(
+            report.written,
+            vec![tsp("/petstore/components/schemas/Pet.yaml")]
+        );
+        assert!(
+            output.files[&tsp("/petstore/components/schemas/Pet.yaml")].contains("name?: string;")
+        );
+        assert_eq!(output.files[&tsp("/petstore/paths/users.yaml")], users);
+    }
+
+    #[test]
+    fn test_run_cycle_skips_unchanged_outputs() {
+        let root_dir = PathBuf::from("/petstore");
+        let options = CompilerOptions {
+            log_dir: None,
+            ..CompilerOptions::default()
+        };
+        let source = build_petstore_source();
+        let mut output = MemoryOutputWriter::default();
+        let mut state = WatchState::default();
+
+        let scan = build_scan(&source, &[]);
+        state.run_cycle(&source, &root_dir, &scan, &options, &mut output);
+        let written = output.files.clone();
+
+        // NOTE: 更新日時だけが変わった場合は作り直すが、出力は書き換えない
+        let scan = build_scan(&source, &[("/petstore/paths/users.yaml", 1)]);
+        let report = state.run_cycle(&source, &root_dir, &scan, &options, &mut output);
+
+        assert_eq!(report.changed, 1);
+        assert!(report.rebuilt.contains(&tsp("/petstore/paths/users.yaml")));
+        assert!(report.written.is_empty());
+        assert_eq!(output.files, written);
+    }
+
+    #[test]
+    fn test_run_cycle_validates_and_emits_project() {
+        let root_dir = PathBuf::from("/petstore");
+        let options = CompilerOptions {
+            log_dir: None,
+            emit_project: true,
+            ..CompilerOptions::default()
+        };
+        let mut source = build_petstore_source();
+        let mut output = MemoryOutputWriter::default();
+        let mut state = WatchState::default();
+
+        let scan = build_scan(&source, &[]);
+        let report = state.run_cycle(&source, &root_dir, &scan, &options, &mut output);
+        assert!(report
+            .written
+            .contains(&root_dir.join(crate::compiler::MAIN_FILE_NAME)));
+        assert!(output
+            .files
+            .contains_key(&root_dir.join(crate::compiler::PACKAGE_FILE_NAME)));
+
+        // NOTE: 作り直していないファイルの宣言と重複する場合も検出して、何も書き出さない
+        source.insert(
+            "/petstore/components/others/Pet.yaml",
+            "type: object
+properties:
+  name:
+    type: string
+",
+        );
+        let scan = build_scan(&source, &[("/petstore/components/others/Pet.yaml", 1)]);
+        let report = state.run_cycle(&source, &root_dir, &scan, &options, &mut output);
+
+        assert!(report.written.is_empty());
+        assert!(!output
+            .files
+            .contains_key(&tsp("/petstore/components/others/Pet.yaml")));
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+use yaml_rust::YamlLoader;
+
+use crate::compiler::is_config_file;
+use crate::yaml_loader::{collect_file_refs, list_yaml_files};
+
+// NOTE: 変換の入力を読み出す先。ディスク以外 (メモリ上のファイルや tar) からも変換できるようにする
+pub trait FileSource: Sync {
+    // NOTE: root 以下の yaml ファイルをパス順に返す。root がファイルの場合はそのファイルだけを返す
+    fn list_yaml_files(&self, root: &Path) -> Vec<PathBuf>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+}
+
+// NOTE: $ref の解決に使う。ファイルが存在しない場合もあるので canonicalize ではなく字句的に正規化する
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !result.pop() {
+                    result.push(component);
+                }
+            }
+            _ => result.push(component),
+        }
+    }
+
+    result
+}
+
+// NOTE: stdin から読んだ文書を置く仮想的なファイル名。ファイル名からモデル名を決めないようにするために使う
+pub const STDIN_FILE_NAME: &str = "<stdin>.yaml";
+
+pub fn is_stdin_path(path: &Path) -> bool {
+    path.file_name().and_then(|s| s.to_str()) == Some(STDIN_FILE_NAME)
+}
+
+fn is_yaml_file(path: &Path) -> bool {
+    path.to_str().map(|s| s.ends_with(".yaml")).unwrap_or(false) && !is_config_file(path)
+}
+
+#[derive(Debug, Default)]
+pub struct DiskFileSource;
+
+impl FileSource for DiskFileSource {
+    fn list_yaml_files(&self, root: &Path) -> Vec<PathBuf> {
+        list_yaml_files(&root.to_path_buf())
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MemoryFileSource {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemoryFileSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<P: AsRef<Path>, S: Into<String>>(&mut self, path: P, content: S) {
+        self.files
+            .insert(normalize_path(path.as_ref()), content.into());
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.keys()
+    }
+
+    // NOTE: stdin から読んだ文書を path に置き、そこから $ref で辿れるディスク上のファイルだけを読み込む
+    //       path にディスク上のファイルと同じ名前を指定すると、そのファイルへの $ref も stdin の文書を参照する
+    pub fn from_document(path: &Path, content: String) -> io::Result<Self> {
+        let mut source = Self::new();
+        let mut stack = vec![(normalize_path(path), content)];
+
+        while let Some((path, content)) = stack.pop() {
+            let yaml = YamlLoader::load_from_str(&content).map_err(|error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), error),
+                )
+            })?;
+            for ref_path in collect_file_refs(&path, &yaml) {
+                if !source.files.contains_key(&ref_path)
+                    && !stack.iter().any(|(path, _)| path == &ref_path)
+                {
+                    let content = fs::read_to_string(&ref_path).map_err(|error| {
+                        io::Error::new(error.kind(), format!("{}: {}", ref_path.display(), error))
+                    })?;
+                    stack.push((ref_path, content));
+                }
+            }
+            source.files.insert(path, content);
+        }
+
+        Ok(source)
+    }
+
+    // NOTE: 圧縮されていない tar (ustar / GNU) を読み込み、通常のファイルだけを root の下に置く
+    //       100 バイトを超えるパスは GNU の L エントリか pax の x エントリで次のエントリの名前として渡される
+    pub fn from_tar<R: Read>(mut reader: R, root: &Path) -> io::Result<Self> {
+        let mut source = Self::new();
+        let mut header = [0u8; 512];
+        let mut long_name = None;
+
+        loop {
+            if let Err(error) = reader.read_exact(&mut header) {
+                if error.kind() == io::ErrorKind::UnexpectedEof {
+                    break;
+                }
+                return Err(error);
+            }
+            if header.iter().all(|b| *b == 0) {
+                break;
+            }
+
+            // NOTE: ヘッダーのサイズは信用できないので、先に確保せず実際に読めた分だけを使う
+            let size = parse_tar_octal(&header[124..136])?;
+            let padded_size = size
+                .checked_add((512 - size % 512) % 512)
+                .ok_or_else(|| invalid_tar_data("tar entry size is too large"))?;
+            let mut content = vec![];
+            (&mut reader)
+                .take(padded_size as u64)
+                .read_to_end(&mut content)?;
+            if content.len() != padded_size {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "tar entry is truncated",
+                ));
+            }
+            content.truncate(size);
+
+            match header[156] {
+                b'L' => {
+                    long_name = Some(read_tar_string(&content));
+                    continue;
+                }
+                b'x' => {
+                    if let Some(path) = parse_pax_path(&content)? {
+                        long_name = Some(path);
+                    }
+                    continue;
+                }
+                b'0' | 0 => {}
+                _ => {
+                    long_name = None;
+                    continue;
+                }
+            }
+            let name = match long_name.take() {
+                Some(name) => name,
+                None => read_tar_name(&header),
+            };
+            let content = String::from_utf8(content)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+            source.insert(root.join(name), content);
+        }
+
+        Ok(source)
+    }
+}
+
+fn invalid_tar_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn read_tar_name(header: &[u8; 512]) -> String {
+    let name = read_tar_string(&header[0..100]);
+    if &header[257..262] != b"ustar" {
+        return name;
+    }
+
+    let prefix = read_tar_string(&header[345..500]);
+    if prefix.is_empty() {
+        name
+    } else {
+        format!("{}/{}", prefix, name)
+    }
+}
+
+// NOTE: pax の拡張ヘッダーは `<長さ> <キー>=<値>\n` の並びで、長さはレコード全体のバイト数
+fn parse_pax_path(content: &[u8]) -> io::Result<Option<String>> {
+    let mut path = None;
+    let mut rest = content;
+
+    while !rest.is_empty() {
+        let space = rest
+            .iter()
+            .position(|b| *b == b' ')
+            .ok_or_else(|| invalid_tar_data("invalid pax record"))?;
+        let length = std::str::from_utf8(&rest[..space])
+            .ok()
+            .and_then(|length| length.parse::<usize>().ok())
+            .filter(|length| *length > space + 1 && *length <= rest.len())
+            .ok_or_else(|| invalid_tar_data("invalid pax record length"))?;
+        let record = &rest[space + 1..length - 1];
+        if let Some(value) = record.strip_prefix(b"path=") {
+            path = Some(String::from_utf8_lossy(value).to_string());
+        }
+        rest = &rest[length..];
+    }
+
+    Ok(path)
+}
+
+fn read_tar_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
+
+fn parse_tar_octal(bytes: &[u8]) -> io::Result<usize> {
+    let s = read_tar_string(bytes);
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(0);
+    }
+
+    usize::from_str_radix(s, 8).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+impl FileSource for MemoryFileSource {
+    fn list_yaml_files(&self, root: &Path) -> Vec<PathBuf> {
+        let root = normalize_path(root);
+        if self.files.contains_key(&root) {
+            return vec![root];
+        }
+
+        // NOTE: BTreeMap の PathBuf はコンポーネント単位で比較されるので、ディスクを辿る場合と同じ順序になる
+        self.files
+            .keys()
+            .filter(|path| path.starts_with(&root) && is_yaml_file(path))
+            .cloned()
+            .collect()
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .get(&normalize_path(path))
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.display().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_tar_entry(name: &str, content: &str) -> Vec<u8> {
+        build_typed_tar_entry(name, b'0', content)
+    }
+
+    fn build_typed_tar_entry(name: &str, entry_type: u8, content: &str) -> Vec<u8> {
+        let mut header = [0u8; 512];
+        let name = &name.as_bytes()[..name.len().min(100)];
+        header[..name.len()].copy_from_slice(name);
+        let size = format!("{:011o}\0", content.len());
+        header[124..136].copy_from_slice(size.as_bytes());
+        header[156] = entry_type;
+        header[257..263].copy_from_slice(b"ustar\0");
+
+        let mut entry = header.to_vec();
+        entry.extend(content.as_bytes());
+        entry.resize(512 + content.len().div_ceil(512) * 512, 0);
+        entry
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("/spec/paths/../components/./schemas/Pet.yaml")),
+            PathBuf::from("/spec/components/schemas/Pet.yaml")
+        );
+        assert_eq!(
+            normalize_path(Path::new("../spec/Pet.yaml")),
+            PathBuf::from("../spec/Pet.yaml")
+        );
+    }
+
+    #[test]
+    fn test_memory_file_source_from_tar() {
+        let mut tar = build_tar_entry("openapi.yaml", "openapi: 3.0.3\n");
+        tar.extend(build_tar_entry(
+            "components/schemas/Pet.yaml",
+            "type: object\n",
+        ));
+        tar.extend(build_tar_entry("openapi2tsp.yaml", "strict: true\n"));
+        tar.extend(build_tar_entry("README.md", "# spec\n"));
+        tar.extend([0u8; 1024]);
+
+        let source = MemoryFileSource::from_tar(tar.as_slice(), Path::new("/spec")).unwrap();
+
+        assert_eq!(
+            source.list_yaml_files(Path::new("/spec")),
+            vec![
+                PathBuf::from("/spec/components/schemas/Pet.yaml"),
+                PathBuf::from("/spec/openapi.yaml"),
+            ]
+        );
+        assert_eq!(
+            source
+                .read_to_string(Path::new("/spec/paths/../openapi.yaml"))
+                .unwrap(),
+            "openapi: 3.0.3\n"
+        );
+    }
+
+    #[test]
+    fn test_memory_file_source_from_tar_with_long_names() {
+        let gnu_name = format!("components/schemas/{}/Pet.yaml", "a".repeat(100));
+        let pax_name = format!("components/schemas/{}/User.yaml", "b".repeat(100));
+        let pax_record = format!("path={}\n", pax_name);
+        // NOTE: レコードの長さは長さ自身の桁数も含む
+        let pax_record = format!("{} {}", pax_record.len() + 4, pax_record);
+        let mut tar = build_typed_tar_entry("././@LongLink", b'L', &format!("{}\0", gnu_name));
+        tar.extend(build_tar_entry(&gnu_name, "type: object\n"));
+        tar.extend(build_typed_tar_entry(
+            "PaxHeaders/User.yaml",
+            b'x',
+            &pax_record,
+        ));
+        tar.extend(build_tar_entry(&pax_name, "type: string\n"));
+        tar.extend([0u8; 1024]);
+
+        let source = MemoryFileSource::from_tar(tar.as_slice(), Path::new("/spec")).unwrap();
+
+        assert_eq!(
+            source.list_yaml_files(Path::new("/spec")),
+            vec![
+                Path::new("/spec").join(&gnu_name),
+                Path::new("/spec").join(&pax_name),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_memory_file_source_from_truncated_tar() {
+        let mut tar = build_tar_entry("openapi.yaml", "openapi: 3.0.3\n");
+        tar[124..136].copy_from_slice(b"77777777777\0");
+
+        let error = MemoryFileSource::from_tar(tar.as_slice(), Path::new("/spec")).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod compiler;
+pub mod file_source;
+pub mod logger;
+pub mod openapi_parser;
+pub mod type_spec;
+pub mod yaml_loader;
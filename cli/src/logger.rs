@@ -0,0 +1,154 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// NOTE: 変換しきれなかった箇所の警告を記録する (strict モードの判定にも使う)
+//       変換ごとに DiagnosticCollector を作り、CompileOutput の diagnostics として返す
+//       記録したものは log にも流すので、ライブラリとして使う場合はホストの logger にも届く
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticCollector {
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<DiagnosticCollector>> = const { RefCell::new(None) };
+}
+
+impl DiagnosticCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // NOTE: f の中で記録された diagnostics をこの collector に集める。入れ子になった場合は内側が優先される
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let _guard = RestoreGuard(previous);
+        f()
+    }
+
+    pub fn take(&self) -> Vec<Diagnostic> {
+        std::mem::take(&mut *self.diagnostics.lock().unwrap())
+    }
+
+    fn push(&self, diagnostic: Diagnostic) {
+        self.diagnostics.lock().unwrap().push(diagnostic);
+    }
+}
+
+// NOTE: f が panic しても元の collector に戻す
+struct RestoreGuard(Option<DiagnosticCollector>);
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+// NOTE: rayon の worker thread には呼び出し元の collector が引き継がれないので、並列処理に渡す closure を包む
+pub fn propagate<T, R>(f: impl Fn(T) -> R + Sync + Send) -> impl Fn(T) -> R + Sync + Send {
+    let collector = CURRENT.with(|current| current.borrow().clone());
+    move |value| match &collector {
+        Some(collector) => collector.scope(|| f(value)),
+        None => f(value),
+    }
+}
+
+pub fn record(level: Level, args: fmt::Arguments) {
+    let message = args.to_string();
+    log::log!(level, "{}", message);
+    CURRENT.with(|current| {
+        if let Some(collector) = current.borrow().as_ref() {
+            collector.push(Diagnostic { level, message });
+        }
+    });
+}
+
+macro_rules! warning {
+    ($($arg:tt)+) => {
+        $crate::logger::record(log::Level::Warn, format_args!($($arg)+))
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::logger::record(log::Level::Error, format_args!($($arg)+))
+    };
+}
+
+pub(crate) use {error, warning};
+
+// NOTE: コマンドラインから使う場合は stderr に出力する
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "{}: {}",
+                record.level().as_str().to_lowercase(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn init() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Warn);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rayon::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_collectors_are_separated() {
+        let first = DiagnosticCollector::new();
+        let second = DiagnosticCollector::new();
+
+        first.scope(|| {
+            warning!("first");
+            second.scope(|| {
+                (0..4).into_par_iter().for_each(propagate(|i| {
+                    error!("second {}", i);
+                }))
+            });
+            warning!("first again");
+        });
+        warning!("outside");
+
+        assert_eq!(
+            first
+                .take()
+                .into_iter()
+                .map(|diagnostic| diagnostic.message)
+                .collect::<Vec<_>>(),
+            vec!["first", "first again"]
+        );
+        let second = second.take();
+        assert_eq!(second.len(), 4);
+        assert!(second
+            .iter()
+            .all(|diagnostic| diagnostic.level == Level::Error));
+    }
+}
@@ -1,15 +1,187 @@
-mod compiler;
-mod openapi_parser;
-mod type_spec;
-mod yaml_loader;
-
 use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+
+use cli::compiler::{self, CompilerOptions, ExtensionHandling, NamedResponses, ReportFormat};
+use cli::{file_source, logger};
+
+#[derive(Debug, Parser)]
+struct Args {
+    /// Directory containing the split OpenAPI files, or `-` to read a single document from stdin
+    root_dir: PathBuf,
+
+    /// Directory that file refs are resolved against when reading from stdin [default: current dir]
+    #[arg(long)]
+    base_dir: Option<PathBuf>,
+
+    /// File name of the document read from stdin, used for file refs back to it and for its model name
+    #[arg(long)]
+    stdin_filename: Option<String>,
+
+    /// Config file to use instead of the openapi2tsp.yaml next to the root spec
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Namespace of the service (defaults to `{Dirname}Service`)
+    #[arg(long)]
+    namespace: Option<String>,
+
+    /// Hoist inline object schemas into named top-level models
+    #[arg(long)]
+    hoist_inline_models: bool,
+
+    /// Put the declarations of each subdirectory into a nested namespace (e.g. `PetService.Models.Billing`)
+    #[arg(long)]
+    nested_namespaces: bool,
+
+    /// Rename duplicate declarations with a numeric suffix instead of failing
+    #[arg(long)]
+    disambiguate_names: bool,
+
+    /// Also emit main.tsp, tspconfig.yaml and package.json so the output compiles as a TypeSpec project
+    #[arg(long)]
+    emit_project: bool,
+
+    /// Emit enums as open unions with a string or int32 fallback variant
+    #[arg(long)]
+    open_enums: bool,
+
+    /// Emit responses as named models instead of inline records
+    #[arg(long, value_enum)]
+    named_responses: Option<NamedResponses>,
+
+    /// How to handle OpenAPI data that has no TypeSpec counterpart
+    #[arg(long, value_enum)]
+    extensions: Option<ExtensionHandling>,
+
+    /// Number of spaces per indentation level in the emitted TypeSpec [default: 2]
+    #[arg(long)]
+    indent_width: Option<usize>,
+
+    /// Line width at which unions, decorator arguments and parameter lists are wrapped [default: 80]
+    #[arg(long)]
+    max_line_width: Option<usize>,
+
+    /// Do not end the emitted files with a newline
+    #[arg(long)]
+    no_trailing_newline: bool,
+
+    /// Directory to write the debug logs to
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Print the OpenAPI data that is not represented in the output (to stderr when reading from stdin)
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "human")]
+    report: Option<ReportFormat>,
+
+    /// Fail without writing any files if a part of the spec could not be converted
+    #[arg(long)]
+    strict: bool,
+
+    /// Watch the input tree and recompile the changed files and the files that depend on them
+    #[arg(long, conflicts_with = "check")]
+    watch: bool,
+
+    /// Verify that the TypeSpec files on disk are up to date without writing them
+    #[arg(long)]
+    check: bool,
+}
+
+// NOTE: 既定値 < 設定ファイル < コマンドライン引数 の順に上書きする
+fn build_compiler_options(args: &Args, root_dir: &Path) -> CompilerOptions {
+    let mut options = CompilerOptions::default();
+
+    let config_file = args
+        .config
+        .clone()
+        .or_else(|| compiler::find_config_file(root_dir));
+    if let Some(config_file) = config_file {
+        if let Err(error) = compiler::apply_config_file(&config_file, &mut options) {
+            eprintln!("error: {}", error);
+            std::process::exit(2);
+        }
+    }
+
+    if let Some(namespace) = &args.namespace {
+        options.namespace = Some(namespace.clone());
+    }
+    if args.hoist_inline_models {
+        options.hoist_inline_models = true;
+    }
+    if args.nested_namespaces {
+        options.nested_namespaces = true;
+    }
+    if args.disambiguate_names {
+        options.disambiguate_names = true;
+    }
+    if args.emit_project {
+        options.emit_project = true;
+    }
+    if args.open_enums {
+        options.open_enums = true;
+    }
+    if args.named_responses.is_some() {
+        options.named_responses = args.named_responses;
+    }
+    if let Some(extensions) = args.extensions {
+        options.extensions = extensions;
+    }
+    if let Some(indent_width) = args.indent_width {
+        options.print_options.indent_width = indent_width;
+    }
+    if let Some(max_line_width) = args.max_line_width {
+        options.print_options.max_line_width = max_line_width;
+    }
+    if args.no_trailing_newline {
+        options.print_options.trailing_newline = false;
+    }
+    if args.log_dir.is_some() {
+        options.log_dir = args.log_dir.clone();
+    }
+    if args.strict {
+        options.strict = true;
+    }
+    if let Some(format) = args.report {
+        options.report = true;
+        options.report_format = format;
+    }
+
+    options
+}
 
 fn main() {
     unsafe { backtrace_on_stack_overflow::enable() };
-    let args = std::env::args().collect::<Vec<_>>();
-    let root_dir = fs::canonicalize(args[1].clone()).unwrap();
-    dbg!(&root_dir);
+    logger::init();
+    let args = Args::parse();
+    if args.root_dir == Path::new("-") {
+        if args.watch || args.check {
+            eprintln!("error: --watch and --check cannot be used when reading from stdin");
+            std::process::exit(2);
+        }
+        let base_dir =
+            fs::canonicalize(args.base_dir.clone().unwrap_or_else(|| PathBuf::from(".")))
+                .expect("failed to find base dir");
+        let options = build_compiler_options(&args, &base_dir);
+        let document_path = base_dir.join(
+            args.stdin_filename
+                .as_deref()
+                .unwrap_or(file_source::STDIN_FILE_NAME),
+        );
+        compiler::compile_stdin(&document_path, &options);
+        return;
+    }
+
+    let root_dir = fs::canonicalize(&args.root_dir).unwrap();
 
-    compiler::compile(&root_dir);
+    let options = build_compiler_options(&args, &root_dir);
+    if args.watch {
+        compiler::watch(&root_dir, &options);
+    } else if args.check {
+        if !compiler::check(&root_dir, &options) {
+            std::process::exit(1);
+        }
+    } else {
+        compiler::compile(&root_dir, &options);
+    }
 }
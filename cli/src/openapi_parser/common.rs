@@ -2,18 +2,71 @@ use std::str::FromStr;
 
 use yaml_rust::{yaml, Yaml};
 
+use crate::logger;
+
+// NOTE: 想定していないキーは無視する。変換レポートでは失われたキーとして報告される
 pub fn check_unexpected_keys(keys: Vec<&str>, hash: &yaml::Hash) {
     for (k, _) in hash.iter() {
-        if !keys.contains(&k.as_str().unwrap()) {
-            dbg!(hash);
-            panic!("unexpected key: {}", k.as_str().unwrap());
+        match k.as_str() {
+            Some(k) if keys.contains(&k) => {}
+            Some(k) => logger::warning!("unexpected key `{}` is ignored", k),
+            None => logger::warning!("unexpected non-string key is ignored"),
         }
     }
 }
 
+pub fn get_string_array(hash: &yaml::Hash, key: &str) -> Option<Vec<String>> {
+    hash.get(&Yaml::String(key.to_string()))
+        .and_then(|v| v.as_vec())
+        .map(|array| {
+            array
+                .iter()
+                .map(|item| match item {
+                    Yaml::String(s) | Yaml::Real(s) => s.clone(),
+                    Yaml::Integer(i) => i.to_string(),
+                    _ => panic!("unexpected array item: {:?}", item),
+                })
+                .collect()
+        })
+}
+
 pub fn get_value<T: FromStr>(hash: &yaml::Hash, key: &str) -> Option<T> {
     hash.get(&Yaml::String(key.to_string()))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+        .and_then(|v| match v {
+            Yaml::String(s) | Yaml::Real(s) => Some(s.clone()),
+            Yaml::Integer(i) => Some(i.to_string()),
+            Yaml::Boolean(b) => Some(b.to_string()),
+            _ => None,
+        })
         .and_then(|s| s.parse().ok())
 }
+
+// NOTE: components 配下のセクションを取り出し、空になった components は取り除く
+pub fn take_components_section(hash: &mut yaml::Hash, section: &str) -> Option<yaml::Hash> {
+    let components_key = Yaml::String("components".to_string());
+    let components = match hash.get_mut(&components_key) {
+        Some(Yaml::Hash(components)) => components,
+        _ => return None,
+    };
+    let section_key = Yaml::String(section.to_string());
+    if !matches!(components.get(&section_key), Some(Yaml::Hash(_))) {
+        return None;
+    }
+    let value = components.remove(&section_key).and_then(|v| v.into_hash());
+
+    if components.is_empty() {
+        hash.remove(&components_key);
+    }
+
+    value
+}
+
+// NOTE: take_components_section の逆で、取り出したエントリを components 配下に戻した Hash を作る
+pub fn build_components_section(section: &str, entries: yaml::Hash) -> yaml::Hash {
+    let mut sections = yaml::Hash::new();
+    sections.insert(Yaml::String(section.to_string()), Yaml::Hash(entries));
+    let mut hash = yaml::Hash::new();
+    hash.insert(Yaml::String("components".to_string()), Yaml::Hash(sections));
+
+    hash
+}
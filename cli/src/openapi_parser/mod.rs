@@ -1,6 +1,7 @@
 mod common;
 pub mod node;
 mod parser;
+mod swagger2;
 
 pub use node::data_model_node::*;
 pub use node::*;
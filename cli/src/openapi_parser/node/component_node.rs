@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use yaml_rust::{yaml, Yaml};
+
+use crate::file_source::normalize_path;
+use crate::logger;
+use crate::openapi_parser::common::{build_components_section, take_components_section};
+use crate::openapi_parser::node::*;
+use crate::yaml_loader::YamlFile;
+
+#[derive(Debug, Clone)]
+pub enum ReferenceOr<T> {
+    Item(T),
+    Ref(ComponentRefNode),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentKind {
+    Parameter,
+    Response,
+    Header,
+    RequestBody,
+}
+
+impl ComponentKind {
+    fn from_section(section: &str) -> Option<Self> {
+        match section {
+            "parameters" => Some(ComponentKind::Parameter),
+            "responses" => Some(ComponentKind::Response),
+            "headers" => Some(ComponentKind::Header),
+            "requestBodies" => Some(ComponentKind::RequestBody),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ComponentContentNode {
+    Parameter(ParameterNode),
+    Response(ResponseObjectNode),
+    Header(HeaderNode),
+    RequestBody(RequestBodyNode),
+    Ref(ComponentRefNode),
+}
+
+#[derive(Debug, Clone)]
+pub struct ComponentNode {
+    pub kind: ComponentKind,
+    pub name: String,
+    pub content: ComponentContentNode,
+}
+
+const COMPONENT_SECTIONS: [&str; 4] = ["parameters", "responses", "headers", "requestBodies"];
+
+// NOTE: schema の中は component を参照する場所ではないので、プロパティ名が parameters などでも辿らない
+const SCHEMA_KEYS: [&str; 12] = [
+    "schema",
+    "schemas",
+    "definitions",
+    "properties",
+    "items",
+    "allOf",
+    "oneOf",
+    "anyOf",
+    "not",
+    "additionalProperties",
+    "example",
+    "examples",
+];
+
+pub type ComponentFileKinds = HashMap<PathBuf, ComponentKind>;
+
+pub fn build_component_ref_node(hash: &yaml::Hash) -> Option<ComponentRefNode> {
+    let ref_path = hash
+        .get(&Yaml::String("$ref".to_string()))
+        .and_then(|v| v.as_str())?;
+    // NOTE: JSON pointer は末尾の名前、ファイル参照はファイル名を component 名とみなす
+    let component_name = match ref_path.split_once('#') {
+        Some((_, pointer)) => pointer.rsplit('/').next().unwrap_or(pointer),
+        None => Path::new(ref_path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .expect("invalid ref path"),
+    };
+
+    Some(ComponentRefNode {
+        component_name: component_name.to_string(),
+        namespace: None,
+    })
+}
+
+fn build_component_content_node(
+    kind: &ComponentKind,
+    hash: &yaml::Hash,
+) -> Option<ComponentContentNode> {
+    if let Some(ref_node) = build_component_ref_node(hash) {
+        return Some(ComponentContentNode::Ref(ref_node));
+    }
+
+    match kind {
+        ComponentKind::Parameter => build_parameter_node(hash).map(ComponentContentNode::Parameter),
+        ComponentKind::Response => Some(ComponentContentNode::Response(
+            build_response_object_node(hash),
+        )),
+        ComponentKind::Header => build_header_node(hash).map(ComponentContentNode::Header),
+        ComponentKind::RequestBody => {
+            build_request_body_node(hash).map(ComponentContentNode::RequestBody)
+        }
+    }
+}
+
+pub fn parse_components_content(hash: &mut yaml::Hash, path: &Path) -> Option<Vec<OpenAPINode>> {
+    let mut nodes = vec![];
+
+    for section in COMPONENT_SECTIONS {
+        let kind = ComponentKind::from_section(section).unwrap();
+        let components = match take_components_section(hash, section) {
+            Some(components) => components,
+            None => continue,
+        };
+
+        let mut invalid_components = yaml::Hash::new();
+        for (key, value) in components.into_iter() {
+            let content = match (&key, &value) {
+                (Yaml::String(key), Yaml::Hash(value)) => {
+                    build_component_content_node(&kind, value).map(|content| (key.clone(), content))
+                }
+                _ => None,
+            };
+            if let Some((name, content)) = content {
+                nodes.push(OpenAPINode::Component(ComponentNode {
+                    kind: kind.clone(),
+                    name,
+                    content,
+                }));
+            } else {
+                logger::warning!(
+                    "invalid component `components.{}.{}` in {} is ignored",
+                    section,
+                    key.as_str().unwrap_or_default(),
+                    path.display()
+                );
+                invalid_components.insert(key, value);
+            }
+        }
+        // NOTE: 解釈できなかった component は Unknown として残し、変換レポートに出す
+        if !invalid_components.is_empty() {
+            nodes.push(OpenAPINode::Unknown(Box::new(build_components_section(
+                section,
+                invalid_components,
+            ))));
+        }
+    }
+
+    if nodes.is_empty() {
+        None
+    } else {
+        Some(nodes)
+    }
+}
+
+fn get_file_ref_path(yaml: &Yaml, dir: &Path) -> Option<PathBuf> {
+    let ref_path = yaml
+        .as_hash()
+        .and_then(|hash| hash.get(&Yaml::String("$ref".to_string())))
+        .and_then(|v| v.as_str())?;
+    // NOTE: JSON pointer で中の component を指す参照は、ファイル全体が component になるわけではない
+    match ref_path.split_once('#') {
+        Some((path, pointer)) if !path.is_empty() && pointer.is_empty() => {
+            Some(normalize_path(&dir.join(path)))
+        }
+        None => Some(normalize_path(&dir.join(ref_path))),
+        _ => None,
+    }
+}
+
+fn collect_ref_kinds(yaml: &Yaml, dir: &Path, kinds: &mut ComponentFileKinds) {
+    let hash = match yaml {
+        Yaml::Hash(hash) => hash,
+        Yaml::Array(array) => {
+            array
+                .iter()
+                .for_each(|item| collect_ref_kinds(item, dir, kinds));
+            return;
+        }
+        _ => return,
+    };
+
+    for (key, value) in hash.iter() {
+        let key = match key.as_str() {
+            Some(key) if !SCHEMA_KEYS.contains(&key) => key,
+            _ => continue,
+        };
+        let kind = match key {
+            "parameters" => Some(ComponentKind::Parameter),
+            "responses" => Some(ComponentKind::Response),
+            "headers" => Some(ComponentKind::Header),
+            "requestBodies" | "requestBody" => Some(ComponentKind::RequestBody),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            // NOTE: requestBody は値そのもの、それ以外は配列か名前をキーにした Hash の要素が参照になる
+            let slots = match value {
+                _ if key == "requestBody" => vec![value],
+                Yaml::Hash(hash) => hash.values().collect(),
+                Yaml::Array(array) => array.iter().collect(),
+                _ => vec![],
+            };
+            slots
+                .into_iter()
+                .filter_map(|slot| get_file_ref_path(slot, dir))
+                .for_each(|path| {
+                    kinds.entry(path).or_insert(kind.clone());
+                });
+        }
+        collect_ref_kinds(value, dir, kinds);
+    }
+}
+
+// NOTE: 分割されたファイルの component の種類は、そのファイルを参照している場所 (components の
+//       セクションや operation の parameters, requestBody, responses, headers) で決める
+//       形だけで判断すると schema のファイルを取り違えるので、参照されていないファイルは component にしない
+pub fn collect_component_file_kinds(files: &[YamlFile]) -> ComponentFileKinds {
+    let mut kinds = ComponentFileKinds::new();
+    for file in files.iter() {
+        let dir = file.path.parent().unwrap_or(Path::new(""));
+        file.content
+            .iter()
+            .for_each(|content| collect_ref_kinds(content, dir, &mut kinds));
+    }
+
+    kinds
+}
+
+pub fn parse_component_content(
+    hash: &mut yaml::Hash,
+    path: &Path,
+    kind: &ComponentKind,
+) -> Option<Vec<OpenAPINode>> {
+    let content = match build_component_content_node(kind, hash) {
+        Some(content) => content,
+        None => {
+            logger::warning!(
+                "{} is referenced as a {:?} but is not a valid one",
+                path.display(),
+                kind
+            );
+            return None;
+        }
+    };
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .expect("invalid file name")
+        .to_string();
+
+    hash.clear();
+    Some(vec![OpenAPINode::Component(ComponentNode {
+        kind: kind.clone(),
+        name,
+        content,
+    })])
+}
+
+#[cfg(test)]
+mod test {
+    use yaml_rust::YamlLoader;
+
+    use super::*;
+
+    fn load_hash(yaml: &str) -> yaml::Hash {
+        YamlLoader::load_from_str(yaml).unwrap()[0]
+            .as_hash()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_build_component_ref_node() {
+        let name =
+            |yaml: &str| build_component_ref_node(&load_hash(yaml)).map(|r| r.component_name);
+
+        assert_eq!(
+            name("$ref: '#/components/parameters/limit'"),
+            Some("limit".to_string())
+        );
+        assert_eq!(
+            name("$ref: ../parameters/limit.yaml"),
+            Some("limit".to_string())
+        );
+        assert_eq!(
+            name("$ref: ./common.yaml#/components/responses/NotFound"),
+            Some("NotFound".to_string())
+        );
+        assert_eq!(name("name: limit"), None);
+    }
+
+    #[test]
+    fn test_collect_component_file_kinds() {
+        let file = |path: &str, yaml: &str| YamlFile {
+            path: PathBuf::from(path),
+            content: Box::new(YamlLoader::load_from_str(yaml).unwrap()),
+        };
+        let files = vec![
+            file(
+                "/api/openapi.yaml",
+                "components:
+  parameters:
+    limit:
+      $ref: ./parameters/limit.yaml
+  responses:
+    NotFound:
+      $ref: ./responses/NotFound.yaml
+  schemas:
+    Pet:
+      $ref: ./schemas/Pet.yaml
+",
+            ),
+            file(
+                "/api/paths/pets.yaml",
+                "parameters:
+  - $ref: ../parameters/petId.yaml
+post:
+  requestBody:
+    $ref: ../requestBodies/Pet.yaml
+  responses:
+    '200':
+      $ref: ../responses/Pet.yaml
+    '404':
+      $ref: ../openapi.yaml#/components/responses/NotFound
+",
+            ),
+            file(
+                "/api/responses/Pet.yaml",
+                "description: pet
+headers:
+  X-Request-Id:
+    $ref: ../headers/RequestId.yaml
+content:
+  application/json:
+    schema:
+      type: object
+      properties:
+        headers:
+          $ref: ../schemas/Headers.yaml
+",
+            ),
+        ];
+
+        let kinds = collect_component_file_kinds(&files);
+
+        let kind = |path: &str| kinds.get(Path::new(path)).cloned();
+        assert_eq!(
+            kind("/api/parameters/limit.yaml"),
+            Some(ComponentKind::Parameter)
+        );
+        assert_eq!(
+            kind("/api/parameters/petId.yaml"),
+            Some(ComponentKind::Parameter)
+        );
+        assert_eq!(
+            kind("/api/responses/NotFound.yaml"),
+            Some(ComponentKind::Response)
+        );
+        assert_eq!(
+            kind("/api/responses/Pet.yaml"),
+            Some(ComponentKind::Response)
+        );
+        assert_eq!(
+            kind("/api/requestBodies/Pet.yaml"),
+            Some(ComponentKind::RequestBody)
+        );
+        assert_eq!(
+            kind("/api/headers/RequestId.yaml"),
+            Some(ComponentKind::Header)
+        );
+        assert_eq!(kinds.len(), 6);
+    }
+}
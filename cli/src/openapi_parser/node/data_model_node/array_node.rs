@@ -1,11 +1,14 @@
 use yaml_rust::yaml;
 
+use crate::logger;
+use crate::openapi_parser::common::get_value;
 use crate::openapi_parser::node::data_model_node::{build_data_model_node, DataModelNode};
 
 #[derive(Debug, Clone)]
 pub struct ArrayNode {
     pub title: Option<String>,
     pub items: Box<DataModelNode>,
+    pub nullable: Option<bool>,
 }
 
 pub fn build_array_node(hash: &yaml::Hash, title: &Option<String>) -> Option<DataModelNode> {
@@ -17,10 +20,11 @@ pub fn build_array_node(hash: &yaml::Hash, title: &Option<String>) -> Option<Dat
             Some(DataModelNode::Array(ArrayNode {
                 title: title.clone(),
                 items: Box::new(node),
+                nullable: get_value(hash, "nullable"),
             }))
         } else {
-            dbg!(items);
-            panic!("unexpected items: {:?}", items);
+            logger::warning!("array has invalid items, so it is ignored");
+            None
         }
     } else {
         None
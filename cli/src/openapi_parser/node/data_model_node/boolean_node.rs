@@ -9,13 +9,12 @@ pub struct BooleanNode {
     default: Option<bool>,
     #[allow(dead_code)]
     description: Option<String>,
-    #[allow(dead_code)]
-    nullable: Option<bool>,
+    pub nullable: Option<bool>,
     #[allow(dead_code)]
     example: Option<bool>,
 }
 
-const EXPECTED_KEYS: [&'static str; 5] = ["type", "description", "default", "nullable", "example"];
+const EXPECTED_KEYS: [&str; 5] = ["type", "description", "default", "nullable", "example"];
 
 pub fn build_boolean_node(hash: &yaml::Hash) -> Option<DataModelNode> {
     if Some("boolean".to_string()) != get_value(hash, "type") {
@@ -0,0 +1,77 @@
+use yaml_rust::{yaml, Yaml};
+
+use crate::logger;
+use crate::openapi_parser::common::get_value;
+use crate::openapi_parser::node::data_model_node::DataModelNode;
+
+#[derive(Debug, Clone)]
+pub struct ConstNode {
+    pub title: Option<String>,
+    pub value: ConstValue,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ConstValue {
+    String(String),
+    Integer(i64),
+    Number(f64),
+    Boolean(bool),
+    Null,
+    Array(Vec<ConstValue>),
+    Object(Vec<(String, ConstValue)>),
+}
+
+fn build_const_key(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(s) | Yaml::Real(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// NOTE: 配列やオブジェクトの const は tuple や model の literal として出力する
+fn build_const_value(yaml: &Yaml) -> Option<ConstValue> {
+    match yaml {
+        Yaml::String(s) => Some(ConstValue::String(s.clone())),
+        Yaml::Integer(i) => Some(ConstValue::Integer(*i)),
+        Yaml::Real(_) => yaml.as_f64().map(ConstValue::Number),
+        Yaml::Boolean(b) => Some(ConstValue::Boolean(*b)),
+        Yaml::Null => Some(ConstValue::Null),
+        Yaml::Array(items) => items
+            .iter()
+            .map(build_const_value)
+            .collect::<Option<Vec<_>>>()
+            .map(ConstValue::Array),
+        Yaml::Hash(hash) => hash
+            .iter()
+            .map(|(key, value)| Some((build_const_key(key)?, build_const_value(value)?)))
+            .collect::<Option<Vec<_>>>()
+            .map(ConstValue::Object),
+        _ => None,
+    }
+}
+
+pub fn build_const_node(hash: &yaml::Hash, title: &Option<String>) -> Option<DataModelNode> {
+    let value = if let Some(value) = hash.get(&Yaml::String("const".to_string())) {
+        match build_const_value(value) {
+            Some(value) => value,
+            None => {
+                // NOTE: literal にできない値は const を無視して、残りのキーから型を決める
+                logger::warning!("unsupported const value is ignored: {:?}", value);
+                return None;
+            }
+        }
+    } else if Some("null".to_string()) == get_value(hash, "type") {
+        ConstValue::Null
+    } else {
+        return None;
+    };
+
+    Some(DataModelNode::Const(ConstNode {
+        title: title.clone().or(get_value(hash, "title")),
+        value,
+        description: get_value(hash, "description"),
+    }))
+}
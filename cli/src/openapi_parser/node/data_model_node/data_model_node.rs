@@ -1,7 +1,10 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::{yaml, Yaml};
 
+use crate::file_source::is_stdin_path;
+use crate::logger;
+use crate::openapi_parser::common::{build_components_section, take_components_section};
 use crate::openapi_parser::node::*;
 
 #[derive(Debug, Clone)]
@@ -15,6 +18,8 @@ pub enum DataModelNode {
     Boolean(BooleanNode),
     AllOf(AllOfNode),
     Ref(RefNode),
+    Const(ConstNode),
+    Tuple(TupleNode),
 }
 
 impl DataModelNode {
@@ -24,21 +29,63 @@ impl DataModelNode {
             DataModelNode::Object(node) => node.title.clone(),
             DataModelNode::OneOf(node) => node.title.clone(),
             DataModelNode::String(node) => node.title.clone(),
-            DataModelNode::Integer(node) => None,
-            DataModelNode::Number(node) => None,
-            DataModelNode::Boolean(node) => None,
+            DataModelNode::Integer(node) => node.title.clone(),
+            DataModelNode::Number(_) => None,
+            DataModelNode::Boolean(_) => None,
             DataModelNode::AllOf(node) => node.title.clone(),
-            DataModelNode::Ref(node) => None,
+            DataModelNode::Ref(_) => None,
+            DataModelNode::Const(node) => node.title.clone(),
+            DataModelNode::Tuple(node) => node.title.clone(),
         }
     }
+
+    // NOTE: title を持たない型 (number, boolean, $ref) の場合は何もしない
+    pub fn set_title(&mut self, title: String) {
+        match self {
+            DataModelNode::Array(node) => node.title = Some(title),
+            DataModelNode::Object(node) => node.title = Some(title),
+            DataModelNode::OneOf(node) => node.title = Some(title),
+            DataModelNode::String(node) => node.title = Some(title),
+            DataModelNode::Integer(node) => node.title = Some(title),
+            DataModelNode::AllOf(node) => node.title = Some(title),
+            DataModelNode::Const(node) => node.title = Some(title),
+            DataModelNode::Tuple(node) => node.title = Some(title),
+            DataModelNode::Number(_) | DataModelNode::Boolean(_) | DataModelNode::Ref(_) => {}
+        }
+    }
+
+    pub fn nullable(&self) -> bool {
+        let nullable = match self {
+            DataModelNode::Array(node) => node.nullable,
+            DataModelNode::Object(node) => node.nullable,
+            DataModelNode::String(node) => node.nullable,
+            DataModelNode::Integer(node) => node.nullable,
+            DataModelNode::Number(node) => node.nullable,
+            DataModelNode::Boolean(node) => node.nullable,
+            DataModelNode::Tuple(node) => node.nullable,
+            DataModelNode::OneOf(_)
+            | DataModelNode::AllOf(_)
+            | DataModelNode::Ref(_)
+            | DataModelNode::Const(_) => None,
+        };
+
+        nullable.unwrap_or(false)
+    }
 }
 
 pub fn build_data_model_node(hash: &yaml::Hash, title: Option<String>) -> Option<DataModelNode> {
+    let hash = normalize_schema_hash(hash);
+    let hash = hash.as_ref();
+
     let builders = vec![
+        build_const_node,
         build_object_node,
         build_one_of_node,
         build_all_of_node,
+        build_tuple_node,
         build_array_node,
+        build_string_node,
+        build_integer_node,
     ];
     for builder in builders {
         if let Some(node) = builder(hash, &title) {
@@ -46,13 +93,7 @@ pub fn build_data_model_node(hash: &yaml::Hash, title: Option<String>) -> Option
         }
     }
 
-    let builders = vec![
-        build_string_node,
-        build_integer_node,
-        build_number_node,
-        build_boolean_node,
-        build_ref_node,
-    ];
+    let builders = vec![build_number_node, build_boolean_node, build_ref_node];
     for builder in builders {
         if let Some(node) = builder(hash) {
             return Some(node);
@@ -62,60 +103,125 @@ pub fn build_data_model_node(hash: &yaml::Hash, title: Option<String>) -> Option
     None
 }
 
-pub fn parse_data_model_content(
-    hash: yaml::Hash,
-    path: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    let key = path
-        .file_name()
-        .and_then(|f| f.to_str())
-        .expect("invalid file name")
-        .replace(".yaml", "");
-
-    if let Some(node) = build_data_model_node(&hash, Some(key)) {
-        (Some(vec![OpenAPINode::DataModel(node)]), yaml::Hash::new())
-    } else {
-        (None, hash)
+// NOTE: $ref だけの schema は title を持てないので、1 要素の allOf にしてキーを alias の名前として残す
+fn build_named_data_model_node(hash: &yaml::Hash, name: Option<String>) -> Option<DataModelNode> {
+    match build_data_model_node(hash, name.clone()) {
+        Some(node @ DataModelNode::Ref(_)) if name.is_some() => {
+            Some(DataModelNode::AllOf(AllOfNode {
+                title: name,
+                items: vec![node],
+            }))
+        }
+        node => node,
     }
 }
 
-pub fn parse_data_models_content(
-    mut hash: yaml::Hash,
-    path: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    let ref components_key = Yaml::String(String::from("components"));
-    let ref schemas_key = Yaml::String(String::from("schemas"));
+// NOTE: components.schemas.Pet: { $ref: ./Pet.yaml } のように同じ名前の model のファイルを指しているだけの
+//       エントリは、参照先のファイルで model が出力されるので何も出力しない
+fn is_redundant_schema_ref(name: &str, schema: &yaml::Hash) -> bool {
+    schema.len() == 1
+        && schema
+            .get(&Yaml::String("$ref".to_string()))
+            .and_then(|v| v.as_str())
+            .filter(|ref_path| ref_path.ends_with(".yaml"))
+            .and_then(|ref_path| Path::new(ref_path).file_stem())
+            .and_then(|stem| stem.to_str())
+            == Some(name)
+}
 
-    let components = hash
-        .get(components_key)
-        .and_then(|c| c.as_hash())
-        .and_then(|h| h.get(schemas_key))
-        .and_then(|s| s.as_hash());
+fn build_defs_nodes(hash: &yaml::Hash, path: &Path) -> Vec<DataModelNode> {
+    let defs = hash
+        .get(&Yaml::String(String::from("$defs")))
+        .and_then(|d| d.as_hash());
 
-    if let Some(components) = components {
+    let mut nodes = vec![];
+    for (key, value) in defs.into_iter().flat_map(|d| d.iter()) {
+        if let (Yaml::String(key), Yaml::Hash(value)) = (key, value) {
+            if let Some(node) = build_named_data_model_node(value, Some(key.clone())) {
+                nodes.push(node);
+                nodes.extend(build_defs_nodes(value, path));
+            } else {
+                logger::warning!(
+                    "invalid $defs entry `{}` in {} is ignored",
+                    key,
+                    path.display()
+                );
+            }
+        }
+    }
+
+    nodes
+}
+
+pub fn parse_data_model_content(hash: &mut yaml::Hash, path: &Path) -> Option<Vec<OpenAPINode>> {
+    // NOTE: stdin から読んだ文書はファイル名を持たないので、title か既定の名前を使う
+    let key = if is_stdin_path(path) {
+        None
+    } else {
+        Some(
+            path.file_name()
+                .and_then(|f| f.to_str())
+                .expect("invalid file name")
+                .replace(".yaml", ""),
+        )
+    };
+
+    if let Some(node) = build_named_data_model_node(hash, key) {
+        let nodes = std::iter::once(node)
+            .chain(build_defs_nodes(hash, path))
+            .map(OpenAPINode::DataModel)
+            .collect();
+        hash.clear();
+        Some(nodes)
+    } else {
+        None
+    }
+}
+
+pub fn parse_data_models_content(hash: &mut yaml::Hash, path: &Path) -> Option<Vec<OpenAPINode>> {
+    if let Some(components) = take_components_section(hash, "schemas") {
         let mut nodes = vec![];
+        let mut invalid_components = yaml::Hash::new();
 
-        for (key, value) in components.iter() {
-            if let (Yaml::String(key), Yaml::Hash(value)) = (key, value) {
-                if let Some(node) = build_data_model_node(value, Some(key.clone())) {
-                    nodes.push(node);
-                } else {
-                    dbg!(path, key, value);
-                    panic!("invalid component");
+        for (key, value) in components.into_iter() {
+            let node = match (&key, &value) {
+                (Yaml::String(name), Yaml::Hash(schema))
+                    if is_redundant_schema_ref(name, schema) =>
+                {
+                    continue;
                 }
+                (Yaml::String(name), Yaml::Hash(schema)) => {
+                    build_named_data_model_node(schema, Some(name.clone()))
+                        .map(|node| (node, build_defs_nodes(schema, path)))
+                }
+                _ => None,
+            };
+            if let Some((node, defs_nodes)) = node {
+                nodes.push(node);
+                nodes.extend(defs_nodes);
             } else {
-                dbg!(path, key, value);
-                panic!("invalid component");
+                logger::warning!(
+                    "invalid component `components.schemas.{}` in {} is ignored",
+                    key.as_str().unwrap_or_default(),
+                    path.display()
+                );
+                invalid_components.insert(key, value);
             }
         }
 
-        let _ = hash.remove(components_key);
-        let nodes = nodes
+        let mut nodes = nodes
             .into_iter()
-            .map(|n| OpenAPINode::DataModel(n))
-            .collect();
-        return (Some(nodes), hash);
+            .map(OpenAPINode::DataModel)
+            .collect::<Vec<_>>();
+        // NOTE: 解釈できなかったスキーマは Unknown として残し、変換レポートに出す
+        if !invalid_components.is_empty() {
+            nodes.push(OpenAPINode::Unknown(Box::new(build_components_section(
+                "schemas",
+                invalid_components,
+            ))));
+        }
+        return Some(nodes);
     }
 
-    (None, hash)
+    None
 }
@@ -2,33 +2,30 @@ use std::str::FromStr;
 
 use yaml_rust::yaml;
 
-use crate::openapi_parser::common::{check_unexpected_keys, get_value};
+use crate::logger;
+use crate::openapi_parser::common::{check_unexpected_keys, get_string_array, get_value};
 use crate::openapi_parser::node::data_model_node::DataModelNode;
 
 #[derive(Debug, Clone)]
 pub struct IntegerNode {
-    #[allow(dead_code)]
-    format: Option<IntegerFormat>,
+    pub title: Option<String>,
+    pub format: Option<IntegerFormat>,
     #[allow(dead_code)]
     description: Option<String>,
     #[allow(dead_code)]
     default: Option<String>,
-    #[allow(dead_code)]
-    minimum: Option<String>,
-    #[allow(dead_code)]
-    maximum: Option<String>,
-    #[allow(dead_code)]
-    exclusive_minimum: Option<bool>,
-    #[allow(dead_code)]
-    exclusive_maximum: Option<bool>,
-    #[allow(dead_code)]
-    nullable: Option<bool>,
-    #[allow(dead_code)]
-    integer_enum: Option<Vec<i64>>,
+    pub minimum: Option<String>,
+    pub maximum: Option<String>,
+    pub exclusive_minimum: Option<bool>,
+    pub exclusive_maximum: Option<bool>,
+    pub nullable: Option<bool>,
+    pub integer_enum: Option<Vec<i64>>,
     #[allow(dead_code)]
     example: Option<String>,
     #[allow(dead_code)]
     x_faker: Option<String>,
+    pub x_enum_varnames: Option<Vec<String>>,
+    pub x_enum_descriptions: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,8 +46,9 @@ impl FromStr for IntegerFormat {
     }
 }
 
-const EXPECTED_KEYS: [&'static str; 12] = [
+const EXPECTED_KEYS: [&str; 15] = [
     "type",
+    "title",
     "format",
     "description",
     "default",
@@ -62,32 +60,45 @@ const EXPECTED_KEYS: [&'static str; 12] = [
     "enum",
     "example",
     "x-faker",
+    "x-enum-varnames",
+    "x-enum-descriptions",
 ];
 
+// NOTE: OpenAPI 3.1 の nullable な enum は null を値に含むので、null は nullable として扱う
 fn get_enum(hash: &yaml::Hash) -> Option<Vec<i64>> {
     let enum_values = hash
         .get(&yaml::Yaml::String("enum".to_string()))
         .and_then(|yaml| yaml.as_vec());
 
-    if let Some(array) = enum_values {
-        Some(
-            array
-                .into_iter()
-                .map(|item| item.as_i64().expect("unexpected integer enum value"))
-                .collect(),
-        )
-    } else {
-        None
-    }
+    enum_values.map(|array| {
+        array
+            .iter()
+            .filter_map(|item| match item {
+                yaml::Yaml::Integer(val) => Some(*val),
+                yaml::Yaml::Null => None,
+                _ => {
+                    logger::warning!("unexpected integer enum value {:?} is ignored", item);
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+fn has_null_enum_value(hash: &yaml::Hash) -> bool {
+    hash.get(&yaml::Yaml::String("enum".to_string()))
+        .and_then(|yaml| yaml.as_vec())
+        .is_some_and(|array| array.iter().any(|item| item.is_null()))
 }
 
-pub fn build_integer_node(hash: &yaml::Hash) -> Option<DataModelNode> {
+pub fn build_integer_node(hash: &yaml::Hash, title: &Option<String>) -> Option<DataModelNode> {
     if Some("integer".to_string()) != get_value(hash, "type") {
         return None;
     }
     check_unexpected_keys(EXPECTED_KEYS.to_vec(), hash);
 
     Some(DataModelNode::Integer(IntegerNode {
+        title: title.clone().or(get_value(hash, "title")),
         format: get_value(hash, "format"),
         description: get_value(hash, "description"),
         default: get_value(hash, "default"),
@@ -95,9 +106,11 @@ pub fn build_integer_node(hash: &yaml::Hash) -> Option<DataModelNode> {
         maximum: get_value(hash, "maximum"),
         exclusive_minimum: get_value(hash, "exclusiveMinimum"),
         exclusive_maximum: get_value(hash, "exclusiveMaximum"),
-        nullable: get_value(hash, "nullable"),
+        nullable: get_value(hash, "nullable").or(has_null_enum_value(hash).then_some(true)),
         integer_enum: get_enum(hash),
         example: get_value(hash, "example"),
         x_faker: get_value(hash, "x-faker"),
+        x_enum_varnames: get_string_array(hash, "x-enum-varnames"),
+        x_enum_descriptions: get_string_array(hash, "x-enum-descriptions"),
     }))
 }
@@ -1,21 +1,28 @@
 mod all_of_node;
 mod array_node;
 mod boolean_node;
+mod const_node;
+#[allow(clippy::module_inception)]
 mod data_model_node;
 mod integer_node;
 mod number_node;
 mod object_node;
 mod one_of_node;
 mod ref_node;
+mod schema_normalizer;
 mod string_node;
+mod tuple_node;
 
 pub use all_of_node::*;
 pub use array_node::*;
 pub use boolean_node::*;
+pub use const_node::*;
 pub use data_model_node::*;
 pub use integer_node::*;
 pub use number_node::*;
 pub use object_node::*;
 pub use one_of_node::*;
 pub use ref_node::*;
+pub use schema_normalizer::*;
 pub use string_node::*;
+pub use tuple_node::*;
@@ -12,23 +12,24 @@ pub struct NumberNode {
     description: Option<String>,
     #[allow(dead_code)]
     default: Option<String>,
-    #[allow(dead_code)]
-    minimum: Option<String>,
-    #[allow(dead_code)]
-    maximum: Option<String>,
-    #[allow(dead_code)]
-    nullable: Option<bool>,
+    pub minimum: Option<String>,
+    pub maximum: Option<String>,
+    pub exclusive_minimum: Option<bool>,
+    pub exclusive_maximum: Option<bool>,
+    pub nullable: Option<bool>,
     #[allow(dead_code)]
     example: Option<String>,
 }
 
-const EXPECTED_KEYS: [&'static str; 8] = [
+const EXPECTED_KEYS: [&str; 10] = [
     "type",
     "format",
     "description",
     "default",
     "minimum",
     "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
     "nullable",
     "example",
 ];
@@ -62,6 +63,8 @@ pub fn build_number_node(hash: &yaml::Hash) -> Option<DataModelNode> {
         default: get_value(hash, "default"),
         minimum: get_value(hash, "minimum"),
         maximum: get_value(hash, "maximum"),
+        exclusive_minimum: get_value(hash, "exclusiveMinimum"),
+        exclusive_maximum: get_value(hash, "exclusiveMaximum"),
         nullable: get_value(hash, "nullable"),
         example: get_value(hash, "example"),
     }))
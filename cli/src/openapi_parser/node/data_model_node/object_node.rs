@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use yaml_rust::{yaml, Yaml};
 
+use crate::logger;
 use crate::openapi_parser::common::{check_unexpected_keys, get_value};
 use crate::openapi_parser::node::data_model_node::{build_data_model_node, DataModelNode};
 
@@ -19,11 +20,13 @@ pub struct ObjectNode {
 #[derive(Debug, Clone)]
 pub struct PropertyNode {
     pub key: String,
+    // NOTE: value の title にはキーが入るので、スキーマに書かれた title は別に持つ (インラインの model を切り出すときに使う)
+    pub title: Option<String>,
     pub value: DataModelNode,
     pub required: bool,
 }
 
-const EXPECTED_KEYS: [&'static str; 8] = [
+const EXPECTED_KEYS: [&str; 8] = [
     "type",
     "title",
     "properties",
@@ -63,20 +66,24 @@ fn get_properties(hash: &yaml::Hash) -> Vec<PropertyNode> {
 
     let mut result = vec![];
     for (key, value) in properties.iter() {
-        if let (Yaml::String(key), Yaml::Hash(property)) = (key, value) {
-            if let Some(node) = build_data_model_node(property, Some(key.clone())) {
-                result.push(PropertyNode {
-                    key: key.clone(),
-                    value: node,
-                    required: required_keys.contains(key),
-                });
-            } else {
-                dbg!(key, value);
-                panic!("invalid property");
+        let node = match (key, value) {
+            (Yaml::String(key), Yaml::Hash(property)) => {
+                build_data_model_node(property, Some(key.clone())).map(|node| (key, property, node))
             }
+            _ => None,
+        };
+        if let Some((key, property, node)) = node {
+            result.push(PropertyNode {
+                key: key.clone(),
+                title: get_value(property, "title"),
+                value: node,
+                required: required_keys.contains(key),
+            });
         } else {
-            dbg!(key, value);
-            panic!("unexpected key: {:?}, value: {:?}", key, value);
+            logger::warning!(
+                "invalid property `{}` is ignored",
+                key.as_str().unwrap_or_default()
+            );
         }
     }
 
@@ -94,11 +101,11 @@ pub fn build_object_node(hash: &yaml::Hash, title: &Option<String>) -> Option<Da
     let x_examples = hash
         .get(&Yaml::String(String::from("x-examples")))
         .and_then(|yaml| yaml.as_hash())
-        .map(|hash| hash.clone());
+        .cloned();
     let example = hash
         .get(&Yaml::String(String::from("example")))
         .and_then(|yaml| yaml.as_hash())
-        .map(|hash| hash.clone());
+        .cloned();
     if x_examples.is_some() && example.is_some() {
         panic!("x-examples and example cannot be used together");
     }
@@ -11,6 +11,8 @@ pub enum RefNode {
 #[derive(Debug, Clone)]
 pub struct ComponentRefNode {
     pub component_name: String,
+    // NOTE: nested_namespaces の場合に、参照先が別の名前空間にあるときだけその名前空間を持つ
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,10 +32,22 @@ pub fn build_ref_node(hash: &yaml::Hash) -> Option<DataModelNode> {
         } else if ref_path.starts_with("#/components/schemas/") {
             let component_name = ref_path.replace("#/components/schemas/", "").to_string();
 
-            RefNode::ComponentRef(ComponentRefNode { component_name })
+            RefNode::ComponentRef(ComponentRefNode {
+                component_name,
+                namespace: None,
+            })
+        } else if let Some((_, pointer)) = ref_path.split_once("#/") {
+            // NOTE: `#/$defs/Foo` や `./other.yaml#/components/schemas/Foo` は末尾の名前で参照する
+            let component_name = pointer.rsplit('/').next().unwrap_or(pointer).to_string();
+
+            RefNode::ComponentRef(ComponentRefNode {
+                component_name,
+                namespace: None,
+            })
         } else {
             RefNode::ComponentRef(ComponentRefNode {
                 component_name: ref_path.to_string(),
+                namespace: None,
             })
         };
 
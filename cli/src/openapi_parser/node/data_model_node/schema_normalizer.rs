@@ -0,0 +1,391 @@
+use std::borrow::Cow;
+
+use yaml_rust::{yaml, Yaml};
+
+use crate::logger;
+
+// NOTE: OpenAPI 3.1 (JSON Schema 2020-12) の書き方を 3.0 相当の形に寄せてから各 builder に渡す
+
+const DROPPED_KEYS: [&str; 4] = ["$schema", "$id", "$comment", "$defs"];
+
+const ANNOTATION_KEYS: [&str; 9] = [
+    "title",
+    "description",
+    "summary",
+    "deprecated",
+    "example",
+    "examples",
+    "readOnly",
+    "writeOnly",
+    "default",
+];
+
+// NOTE: type が配列のとき、各キーをどの型の分岐に移すか
+const TYPE_KEYS: [(&str, &[&str]); 5] = [
+    (
+        "object",
+        &[
+            "properties",
+            "required",
+            "additionalProperties",
+            "patternProperties",
+            "minProperties",
+            "maxProperties",
+        ],
+    ),
+    (
+        "array",
+        &[
+            "items",
+            "prefixItems",
+            "minItems",
+            "maxItems",
+            "uniqueItems",
+        ],
+    ),
+    (
+        "string",
+        &["format", "pattern", "minLength", "maxLength", "enum"],
+    ),
+    (
+        "integer",
+        &[
+            "format",
+            "minimum",
+            "maximum",
+            "exclusiveMinimum",
+            "exclusiveMaximum",
+            "multipleOf",
+            "enum",
+        ],
+    ),
+    (
+        "number",
+        &[
+            "format",
+            "minimum",
+            "maximum",
+            "exclusiveMinimum",
+            "exclusiveMaximum",
+            "multipleOf",
+        ],
+    ),
+];
+
+fn key(s: &str) -> Yaml {
+    Yaml::String(s.to_string())
+}
+
+fn is_numeric(yaml: &Yaml) -> bool {
+    matches!(yaml, Yaml::Integer(_) | Yaml::Real(_))
+}
+
+fn has_non_annotation_siblings(hash: &yaml::Hash) -> bool {
+    hash.keys()
+        .filter_map(|k| k.as_str())
+        .any(|k| k != "$ref" && !ANNOTATION_KEYS.contains(&k))
+}
+
+fn needs_normalization(hash: &yaml::Hash) -> bool {
+    let has_key = |k: &str| hash.contains_key(&key(k));
+
+    DROPPED_KEYS.iter().any(|k| has_key(k))
+        || has_key("examples")
+        || has_key("contentEncoding")
+        || has_key("contentMediaType")
+        || hash.get(&key("type")).map(|t| t.as_vec().is_some()) == Some(true)
+        || hash.get(&key("exclusiveMinimum")).map(is_numeric) == Some(true)
+        || hash.get(&key("exclusiveMaximum")).map(is_numeric) == Some(true)
+        || (has_key("$ref") && has_non_annotation_siblings(hash))
+}
+
+fn normalize_examples(hash: &mut yaml::Hash) {
+    if let Some(examples) = hash.remove(&key("examples")) {
+        let first = examples.as_vec().and_then(|v| v.first()).cloned();
+        if let (false, Some(first)) = (hash.contains_key(&key("example")), first) {
+            hash.insert(key("example"), first);
+        }
+    }
+}
+
+fn normalize_content_encoding(hash: &mut yaml::Hash) {
+    let encoding = hash.remove(&key("contentEncoding"));
+    let media_type = hash.remove(&key("contentMediaType"));
+    if encoding.is_none() && media_type.is_none() {
+        return;
+    }
+
+    if !hash.contains_key(&key("type")) {
+        hash.insert(key("type"), key("string"));
+    }
+    if hash.contains_key(&key("format")) {
+        return;
+    }
+    let format = match encoding.as_ref().and_then(|e| e.as_str()) {
+        Some("base64") | Some("base64url") => "byte",
+        _ => "binary",
+    };
+    hash.insert(key("format"), key(format));
+}
+
+fn normalize_exclusive_bound(hash: &mut yaml::Hash, exclusive_key: &str, bound_key: &str) {
+    let is_numeric_bound = hash.get(&key(exclusive_key)).map(is_numeric) == Some(true);
+    if is_numeric_bound {
+        let value = hash.remove(&key(exclusive_key)).unwrap();
+        hash.insert(key(bound_key), value);
+        hash.insert(key(exclusive_key), Yaml::Boolean(true));
+    }
+}
+
+fn null_schema() -> Yaml {
+    let mut hash = yaml::Hash::new();
+    hash.insert(key("type"), key("null"));
+    Yaml::Hash(hash)
+}
+
+fn normalize_type_array(hash: &mut yaml::Hash) {
+    let types = match hash.get(&key("type")).and_then(|t| t.as_vec()) {
+        Some(types) => types
+            .iter()
+            .map(|t| t.as_str().map(|t| t.to_string()))
+            .collect::<Option<Vec<_>>>(),
+        None => return,
+    };
+    // NOTE: 文字列以外を含む type はそのまま残し、どの builder にも当てはまらない不正な schema として扱う
+    let types = match types {
+        Some(types) => types,
+        None => {
+            logger::warning!(
+                "unexpected type value {:?} is ignored",
+                hash.get(&key("type"))
+            );
+            return;
+        }
+    };
+    let nullable = types.iter().any(|t| t == "null");
+    let non_null_types = types
+        .into_iter()
+        .filter(|t| t != "null")
+        .collect::<Vec<_>>();
+
+    match non_null_types.len() {
+        0 => {
+            hash.insert(key("type"), key("null"));
+        }
+        1 => {
+            hash.insert(key("type"), key(&non_null_types[0]));
+            if nullable {
+                hash.insert(key("nullable"), Yaml::Boolean(true));
+            }
+        }
+        _ => {
+            let mut items = non_null_types
+                .iter()
+                .map(|t| {
+                    let mut item = yaml::Hash::new();
+                    item.insert(key("type"), key(t));
+                    TYPE_KEYS
+                        .iter()
+                        .filter(|(type_name, _)| type_name == t)
+                        .flat_map(|(_, keys)| keys.iter())
+                        .for_each(|k| {
+                            if let Some(value) = hash.get(&key(k)) {
+                                item.insert(key(k), value.clone());
+                            }
+                        });
+                    Yaml::Hash(item)
+                })
+                .collect::<Vec<_>>();
+            if nullable {
+                items.push(null_schema());
+            }
+
+            // NOTE: 型ごとの制約キーは各分岐に移したので、元の schema には注釈だけを残す
+            let annotations = ANNOTATION_KEYS
+                .iter()
+                .filter_map(|k| hash.remove(&key(k)).map(|v| (key(k), v)))
+                .collect::<Vec<_>>();
+            hash.clear();
+            annotations.into_iter().for_each(|(k, v)| {
+                hash.insert(k, v);
+            });
+            hash.insert(key("oneOf"), Yaml::Array(items));
+        }
+    }
+}
+
+fn normalize_ref_siblings(hash: &mut yaml::Hash) {
+    if !hash.contains_key(&key("$ref")) || !has_non_annotation_siblings(hash) {
+        return;
+    }
+
+    let mut ref_hash = yaml::Hash::new();
+    ref_hash.insert(key("$ref"), hash.remove(&key("$ref")).unwrap());
+    let annotations = ANNOTATION_KEYS
+        .iter()
+        .filter_map(|k| hash.remove(&key(k)).map(|v| (key(k), v)))
+        .collect::<Vec<_>>();
+
+    let nullable = hash.remove(&key("nullable")).and_then(|v| v.as_bool()) == Some(true);
+    let schema = if hash.is_empty() {
+        ref_hash
+    } else {
+        let mut all_of = yaml::Hash::new();
+        all_of.insert(
+            key("allOf"),
+            Yaml::Array(vec![Yaml::Hash(ref_hash), Yaml::Hash(hash.clone())]),
+        );
+        all_of
+    };
+
+    let mut normalized = yaml::Hash::new();
+    annotations.into_iter().for_each(|(k, v)| {
+        normalized.insert(k, v);
+    });
+    if nullable {
+        normalized.insert(
+            key("oneOf"),
+            Yaml::Array(vec![Yaml::Hash(schema), null_schema()]),
+        );
+    } else {
+        schema.into_iter().for_each(|(k, v)| {
+            normalized.insert(k, v);
+        });
+    }
+
+    *hash = normalized;
+}
+
+pub fn normalize_schema_hash(hash: &yaml::Hash) -> Cow<'_, yaml::Hash> {
+    if !needs_normalization(hash) {
+        return Cow::Borrowed(hash);
+    }
+
+    let mut hash = hash.clone();
+    DROPPED_KEYS.iter().for_each(|k| {
+        hash.remove(&key(k));
+    });
+    normalize_examples(&mut hash);
+    normalize_content_encoding(&mut hash);
+    normalize_exclusive_bound(&mut hash, "exclusiveMinimum", "minimum");
+    normalize_exclusive_bound(&mut hash, "exclusiveMaximum", "maximum");
+    normalize_type_array(&mut hash);
+    normalize_ref_siblings(&mut hash);
+
+    Cow::Owned(hash)
+}
+
+#[cfg(test)]
+mod test {
+    use yaml_rust::YamlLoader;
+
+    use super::*;
+
+    fn load_hash(yaml: &str) -> yaml::Hash {
+        YamlLoader::load_from_str(yaml).unwrap()[0]
+            .as_hash()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_normalize_nullable_type_array() {
+        let hash = load_hash(
+            r#"
+            type: [string, "null"]
+            examples: [foo, bar]
+            "#,
+        );
+
+        let normalized = normalize_schema_hash(&hash);
+
+        assert_eq!(normalized.get(&key("type")), Some(&key("string")));
+        assert_eq!(normalized.get(&key("nullable")), Some(&Yaml::Boolean(true)));
+        assert_eq!(normalized.get(&key("example")), Some(&key("foo")));
+        assert_eq!(normalized.get(&key("examples")), None);
+    }
+
+    #[test]
+    fn test_normalize_multi_type_array() {
+        let hash = load_hash(
+            r#"
+            type: [array, object]
+            description: list or map
+            items:
+              type: string
+            properties:
+              id:
+                type: string
+            "#,
+        );
+
+        let normalized = normalize_schema_hash(&hash);
+
+        assert_eq!(
+            normalized.as_ref(),
+            &load_hash(
+                r#"
+                description: list or map
+                oneOf:
+                  - type: array
+                    items:
+                      type: string
+                  - type: object
+                    properties:
+                      id:
+                        type: string
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_keeps_invalid_type_array() {
+        let hash = load_hash(
+            r#"
+            type: [string, 1]
+            "#,
+        );
+
+        let normalized = normalize_schema_hash(&hash);
+
+        assert_eq!(normalized.as_ref(), &hash);
+    }
+
+    #[test]
+    fn test_normalize_ref_with_siblings() {
+        let hash = load_hash(
+            r##"
+            $ref: "#/$defs/Owner"
+            description: owner
+            nullable: true
+            "##,
+        );
+
+        let normalized = normalize_schema_hash(&hash);
+
+        assert_eq!(
+            normalized.as_ref(),
+            &load_hash(
+                r##"
+                description: owner
+                oneOf:
+                  - $ref: "#/$defs/Owner"
+                  - type: "null"
+                "##
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_keeps_openapi_30_schema() {
+        let hash = load_hash(
+            r#"
+            type: string
+            nullable: true
+            "#,
+        );
+
+        assert!(matches!(normalize_schema_hash(&hash), Cow::Borrowed(_)));
+    }
+}
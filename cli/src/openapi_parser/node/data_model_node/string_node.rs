@@ -3,15 +3,15 @@ use std::str::FromStr;
 
 use yaml_rust::{yaml, Yaml};
 
-use crate::openapi_parser::common::{check_unexpected_keys, get_value};
+use crate::logger;
+use crate::openapi_parser::common::{check_unexpected_keys, get_string_array, get_value};
 use crate::openapi_parser::node::data_model_node::data_model_node::DataModelNode;
 
 #[derive(Debug, Clone)]
 pub struct StringNode {
     pub title: Option<String>,
     pub string_enum: Option<Vec<String>>,
-    #[allow(dead_code)]
-    nullable: Option<bool>,
+    pub nullable: Option<bool>,
     #[allow(dead_code)]
     example: Option<String>,
     #[allow(dead_code)]
@@ -24,14 +24,29 @@ pub struct StringNode {
     pub max_length: Option<usize>,
     #[allow(dead_code)]
     x_faker: Option<String>,
+    pub x_enum_varnames: Option<Vec<String>>,
+    pub x_enum_descriptions: Option<Vec<String>>,
+    // NOTE: 設定の type_mappings で format に対応付けられた TypeSpec の型
+    pub mapped_type: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum StringFormat {
     Date,
     DateTime,
+    Time,
+    Duration,
     Byte,
     Binary,
+    Password,
+    Uuid,
+    Email,
+    Uri,
+    Url,
+    Hostname,
+    Ipv4,
+    Ipv6,
+    Other(String),
 }
 
 impl Display for StringFormat {
@@ -39,8 +54,19 @@ impl Display for StringFormat {
         match self {
             Self::Date => write!(f, "date"),
             Self::DateTime => write!(f, "date-time"),
+            Self::Time => write!(f, "time"),
+            Self::Duration => write!(f, "duration"),
             Self::Byte => write!(f, "byte"),
             Self::Binary => write!(f, "binary"),
+            Self::Password => write!(f, "password"),
+            Self::Uuid => write!(f, "uuid"),
+            Self::Email => write!(f, "email"),
+            Self::Uri => write!(f, "uri"),
+            Self::Url => write!(f, "url"),
+            Self::Hostname => write!(f, "hostname"),
+            Self::Ipv4 => write!(f, "ipv4"),
+            Self::Ipv6 => write!(f, "ipv6"),
+            Self::Other(format) => write!(f, "{}", format),
         }
     }
 }
@@ -52,14 +78,25 @@ impl FromStr for StringFormat {
         match s {
             "date" => Ok(Self::Date),
             "date-time" => Ok(Self::DateTime),
+            "time" => Ok(Self::Time),
+            "duration" => Ok(Self::Duration),
             "byte" => Ok(Self::Byte),
             "binary" => Ok(Self::Binary),
-            _ => Err(format!("unexpected string format: {}", s)),
+            "password" => Ok(Self::Password),
+            "uuid" => Ok(Self::Uuid),
+            "email" => Ok(Self::Email),
+            "uri" => Ok(Self::Uri),
+            "url" => Ok(Self::Url),
+            "hostname" => Ok(Self::Hostname),
+            "ipv4" => Ok(Self::Ipv4),
+            "ipv6" => Ok(Self::Ipv6),
+            // NOTE: 未知の format も捨てずに @format としてそのまま出力する
+            _ => Ok(Self::Other(s.to_string())),
         }
     }
 }
 
-const EXPECTED_KEYS: [&'static str; 12] = [
+const EXPECTED_KEYS: [&str; 14] = [
     "type",
     "title",
     "enum",
@@ -72,8 +109,11 @@ const EXPECTED_KEYS: [&'static str; 12] = [
     "minLength",
     "maxLength",
     "x-faker",
+    "x-enum-varnames",
+    "x-enum-descriptions",
 ];
 
+// NOTE: OpenAPI 3.1 の nullable な enum は null を値に含むので、null は nullable として扱う
 fn get_string_enum(hash: &yaml::Hash) -> Option<Vec<String>> {
     let enum_values = hash
         .get(&Yaml::String("enum".to_string()))
@@ -82,25 +122,36 @@ fn get_string_enum(hash: &yaml::Hash) -> Option<Vec<String>> {
     enum_values.map(|array| {
         array
             .iter()
-            .map(|item| match item {
-                Yaml::String(str) => str.clone(),
-                Yaml::Integer(val) => val.clone().to_string(),
-                _ => panic!("unexpected string enum value"),
+            .filter_map(|item| match item {
+                Yaml::String(str) | Yaml::Real(str) => Some(str.clone()),
+                Yaml::Integer(val) => Some(val.to_string()),
+                Yaml::Boolean(val) => Some(val.to_string()),
+                Yaml::Null => None,
+                _ => {
+                    logger::warning!("unexpected string enum value {:?} is ignored", item);
+                    None
+                }
             })
             .collect()
     })
 }
 
-pub fn build_string_node(hash: &yaml::Hash) -> Option<DataModelNode> {
+fn has_null_enum_value(hash: &yaml::Hash) -> bool {
+    hash.get(&Yaml::String("enum".to_string()))
+        .and_then(|yaml| yaml.as_vec())
+        .is_some_and(|array| array.iter().any(|item| item.is_null()))
+}
+
+pub fn build_string_node(hash: &yaml::Hash, title: &Option<String>) -> Option<DataModelNode> {
     if Some("string".to_string()) != get_value(hash, "type") {
         return None;
     }
     check_unexpected_keys(EXPECTED_KEYS.to_vec(), hash);
 
     Some(DataModelNode::String(StringNode {
-        title: get_value(hash, "title"),
+        title: title.clone().or(get_value(hash, "title")),
         string_enum: get_string_enum(hash),
-        nullable: get_value(hash, "nullable"),
+        nullable: get_value(hash, "nullable").or(has_null_enum_value(hash).then_some(true)),
         example: get_value(hash, "example"),
         description: get_value(hash, "description"),
         default: get_value(hash, "default"),
@@ -109,5 +160,8 @@ pub fn build_string_node(hash: &yaml::Hash) -> Option<DataModelNode> {
         min_length: get_value(hash, "minLength"),
         max_length: get_value(hash, "maxLength"),
         x_faker: get_value(hash, "x-faker"),
+        x_enum_varnames: get_string_array(hash, "x-enum-varnames"),
+        x_enum_descriptions: get_string_array(hash, "x-enum-descriptions"),
+        mapped_type: None,
     }))
 }
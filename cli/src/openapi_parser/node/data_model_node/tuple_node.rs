@@ -0,0 +1,40 @@
+use yaml_rust::{yaml, Yaml};
+
+use crate::logger;
+use crate::openapi_parser::common::get_value;
+use crate::openapi_parser::node::data_model_node::{build_data_model_node, DataModelNode};
+
+#[derive(Debug, Clone)]
+pub struct TupleNode {
+    pub title: Option<String>,
+    pub items: Vec<DataModelNode>,
+    pub nullable: Option<bool>,
+}
+
+pub fn build_tuple_node(hash: &yaml::Hash, title: &Option<String>) -> Option<DataModelNode> {
+    let prefix_items = hash
+        .get(&Yaml::String("prefixItems".to_string()))
+        .and_then(|yaml| yaml.as_vec())?;
+
+    // NOTE: prefixItems の要素が schema として解釈できない場合は tuple にしない
+    let items = prefix_items
+        .iter()
+        .map(|item| {
+            item.as_hash()
+                .and_then(|hash| build_data_model_node(hash, None))
+        })
+        .collect::<Option<Vec<_>>>();
+    let items = match items {
+        Some(items) => items,
+        None => {
+            logger::warning!("prefixItems has an invalid item, so it is ignored");
+            return None;
+        }
+    };
+
+    Some(DataModelNode::Tuple(TupleNode {
+        title: title.clone(),
+        items,
+        nullable: get_value(hash, "nullable"),
+    }))
+}
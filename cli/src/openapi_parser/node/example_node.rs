@@ -1,10 +1,10 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::{yaml, Yaml};
 
 use crate::openapi_parser::node::OpenAPINode;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExampleNode {
     #[allow(dead_code)]
     yaml: Yaml,
@@ -14,19 +14,15 @@ fn build_example_node(yaml: &Yaml) -> Option<OpenAPINode> {
     Some(OpenAPINode::Example(ExampleNode { yaml: yaml.clone() }))
 }
 
-pub fn parse_example_content(
-    hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    let ref value_key = Yaml::String(String::from("value"));
+pub fn parse_example_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
+    let value_key = &Yaml::String(String::from("value"));
 
-    let node = hash
-        .get(value_key)
-        .and_then(|value| build_example_node(value));
+    let node = hash.get(value_key).and_then(build_example_node);
 
     if let Some(node) = node {
-        (Some(vec![node]), yaml::Hash::new())
+        hash.clear();
+        Some(vec![node])
     } else {
-        (None, hash)
+        None
     }
 }
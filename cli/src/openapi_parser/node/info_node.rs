@@ -1,67 +1,66 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::yaml;
 
 use crate::openapi_parser::common::get_value;
 use crate::openapi_parser::node::OpenAPINode;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InfoNode {
     pub title: String,
     pub version: String,
-    pub contact: Contact,
-    pub terms_of_service: String,
+    pub contact: Option<Contact>,
+    pub terms_of_service: Option<String>,
+    pub key_order: Vec<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Contact {
-    pub name: String,
-    pub url: String,
-    pub email: String,
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub email: Option<String>,
+    pub key_order: Vec<String>,
+}
+
+// NOTE: 出力する decorator のプロパティ順を元の YAML に合わせるため、キーの並びを保持しておく
+fn build_key_order(hash: &yaml::Hash) -> Vec<String> {
+    hash.keys()
+        .filter_map(|key| key.as_str().map(|s| s.to_string()))
+        .collect()
 }
 
 fn build_contact(hash: &yaml::Hash) -> Option<Contact> {
-    if let Some(contact) = hash
-        .get(&yaml::Yaml::String("contact".to_string()))
+    hash.get(&yaml::Yaml::String("contact".to_string()))
         .and_then(|y| y.as_hash())
-    {
-        Some(Contact {
-            name: get_value(contact, "name").expect("name not found"),
-            url: get_value(contact, "url").expect("url not found"),
-            email: get_value(contact, "email").expect("email not found"),
+        .map(|contact| Contact {
+            name: get_value(contact, "name"),
+            url: get_value(contact, "url"),
+            email: get_value(contact, "email"),
+            key_order: build_key_order(contact),
         })
-    } else {
-        None
-    }
 }
 
 fn build_info_node(hash: &yaml::Hash) -> Option<OpenAPINode> {
-    if let Some(info) = hash
-        .get(&yaml::Yaml::String("info".to_string()))
+    hash.get(&yaml::Yaml::String("info".to_string()))
         .and_then(|y| y.as_hash())
-    {
-        Some(OpenAPINode::Info(InfoNode {
-            title: get_value(info, "title").expect("title not found"),
-            version: get_value(info, "version").expect("version not found"),
-            contact: build_contact(info).expect("contact not found"),
-            terms_of_service: get_value(info, "termsOfService")
-                .expect("terms_of_service not found"),
-        }))
-    } else {
-        None
-    }
+        .map(|info| {
+            OpenAPINode::Info(InfoNode {
+                title: get_value(info, "title").expect("title not found"),
+                version: get_value(info, "version").expect("version not found"),
+                contact: build_contact(info),
+                terms_of_service: get_value(info, "termsOfService"),
+                key_order: build_key_order(info),
+            })
+        })
 }
 
-pub fn parse_info_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    let node = build_info_node(&hash);
+pub fn parse_info_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
+    let node = build_info_node(hash);
 
     if let Some(node) = node {
         hash.remove(&yaml::Yaml::String("info".to_string()));
-        (Some(vec![node]), hash)
+        Some(vec![node])
     } else {
-        (None, hash)
+        None
     }
 }
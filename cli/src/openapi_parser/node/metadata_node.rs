@@ -1,34 +1,74 @@
-use std::path::PathBuf;
+use std::path::Path;
+use std::str::FromStr;
 
 use yaml_rust::yaml;
 
+use crate::logger;
 use crate::openapi_parser::common::get_value;
 use crate::openapi_parser::node::OpenAPINode;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenAPIVersion {
+    V3_0,
+    V3_1,
+}
+
+impl FromStr for OpenAPIVersion {
+    type Err = String;
+
+    // NOTE: 3.2 以降の 3.x は 3.1 の上位互換なので 3.1 として扱う
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let minor = s
+            .strip_prefix("3.")
+            .and_then(|rest| rest.split('.').next())
+            .and_then(|minor| minor.parse::<u32>().ok());
+        match minor {
+            Some(0) => Ok(Self::V3_0),
+            Some(_) => Ok(Self::V3_1),
+            None => Err(format!("unsupported openapi version: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MetadataNode {
-    #[allow(dead_code)]
-    openapi: String,
+    pub openapi: String,
 }
 
-fn build_metadata_node(hash: &yaml::Hash) -> Option<MetadataNode> {
-    if let Some(openapi) = get_value(hash, "openapi") {
-        Some(MetadataNode { openapi })
-    } else {
-        None
+impl MetadataNode {
+    pub fn version(&self) -> OpenAPIVersion {
+        self.openapi.parse().unwrap_or_else(|err| {
+            logger::warning!("{}, treated as 3.0", err);
+            OpenAPIVersion::V3_0
+        })
     }
 }
 
-pub fn parse_metadata_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    let node = build_metadata_node(&hash);
+fn build_metadata_node(hash: &yaml::Hash) -> Option<MetadataNode> {
+    get_value(hash, "openapi").map(|openapi| MetadataNode { openapi })
+}
+
+pub fn parse_metadata_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
+    let node = build_metadata_node(hash);
 
     if let Some(node) = node {
         hash.remove(&yaml::Yaml::String("openapi".to_string()));
-        (Some(vec![OpenAPINode::Metadata(node)]), hash)
+        hash.remove(&yaml::Yaml::String("jsonSchemaDialect".to_string()));
+        Some(vec![OpenAPINode::Metadata(node)])
     } else {
-        (None, hash)
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_openapi_version() {
+        assert_eq!("3.0.3".parse(), Ok(OpenAPIVersion::V3_0));
+        assert_eq!("3.1.0".parse(), Ok(OpenAPIVersion::V3_1));
+        assert_eq!("3.2.0".parse(), Ok(OpenAPIVersion::V3_1));
+        assert!("4.0.0".parse::<OpenAPIVersion>().is_err());
     }
 }
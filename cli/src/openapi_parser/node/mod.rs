@@ -1,3 +1,4 @@
+pub mod component_node;
 pub mod data_model_node;
 pub mod example_node;
 pub mod info_node;
@@ -10,6 +11,7 @@ pub mod unknown_node;
 mod openapi_file_node;
 pub mod operation_node;
 
+pub use component_node::*;
 pub use data_model_node::*;
 pub use example_node::*;
 pub use info_node::*;
@@ -4,22 +4,25 @@ use yaml_rust::yaml;
 
 use crate::openapi_parser::node::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OpenAPIFileNode {
     pub path: PathBuf,
     pub contents: Box<Vec<OpenAPINode>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum OpenAPINode {
     Metadata(MetadataNode),
     Info(InfoNode),
     Servers(Box<Vec<ServerNode>>),
     Tags(Box<Vec<TagNode>>),
     Paths(Box<Vec<PathNode>>),
+    Webhooks(Box<Vec<PathNode>>),
+    Webhook(WebhookNode),
     Operation(OperationNode),
     DataModel(DataModelNode),
-    Parameters(Box<Vec<ParameterNode>>),
+    Parameters(Box<Vec<ReferenceOr<ParameterNode>>>),
+    Component(ComponentNode),
     Example(ExampleNode),
     Unknown(Box<yaml::Hash>),
 }
@@ -0,0 +1,42 @@
+use yaml_rust::{yaml, Yaml};
+
+use crate::logger::warning;
+use crate::openapi_parser::node::operation_node::operation_node::{
+    build_path_item_operation_nodes, OperationNode,
+};
+
+#[derive(Debug, Clone)]
+pub struct CallbackNode {
+    pub name: String,
+    pub expression: String,
+    pub operations: Box<Vec<OperationNode>>,
+}
+
+// NOTE: callback は名前ごとに runtime expression をキーとした path item を持つ
+fn build_callback_nodes_from_callback((name, yaml): (&Yaml, &Yaml)) -> Vec<CallbackNode> {
+    let name = name.as_str().expect("Invalid callback name");
+    let hash = yaml.as_hash().expect("Invalid callback");
+    if hash.contains_key(&Yaml::String("$ref".to_string())) {
+        warning!("callback refs are not supported: {}", name);
+        return vec![];
+    }
+
+    hash.iter()
+        .map(|(expression, path_item)| {
+            let expression = expression.as_str().expect("Invalid callback expression");
+            let path_item = path_item.as_hash().expect("Invalid callback path item");
+
+            CallbackNode {
+                name: name.to_string(),
+                expression: expression.to_string(),
+                operations: Box::new(build_path_item_operation_nodes(path_item)),
+            }
+        })
+        .collect()
+}
+
+pub fn build_callback_nodes(hash: &yaml::Hash) -> Vec<CallbackNode> {
+    hash.iter()
+        .flat_map(build_callback_nodes_from_callback)
+        .collect()
+}
@@ -8,14 +8,14 @@ pub enum ContentType {
     TextPlain,
 }
 
-impl ContentType {
-    pub fn from_str(s: &str) -> Self {
+impl From<&str> for ContentType {
+    fn from(s: &str) -> Self {
         match s {
             "application/json" => ContentType::ApplicationJson,
             "application/x-www-form-urlencoded" => ContentType::ApplicationXWwwFormUrlencoded,
             "multipart/form-data" => ContentType::MultipartFormData,
             "text/plain" => ContentType::TextPlain,
-            _ => panic!("[RequestBodyType::from_str] invalid request body type {s}"),
+            _ => panic!("[ContentType::from] invalid request body type {s}"),
         }
     }
 }
@@ -0,0 +1,37 @@
+use yaml_rust::{yaml, Yaml};
+
+use crate::openapi_parser::common::{check_unexpected_keys, get_value};
+use crate::openapi_parser::node::data_model_node::{build_data_model_node, DataModelNode};
+
+#[derive(Debug, Clone)]
+pub struct HeaderNode {
+    pub description: Option<String>,
+    pub required: Option<bool>,
+    pub schema: DataModelNode,
+}
+
+pub const HEADER_EXPECTED_KEYS: [&str; 8] = [
+    "description",
+    "required",
+    "deprecated",
+    "schema",
+    "style",
+    "explode",
+    "example",
+    "examples",
+];
+
+pub fn build_header_node(hash: &yaml::Hash) -> Option<HeaderNode> {
+    check_unexpected_keys(HEADER_EXPECTED_KEYS.to_vec(), hash);
+
+    let schema = hash
+        .get(&Yaml::String("schema".to_string()))
+        .and_then(|v| v.as_hash())
+        .and_then(|h| build_data_model_node(h, None))?;
+
+    Some(HeaderNode {
+        description: get_value(hash, "description"),
+        required: get_value(hash, "required"),
+        schema,
+    })
+}
@@ -0,0 +1,71 @@
+use yaml_rust::{yaml, Yaml};
+
+use crate::logger::warning;
+use crate::openapi_parser::common::{check_unexpected_keys, get_value};
+
+#[derive(Debug, Clone)]
+pub struct LinkNode {
+    pub name: String,
+    pub operation_id: Option<String>,
+    pub operation_ref: Option<String>,
+    pub parameters: Box<Vec<(String, String)>>,
+    pub request_body: Option<String>,
+    pub description: Option<String>,
+}
+
+const EXPECTED_KEYS: [&str; 6] = [
+    "operationRef",
+    "operationId",
+    "parameters",
+    "requestBody",
+    "description",
+    "server",
+];
+
+// NOTE: link の値は runtime expression か定数なので文字列として持つ
+fn yaml_to_string(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(s) | Yaml::Real(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn build_link_node((name, yaml): (&Yaml, &Yaml)) -> Option<LinkNode> {
+    let name = name.as_str()?;
+    let hash = yaml.as_hash()?;
+    if hash.contains_key(&Yaml::String("$ref".to_string())) {
+        warning!("link refs are not supported: {}", name);
+        return None;
+    }
+    check_unexpected_keys(EXPECTED_KEYS.to_vec(), hash);
+    if hash.contains_key(&Yaml::String("server".to_string())) {
+        warning!("link server is not supported: {}", name);
+    }
+
+    let parameters = hash
+        .get(&Yaml::String("parameters".to_string()))
+        .and_then(|v| v.as_hash())
+        .map(|h| {
+            h.iter()
+                .filter_map(|(k, v)| Some((k.as_str()?.to_string(), yaml_to_string(v)?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(LinkNode {
+        name: name.to_string(),
+        operation_id: get_value(hash, "operationId"),
+        operation_ref: get_value(hash, "operationRef"),
+        parameters: Box::new(parameters),
+        request_body: hash
+            .get(&Yaml::String("requestBody".to_string()))
+            .and_then(yaml_to_string),
+        description: get_value(hash, "description"),
+    })
+}
+
+pub fn build_link_nodes(hash: &yaml::Hash) -> Vec<LinkNode> {
+    hash.iter().filter_map(build_link_node).collect()
+}
@@ -1,10 +1,17 @@
+mod callback_node;
 mod content_type;
+mod header_node;
+mod link_node;
+#[allow(clippy::module_inception)]
 mod operation_node;
 mod parameter_node;
 mod request_body_node;
 mod response_node;
 
+pub use callback_node::*;
 pub use content_type::*;
+pub use header_node::*;
+pub use link_node::*;
 pub use operation_node::*;
 pub use parameter_node::*;
 pub use request_body_node::*;
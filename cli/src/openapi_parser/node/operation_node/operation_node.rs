@@ -1,7 +1,8 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::{yaml, Yaml};
 
+use crate::logger::warning;
 use crate::openapi_parser::common::{check_unexpected_keys, get_value};
 use crate::openapi_parser::node::operation_node::request_body_node::{
     build_request_body_node, RequestBodyNode,
@@ -11,7 +12,7 @@ use crate::openapi_parser::node::operation_node::response_node::{
 };
 use crate::openapi_parser::node::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Operation {
     Get,
     Post,
@@ -44,7 +45,7 @@ impl Operation {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OperationNode {
     #[allow(dead_code)]
     pub op: Operation,
@@ -58,12 +59,13 @@ pub struct OperationNode {
     pub tags: Box<Vec<String>>,
     #[allow(dead_code)]
     pub securities: Box<Vec<yaml::Hash>>,
-    pub parameters: Box<Vec<ParameterNode>>,
-    pub request_body: Option<RequestBodyNode>,
+    pub parameters: Box<Vec<ReferenceOr<ParameterNode>>>,
+    pub request_body: Option<ReferenceOr<RequestBodyNode>>,
     pub responses: Box<Vec<ResponseNode>>,
+    pub callbacks: Box<Vec<CallbackNode>>,
 }
 
-const EXPECTED_KEYS: [&'static str; 8] = [
+const EXPECTED_KEYS: [&str; 9] = [
     "summary",
     "operationId",
     "description",
@@ -72,6 +74,7 @@ const EXPECTED_KEYS: [&'static str; 8] = [
     "parameters",
     "requestBody",
     "responses",
+    "callbacks",
 ];
 
 fn build_tags(hash: &yaml::Hash) -> Option<Vec<String>> {
@@ -89,7 +92,7 @@ fn build_securities(hash: &yaml::Hash) -> Option<Vec<yaml::Hash>> {
         .and_then(|v| v.as_vec())
         .and_then(|v| {
             v.iter()
-                .map(|v| v.as_hash().map(|h| h.clone()))
+                .map(|v| v.as_hash().cloned())
                 .collect::<Option<Vec<_>>>()
         })
 }
@@ -109,28 +112,37 @@ fn build_operation_node(hash: &yaml::Hash) -> Option<OpenAPINode> {
             let parameters = operation
                 .get(&Yaml::String("parameters".to_string()))
                 .and_then(|v| v.as_vec())
-                .and_then(|v| build_parameters_node(v));
+                .and_then(build_parameters_node);
 
             let request_body = operation
                 .get(&Yaml::String("requestBody".to_string()))
                 .and_then(|v| v.as_hash())
-                .and_then(|h| build_request_body_node(h));
+                .and_then(|h| match build_component_ref_node(h) {
+                    Some(ref_node) => Some(ReferenceOr::Ref(ref_node)),
+                    None => build_request_body_node(h).map(ReferenceOr::Item),
+                });
 
             let responses = operation
                 .get(&Yaml::String("responses".to_string()))
                 .and_then(|v| v.as_hash())
                 .and_then(build_response_nodes);
 
+            let callbacks = operation
+                .get(&Yaml::String("callbacks".to_string()))
+                .and_then(|v| v.as_hash())
+                .map(build_callback_nodes);
+
             return Some(OpenAPINode::Operation(OperationNode {
                 op: Operation::from_str(op.as_str().unwrap()),
                 summary: get_value(operation, "summary"),
                 operation_id: get_value(operation, "operationId"),
                 description: get_value(operation, "description"),
-                tags: Box::new(build_tags(operation).unwrap_or(Vec::new())),
-                securities: Box::new(build_securities(operation).unwrap_or(Vec::new())),
+                tags: Box::new(build_tags(operation).unwrap_or_default()),
+                securities: Box::new(build_securities(operation).unwrap_or_default()),
                 parameters: Box::new(parameters.unwrap_or(Vec::new())),
                 request_body,
                 responses: Box::new(responses.unwrap_or(Vec::new())),
+                callbacks: Box::new(callbacks.unwrap_or(Vec::new())),
             }));
         }
     }
@@ -138,15 +150,27 @@ fn build_operation_node(hash: &yaml::Hash) -> Option<OpenAPINode> {
     None
 }
 
-pub fn parse_operation_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    if let Some(OpenAPINode::Operation(node)) = build_operation_node(&hash) {
+// NOTE: callback や inline の webhook は path item をその場で持つので、全メソッドの operation を取り出す
+pub fn build_path_item_operation_nodes(hash: &yaml::Hash) -> Vec<OperationNode> {
+    let mut hash = hash.clone();
+    let mut operations = vec![];
+    while let Some(OpenAPINode::Operation(node)) = build_operation_node(&hash) {
+        hash.remove(&node.op.as_yaml_str());
+        operations.push(node);
+    }
+    hash.keys().filter_map(|k| k.as_str()).for_each(|key| {
+        warning!("unsupported path item key: {}", key);
+    });
+
+    operations
+}
+
+pub fn parse_operation_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
+    if let Some(OpenAPINode::Operation(node)) = build_operation_node(hash) {
         let _ = hash.remove(&node.op.as_yaml_str());
 
-        (Some(vec![OpenAPINode::Operation(node)]), hash)
+        Some(vec![OpenAPINode::Operation(node)])
     } else {
-        (None, hash)
+        None
     }
 }
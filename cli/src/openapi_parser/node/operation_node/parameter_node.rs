@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::Path;
 use std::str::FromStr;
 
 use yaml_rust::{yaml, Yaml};
@@ -9,15 +9,43 @@ use crate::openapi_parser::node::*;
 #[derive(Debug, Clone)]
 pub struct ParameterNode {
     pub name: String,
-    #[allow(dead_code)]
     pub position: ParameterPosition,
-    #[allow(dead_code)]
     pub description: Option<String>,
-    #[allow(dead_code)]
     pub required: Option<bool>,
     pub schema: DataModelNode,
     #[allow(dead_code)]
     pub parameter_enum: Option<Vec<String>>,
+    pub style: Option<ParameterStyle>,
+    pub explode: Option<bool>,
+    pub allow_reserved: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterStyle {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+impl FromStr for ParameterStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "matrix" => Ok(ParameterStyle::Matrix),
+            "label" => Ok(ParameterStyle::Label),
+            "form" => Ok(ParameterStyle::Form),
+            "simple" => Ok(ParameterStyle::Simple),
+            "spaceDelimited" => Ok(ParameterStyle::SpaceDelimited),
+            "pipeDelimited" => Ok(ParameterStyle::PipeDelimited),
+            "deepObject" => Ok(ParameterStyle::DeepObject),
+            _ => Err(format!("Invalid parameter style: {}", s)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -42,13 +70,17 @@ impl FromStr for ParameterPosition {
     }
 }
 
-const EXPECTED_KEYS: [&'static str; 9] = [
+const EXPECTED_KEYS: [&str; 13] = [
     "name",
     "in",
     "description",
     "schema",
+    "content",
     "enum",
     "required",
+    "style",
+    "explode",
+    "allowReserved",
     "require",          // NOTE: typo 生成後のファイルには含まれていないので無視する
     "minimum",          // NOTE: 意味のないパラメータだが間違えて書かれている
     "exclusiveMinimum", // NOTE: 意味のないパラメータだが間違えて書かれている
@@ -70,16 +102,31 @@ pub fn build_parameter_node(hash: &yaml::Hash) -> Option<ParameterNode> {
 
     let name: String = get_value(hash, "name").expect("Invalid parameter name");
     let schema_name = format!("{name}_schema");
+    // NOTE: content を使う形式はメディアタイプが 1 つだけなので、その schema を型として扱う
     let schema = hash
         .get(&Yaml::String("schema".to_string()))
+        .or_else(|| {
+            hash.get(&Yaml::String("content".to_string()))
+                .and_then(|v| v.as_hash())
+                .and_then(|h| h.values().next())
+                .and_then(|v| v.as_hash())
+                .and_then(|h| h.get(&Yaml::String("schema".to_string())))
+        })
         .and_then(|v| v.as_hash())
         .and_then(|h| build_data_model_node(h, Some(schema_name)))
         .expect("Invalid parameter schema");
 
+    // NOTE: content を使う形式はメディアタイプでシリアライズされるので、explode は適用されない
+    let explode = if hash.contains_key(&Yaml::String("content".to_string())) {
+        Some(false)
+    } else {
+        get_value(hash, "explode")
+    };
+
     let parameter_enum = hash
         .get(&yaml::Yaml::String("enum".to_string()))
         .and_then(|v| v.as_vec())
-        .and_then(|v| build_parameter_enum(v));
+        .and_then(build_parameter_enum);
 
     Some(ParameterNode {
         name: name.clone(),
@@ -88,33 +135,39 @@ pub fn build_parameter_node(hash: &yaml::Hash) -> Option<ParameterNode> {
         required: get_value(hash, "required"),
         schema,
         parameter_enum,
+        style: hash
+            .get(&Yaml::String("style".to_string()))
+            .and_then(|v| v.as_str())
+            .map(|s| s.parse().expect("Invalid parameter style")),
+        explode,
+        allow_reserved: get_value(hash, "allowReserved"),
     })
 }
 
-pub fn build_parameters_node(array: &yaml::Array) -> Option<Vec<ParameterNode>> {
+fn build_parameter_item(hash: &yaml::Hash) -> Option<ReferenceOr<ParameterNode>> {
+    match build_component_ref_node(hash) {
+        Some(ref_node) => Some(ReferenceOr::Ref(ref_node)),
+        None => build_parameter_node(hash).map(ReferenceOr::Item),
+    }
+}
+
+pub fn build_parameters_node(array: &yaml::Array) -> Option<Vec<ReferenceOr<ParameterNode>>> {
     array
         .iter()
-        .map(|v| v.as_hash().and_then(|h| build_parameter_node(h)))
+        .map(|v| v.as_hash().and_then(build_parameter_item))
         .collect::<Option<Vec<_>>>()
 }
 
-pub fn parse_parameters_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
+pub fn parse_parameters_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
     if let Some(parameters) = hash
         .get(&yaml::Yaml::String("parameters".to_string()))
         .and_then(|v| v.as_vec())
     {
-        let nodes = parameters
-            .iter()
-            .map(|v| v.as_hash().and_then(|h| build_parameter_node(h)))
-            .collect::<Option<Vec<_>>>()
-            .expect("Invalid parameters");
+        let nodes = build_parameters_node(parameters).expect("Invalid parameters");
 
         hash.remove(&Yaml::String("parameters".to_string()));
-        (Some(vec![OpenAPINode::Parameters(Box::new(nodes))]), hash)
+        Some(vec![OpenAPINode::Parameters(Box::new(nodes))])
     } else {
-        (None, hash)
+        None
     }
 }
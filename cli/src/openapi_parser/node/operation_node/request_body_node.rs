@@ -1,15 +1,54 @@
 use yaml_rust::{yaml, Yaml};
 
+use crate::logger::warning;
+use crate::openapi_parser::common::get_value;
 use crate::openapi_parser::node::data_model_node::{build_data_model_node, DataModelNode};
 use crate::openapi_parser::node::operation_node::content_type::ContentType;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct EncodingNode {
+    pub name: String,
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Clone)]
 pub struct RequestBodyNode {
-    #[allow(dead_code)]
-    content_type: ContentType,
+    pub content_type: ContentType,
     pub schema: DataModelNode,
+    pub encoding: Box<Vec<EncodingNode>>,
+    // NOTE: OpenAPI では requestBody.required の既定値は false
+    pub required: bool,
+    #[allow(dead_code)]
+    pub description: Option<String>,
     #[allow(dead_code)]
-    examples: Box<Vec<Yaml>>,
+    examples: Vec<Yaml>,
+}
+
+impl RequestBodyNode {
+    pub fn is_multipart(&self) -> bool {
+        matches!(self.content_type, ContentType::MultipartFormData)
+    }
+
+    pub fn get_encoding(&self, name: &str) -> Option<&EncodingNode> {
+        self.encoding.iter().find(|encoding| encoding.name == name)
+    }
+}
+
+fn build_encoding_nodes(hash: &yaml::Hash) -> Vec<EncodingNode> {
+    hash.iter()
+        .map(|(key, value)| {
+            let name = key.as_str().expect("Invalid encoding name").to_string();
+            let value = value.as_hash().expect("Invalid encoding");
+            if value.contains_key(&Yaml::String("headers".to_string())) {
+                warning!("encoding headers are not supported: {}", name);
+            }
+
+            EncodingNode {
+                name,
+                content_type: get_value(value, "contentType"),
+            }
+        })
+        .collect()
 }
 
 pub fn build_request_body_node(hash: &yaml::Hash) -> Option<RequestBodyNode> {
@@ -34,13 +73,22 @@ pub fn build_request_body_node(hash: &yaml::Hash) -> Option<RequestBodyNode> {
         let examples = content
             .get(&Yaml::String("examples".to_string()))
             .and_then(|v| v.as_vec())
-            .map(|v| v.clone())
-            .unwrap_or(Vec::new());
+            .cloned()
+            .unwrap_or_default();
+        // NOTE: encoding は multipart や urlencoded のときだけ意味を持つ
+        let encoding = content
+            .get(&Yaml::String("encoding".to_string()))
+            .and_then(|v| v.as_hash())
+            .map(build_encoding_nodes)
+            .unwrap_or_default();
 
         Some(RequestBodyNode {
-            content_type: ContentType::from_str(content_type),
+            description: get_value(hash, "description"),
+            content_type: ContentType::from(content_type),
             schema,
-            examples: Box::new(examples),
+            encoding: Box::new(encoding),
+            required: get_value(hash, "required").unwrap_or(false),
+            examples,
         })
     } else {
         None
@@ -1,52 +1,106 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::str::FromStr;
 
 use yaml_rust::{yaml, Yaml};
 
+use crate::logger;
 use crate::openapi_parser::common::get_value;
+use crate::openapi_parser::node::component_node::{build_component_ref_node, ReferenceOr};
 use crate::openapi_parser::node::data_model_node::{build_data_model_node, DataModelNode};
 use crate::openapi_parser::node::operation_node::content_type::ContentType;
+use crate::openapi_parser::node::operation_node::header_node::{build_header_node, HeaderNode};
+use crate::openapi_parser::node::operation_node::link_node::{build_link_nodes, LinkNode};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ResponseNode {
     pub status: ResponseStatus,
-    #[allow(dead_code)]
-    description: Option<String>,
+    pub response: ReferenceOr<ResponseObjectNode>,
+    // NOTE: 切り出した response model はステータスコードも持つので、参照する側では付け足さない
+    pub is_named_response: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseObjectNode {
+    pub status_code: Option<ResponseStatus>,
+    pub description: Option<String>,
     pub content_type: Option<ContentType>,
     pub schema: Option<DataModelNode>,
+    pub headers: Box<Vec<ResponseHeaderNode>>,
+    pub links: Box<Vec<LinkNode>>,
     #[allow(dead_code)]
-    examples: Option<Box<HashMap<String, Yaml>>>,
+    examples: Option<BTreeMap<String, Yaml>>,
+}
+
+impl ResponseObjectNode {
+    // NOTE: 同じ内容のレスポンスをまとめるときの比較に使う。examples は出力しないので比較に含めない
+    pub fn build_signature(&self) -> String {
+        let response = ResponseObjectNode {
+            examples: None,
+            ..self.clone()
+        };
+
+        format!("{:?}", response)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseHeaderNode {
+    pub name: String,
+    pub header: ReferenceOr<HeaderNode>,
 }
 
-#[derive(Debug)]
+// NOTE: OpenAPI のレスポンスのキーは 3 桁のステータスコード、1XX-5XX の範囲指定、default のいずれか
+#[derive(Debug, Clone, PartialEq)]
 pub enum ResponseStatus {
-    OK,
-    Created,
-    Accepted,
-    NoContent,
-    MovedPermanently,
-    BadRequest,
-    Unauthorized,
-    Forbidden,
-    NotFound,
-    UnprocessableEntity,
-    InternalServerError,
+    Code(u16),
+    // NOTE: 4XX なら 4 のように先頭の桁を持つ
+    Range(u16),
+    Default,
 }
 
 impl ResponseStatus {
-    pub fn get_code(&self) -> u16 {
+    // NOTE: default は成功のレスポンスを別に定義したうえでの「それ以外」なのでエラーとして扱う
+    pub fn is_error(&self) -> bool {
         match self {
-            ResponseStatus::OK => 200,
-            ResponseStatus::Created => 201,
-            ResponseStatus::Accepted => 202,
-            ResponseStatus::NoContent => 204,
-            ResponseStatus::MovedPermanently => 301,
-            ResponseStatus::BadRequest => 400,
-            ResponseStatus::Unauthorized => 401,
-            ResponseStatus::Forbidden => 403,
-            ResponseStatus::NotFound => 404,
-            ResponseStatus::UnprocessableEntity => 422,
-            ResponseStatus::InternalServerError => 500,
+            ResponseStatus::Code(code) => *code >= 400,
+            ResponseStatus::Range(class) => *class >= 4,
+            ResponseStatus::Default => true,
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ResponseStatus::Code(code) => match code {
+                200 => "Ok".to_string(),
+                201 => "Created".to_string(),
+                202 => "Accepted".to_string(),
+                204 => "NoContent".to_string(),
+                301 => "MovedPermanently".to_string(),
+                304 => "NotModified".to_string(),
+                400 => "BadRequest".to_string(),
+                401 => "Unauthorized".to_string(),
+                403 => "Forbidden".to_string(),
+                404 => "NotFound".to_string(),
+                409 => "Conflict".to_string(),
+                422 => "UnprocessableEntity".to_string(),
+                429 => "TooManyRequests".to_string(),
+                500 => "InternalServerError".to_string(),
+                503 => "ServiceUnavailable".to_string(),
+                code => format!("Status{}", code),
+            },
+            ResponseStatus::Range(class) => format!("Status{}XX", class),
+            ResponseStatus::Default => "Default".to_string(),
+        }
+    }
+}
+
+impl Display for ResponseStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResponseStatus::Code(code) => write!(f, "{}", code),
+            ResponseStatus::Range(class) => write!(f, "{}XX", class),
+            ResponseStatus::Default => write!(f, "default"),
         }
     }
 }
@@ -55,64 +109,130 @@ impl FromStr for ResponseStatus {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "200" => Ok(ResponseStatus::OK),
-            "201" => Ok(ResponseStatus::Created),
-            "202" => Ok(ResponseStatus::Accepted),
-            "204" => Ok(ResponseStatus::NoContent),
-            "301" => Ok(ResponseStatus::MovedPermanently),
-            "400" => Ok(ResponseStatus::BadRequest),
-            "401" => Ok(ResponseStatus::Unauthorized),
-            "403" => Ok(ResponseStatus::Forbidden),
-            "404" => Ok(ResponseStatus::NotFound),
-            "422" => Ok(ResponseStatus::UnprocessableEntity),
-            "500" => Ok(ResponseStatus::InternalServerError),
-            _ => Err(format!("Invalid response status: {}", s)),
+        let invalid = || format!("Invalid response status: {}", s);
+        if s == "default" {
+            return Ok(ResponseStatus::Default);
+        }
+        if s.len() != 3 {
+            return Err(invalid());
         }
+        match s.to_ascii_uppercase().as_str() {
+            range if range.ends_with("XX") => range[..1]
+                .parse()
+                .ok()
+                .filter(|class| (1..=5).contains(class))
+                .map(ResponseStatus::Range)
+                .ok_or_else(invalid),
+            code => code
+                .parse()
+                .ok()
+                .filter(|code| (100..=599).contains(code))
+                .map(ResponseStatus::Code)
+                .ok_or_else(invalid),
+        }
+    }
+}
+
+fn build_response_header_node((name, yaml): (&Yaml, &Yaml)) -> Option<ResponseHeaderNode> {
+    if let (Some(name), Some(hash)) = (name.as_str(), yaml.as_hash()) {
+        let header = match build_component_ref_node(hash) {
+            Some(ref_node) => ReferenceOr::Ref(ref_node),
+            None => ReferenceOr::Item(build_header_node(hash)?),
+        };
+
+        Some(ResponseHeaderNode {
+            name: name.to_string(),
+            header,
+        })
+    } else {
+        None
     }
 }
 
-fn build_examples(hash: &yaml::Hash) -> HashMap<String, Yaml> {
+fn build_examples(hash: &yaml::Hash) -> BTreeMap<String, Yaml> {
     hash.iter()
         .map(|(k, v)| (k.as_str().unwrap().to_string(), v.clone()))
         .collect()
 }
 
+pub fn build_response_object_node(hash: &yaml::Hash) -> ResponseObjectNode {
+    let content = hash
+        .get(&Yaml::String("content".to_string()))
+        .and_then(|v| v.as_hash());
+    let content_type = content
+        .and_then(|c| c.keys().next())
+        .and_then(|k| k.as_str())
+        .map(ContentType::from);
+    let content_inner = content_type
+        .clone()
+        .and_then(|c| content.and_then(|ctt| ctt.get(&Yaml::String(c.to_string()))))
+        .and_then(|c| c.as_hash());
+    let schema = content_inner
+        .and_then(|c| c.get(&Yaml::String("schema".to_string())))
+        .and_then(|y| y.as_hash())
+        .and_then(|h| build_data_model_node(h, None));
+    let examples = content_inner
+        .and_then(|c| c.get(&Yaml::String("examples".to_string())))
+        .and_then(|y| y.as_hash())
+        .map(build_examples);
+    let headers = hash
+        .get(&Yaml::String("headers".to_string()))
+        .and_then(|y| y.as_hash())
+        .map(|h| {
+            h.iter()
+                .map(build_response_header_node)
+                .collect::<Option<Vec<_>>>()
+                .expect("Invalid response headers")
+        })
+        .unwrap_or_default();
+    let links = hash
+        .get(&Yaml::String("links".to_string()))
+        .and_then(|y| y.as_hash())
+        .map(build_link_nodes)
+        .unwrap_or_default();
+
+    ResponseObjectNode {
+        status_code: None,
+        description: get_value(hash, "description"),
+        content_type,
+        schema,
+        headers: Box::new(headers),
+        links: Box::new(links),
+        examples,
+    }
+}
+
 fn build_response_node((status, yaml): (&Yaml, &Yaml)) -> Option<ResponseNode> {
-    if let (Some(status), Some(hash)) = (status.as_str(), yaml.as_hash()) {
-        let content = hash
-            .get(&Yaml::String("content".to_string()))
-            .and_then(|v| v.as_hash());
-        let content_type = content
-            .and_then(|c| c.keys().next())
-            .and_then(|k| k.as_str())
-            .map(ContentType::from_str);
-        let content_inner = content_type
-            .clone()
-            .and_then(|c| content.and_then(|ctt| ctt.get(&Yaml::String(c.to_string()))))
-            .and_then(|c| c.as_hash());
-        let schema = content_inner
-            .and_then(|c| c.get(&Yaml::String("schema".to_string())))
-            .and_then(|y| y.as_hash())
-            .and_then(|h| build_data_model_node(h, None));
-        let examples = content_inner
-            .and_then(|c| c.get(&Yaml::String("examples".to_string())))
-            .and_then(|y| y.as_hash())
-            .map(build_examples)
-            .map(Box::new);
+    // NOTE: 引用符なしのステータスコード (200:) は Integer として読まれる
+    let status = match status {
+        Yaml::Integer(code) => Some(code.to_string()),
+        status => status.as_str().map(|s| s.to_string()),
+    };
+    if let (Some(status), Some(hash)) = (status.as_deref(), yaml.as_hash()) {
+        let response = match build_component_ref_node(hash) {
+            Some(ref_node) => ReferenceOr::Ref(ref_node),
+            None => ReferenceOr::Item(build_response_object_node(hash)),
+        };
+
+        let status = match ResponseStatus::from_str(status) {
+            Ok(status) => status,
+            Err(message) => {
+                logger::warning!("{} is ignored", message);
+                return None;
+            }
+        };
 
         Some(ResponseNode {
-            status: ResponseStatus::from_str(status).expect("Invalid response status"),
-            description: get_value(hash, "description"),
-            content_type,
-            schema,
-            examples,
+            status,
+            response,
+            is_named_response: false,
         })
     } else {
         None
     }
 }
 
+// NOTE: 解釈できないステータスのレスポンスだけを捨てて、残りのレスポンスは出力する
 pub fn build_response_nodes(hash: &yaml::Hash) -> Option<Vec<ResponseNode>> {
-    hash.iter().map(build_response_node).collect()
+    Some(hash.iter().filter_map(build_response_node).collect())
 }
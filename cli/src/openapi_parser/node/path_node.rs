@@ -1,11 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use yaml_rust::{yaml, Yaml};
 
 use crate::openapi_parser::common::get_value;
-use crate::openapi_parser::node::OpenAPINode;
+use crate::openapi_parser::node::{build_path_item_operation_nodes, OpenAPINode, OperationNode};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PathNode {
     pub path: String,
     pub ref_file_path: PathBuf,
@@ -24,10 +24,7 @@ fn build_path_node((key, value): (&Yaml, &Yaml)) -> Option<PathNode> {
     }
 }
 
-pub fn parse_paths_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
+pub fn parse_paths_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
     if let Some(paths) = hash
         .get(&Yaml::String("paths".to_string()))
         .and_then(|y| y.as_hash())
@@ -39,8 +36,49 @@ pub fn parse_paths_content(
             .expect("failed to parse paths");
 
         hash.remove(&yaml::Yaml::String("paths".to_string()));
-        (Some(vec![OpenAPINode::Paths(Box::new(nodes))]), hash)
+        Some(vec![OpenAPINode::Paths(Box::new(nodes))])
     } else {
-        (None, hash)
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookNode {
+    pub name: String,
+    pub operations: Box<Vec<OperationNode>>,
+}
+
+// NOTE: webhook は paths と違い、ファイルを参照せずにその場で operation を書くことが多い
+pub fn parse_webhooks_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
+    if let Some(webhooks) = hash
+        .get(&Yaml::String("webhooks".to_string()))
+        .and_then(|y| y.as_hash())
+    {
+        let mut paths = vec![];
+        let mut nodes = vec![];
+        for (key, value) in webhooks.iter() {
+            let is_ref = value
+                .as_hash()
+                .map(|h| h.contains_key(&Yaml::String("$ref".to_string())))
+                .unwrap_or(false);
+            if is_ref {
+                paths.push(build_path_node((key, value)).expect("failed to parse webhooks"));
+            } else if let (Some(name), Some(path_item)) = (key.as_str(), value.as_hash()) {
+                nodes.push(OpenAPINode::Webhook(WebhookNode {
+                    name: name.to_string(),
+                    operations: Box::new(build_path_item_operation_nodes(path_item)),
+                }));
+            } else {
+                panic!("failed to parse webhooks");
+            }
+        }
+        if !paths.is_empty() {
+            nodes.insert(0, OpenAPINode::Webhooks(Box::new(paths)));
+        }
+
+        hash.remove(&yaml::Yaml::String("webhooks".to_string()));
+        Some(nodes)
+    } else {
+        None
     }
 }
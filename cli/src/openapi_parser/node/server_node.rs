@@ -1,29 +1,26 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::yaml;
 
 use crate::openapi_parser::common::get_value;
 use crate::openapi_parser::node::OpenAPINode;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ServerNode {
     #[allow(dead_code)]
     url: String,
     #[allow(dead_code)]
-    description: String,
+    description: Option<String>,
 }
 
 fn build_server(hash: &yaml::Hash) -> ServerNode {
     ServerNode {
         url: get_value(hash, "url").expect("url not found"),
-        description: get_value(hash, "description").expect("description not found"),
+        description: get_value(hash, "description"),
     }
 }
 
-pub fn parse_servers_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
+pub fn parse_servers_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
     if let Some(servers) = hash
         .get(&yaml::Yaml::String("servers".to_string()))
         .and_then(|y| y.as_vec())
@@ -36,8 +33,8 @@ pub fn parse_servers_content(
         let nodes = servers.into_iter().map(build_server).collect();
 
         hash.remove(&yaml::Yaml::String("servers".to_string()));
-        (Some(vec![OpenAPINode::Servers(Box::new(nodes))]), hash)
+        Some(vec![OpenAPINode::Servers(Box::new(nodes))])
     } else {
-        (None, hash)
+        None
     }
 }
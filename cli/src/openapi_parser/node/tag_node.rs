@@ -1,11 +1,11 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::yaml;
 
 use crate::openapi_parser::common::get_value;
 use crate::openapi_parser::node::OpenAPINode;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TagNode {
     #[allow(dead_code)]
     name: String,
@@ -15,23 +15,18 @@ pub struct TagNode {
     external_docs: Option<ExternalDocs>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExternalDocs {
     #[allow(dead_code)]
     url: String,
 }
 
 fn build_external_docs(hash: &yaml::Hash) -> Option<ExternalDocs> {
-    if let Some(external_docs) = hash
-        .get(&yaml::Yaml::String("externalDocs".to_string()))
+    hash.get(&yaml::Yaml::String("externalDocs".to_string()))
         .and_then(|y| y.as_hash())
-    {
-        Some(ExternalDocs {
+        .map(|external_docs| ExternalDocs {
             url: get_value(external_docs, "url").expect("url not found"),
         })
-    } else {
-        None
-    }
 }
 
 fn build_tag_node(hash: &yaml::Hash) -> TagNode {
@@ -42,10 +37,7 @@ fn build_tag_node(hash: &yaml::Hash) -> TagNode {
     }
 }
 
-pub fn parse_tags_content(
-    mut hash: yaml::Hash,
-    _: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
+pub fn parse_tags_content(hash: &mut yaml::Hash, _: &Path) -> Option<Vec<OpenAPINode>> {
     if let Some(tags) = hash
         .get(&yaml::Yaml::String("tags".to_string()))
         .and_then(|y| y.as_vec())
@@ -58,8 +50,8 @@ pub fn parse_tags_content(
         let nodes = tags.into_iter().map(build_tag_node).collect();
 
         hash.remove(&yaml::Yaml::String("tags".to_string()));
-        (Some(vec![OpenAPINode::Tags(Box::new(nodes))]), hash)
+        Some(vec![OpenAPINode::Tags(Box::new(nodes))])
     } else {
-        (None, hash)
+        None
     }
 }
@@ -1,22 +1,10 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use yaml_rust::yaml;
 
 use crate::openapi_parser::node::OpenAPINode;
 
-pub fn parse_unknown_content(
-    hash: yaml::Hash,
-    path: &PathBuf,
-) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    let unknown_keys = hash
-        .iter()
-        .map(|(k, _)| k.as_str().unwrap())
-        .collect::<Vec<_>>()
-        .join(", ");
-    dbg!(path, unknown_keys);
-
-    return (
-        Some(vec![OpenAPINode::Unknown(Box::new(hash))]),
-        yaml::Hash::new(),
-    );
+// NOTE: 残ったキーは変換レポートで未対応のキーとして報告する
+pub fn parse_unknown_content(hash: &mut yaml::Hash, _path: &Path) -> Option<Vec<OpenAPINode>> {
+    Some(vec![OpenAPINode::Unknown(Box::new(std::mem::take(hash)))])
 }
@@ -1,68 +1,90 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
+use rayon::prelude::*;
 use yaml_rust::yaml;
 
+use crate::file_source::normalize_path;
+use crate::logger;
 use crate::openapi_parser::node::{
+    collect_component_file_kinds, parse_component_content, parse_components_content,
     parse_data_model_content, parse_data_models_content, parse_example_content, parse_info_content,
     parse_metadata_content, parse_operation_content, parse_parameters_content, parse_paths_content,
-    parse_servers_content, parse_tags_content, parse_unknown_content, OpenAPIFileNode, OpenAPINode,
+    parse_servers_content, parse_tags_content, parse_unknown_content, parse_webhooks_content,
+    ComponentFileKinds, OpenAPIFileNode, OpenAPINode,
 };
+use crate::openapi_parser::swagger2::Swagger2Context;
 use crate::yaml_loader::YamlFile;
 
-fn parse_content(hash: yaml::Hash, path: &PathBuf) -> (Option<Vec<OpenAPINode>>, yaml::Hash) {
-    vec![
-        parse_metadata_content,
-        parse_info_content,
-        parse_servers_content,
-        parse_tags_content,
-        parse_paths_content,
-        parse_operation_content,
-        parse_data_model_content,
-        parse_data_models_content,
-        parse_parameters_content,
-        parse_example_content,
-        parse_unknown_content,
-    ]
-    .iter()
-    .fold((None, hash), |(node, hash), parser| {
-        if node.is_some() {
-            (node, hash)
-        } else {
-            parser(hash, path)
-        }
-    })
+type ContentParser = fn(&mut yaml::Hash, &Path) -> Option<Vec<OpenAPINode>>;
+
+// NOTE: 各 parser は自分が解釈したキーを hash から取り除く。hash は複製せずに借用して渡す
+const CONTENT_PARSERS: [ContentParser; 13] = [
+    parse_metadata_content,
+    parse_info_content,
+    parse_servers_content,
+    parse_tags_content,
+    parse_paths_content,
+    parse_webhooks_content,
+    parse_operation_content,
+    parse_data_model_content,
+    parse_data_models_content,
+    parse_components_content,
+    parse_parameters_content,
+    parse_example_content,
+    parse_unknown_content,
+];
+
+fn parse_content(hash: &mut yaml::Hash, path: &Path) -> Option<Vec<OpenAPINode>> {
+    CONTENT_PARSERS.iter().find_map(|parser| parser(hash, path))
 }
 
-fn parse_yaml_content(mut hash: yaml::Hash, path: &PathBuf) -> Vec<OpenAPINode> {
+fn parse_yaml_content(mut hash: yaml::Hash, path: &Path) -> Vec<OpenAPINode> {
     let mut result = Vec::new();
 
-    while hash.len() > 0 {
+    while !hash.is_empty() {
         let len = hash.len();
-        let (nodes, new_hash) = parse_content(hash, path);
+        let nodes = parse_content(&mut hash, path);
+        // NOTE: components は中のセクション単位で取り出すので、キーの数が変わらなくても進んでいる
+        let parsed = nodes.is_some();
         if let Some(nodes) = nodes {
             nodes.into_iter().for_each(|node| result.push(node));
         }
-        if len == new_hash.len() {
-            dbg!(path, &new_hash);
-            panic!("infinite loop")
+        if !parsed && len == hash.len() {
+            panic!("infinite loop while parsing {}", path.display())
         }
-        hash = new_hash;
     }
 
     result
 }
 
-fn parse_yaml_file(file: &YamlFile) -> OpenAPIFileNode {
+fn parse_yaml_file(
+    file: YamlFile,
+    swagger2: Option<&Swagger2Context>,
+    component_kinds: &ComponentFileKinds,
+) -> OpenAPIFileNode {
     assert_eq!(file.content.len(), 1);
 
-    let path = file.path.clone();
-    let hash = file
-        .content
-        .get(0)
-        .and_then(|c| c.as_hash())
-        .expect("invalid yaml file")
-        .to_owned();
-    let content = parse_yaml_content(hash, &path);
+    let YamlFile { path, content } = file;
+    let hash = content
+        .into_iter()
+        .next()
+        .and_then(|c| c.into_hash())
+        .expect("invalid yaml file");
+    let hash = match swagger2 {
+        Some(context) => {
+            context.warn_unconverted_keys(&hash);
+            context.upconvert(hash, &path)
+        }
+        None => hash,
+    };
+    // NOTE: 分割された component のファイルは参照元で決まった種類として parse する
+    let mut hash = hash;
+    let content = component_kinds
+        .get(&normalize_path(&path))
+        .filter(|_| !hash.is_empty())
+        .and_then(|kind| parse_component_content(&mut hash, &path, kind))
+        .unwrap_or_else(|| parse_yaml_content(hash, &path));
 
     OpenAPIFileNode {
         path,
@@ -70,6 +92,55 @@ fn parse_yaml_file(file: &YamlFile) -> OpenAPIFileNode {
     }
 }
 
-pub fn parse_yaml_files(files: &Vec<YamlFile>) -> Vec<OpenAPIFileNode> {
-    files.into_iter().map(parse_yaml_file).collect()
+// NOTE: swagger 2.0 かどうかはルートのファイルで、component の種類は参照元のファイルで決まるので、
+//       判定には全ファイルを使い、parse は指定したファイルだけ行う
+pub fn parse_yaml_files_partially(
+    files: &[YamlFile],
+    targets: &HashSet<PathBuf>,
+) -> Vec<OpenAPIFileNode> {
+    let swagger2 = Swagger2Context::detect(files);
+    let component_kinds = collect_component_file_kinds(files);
+
+    files
+        .par_iter()
+        .filter(|file| targets.contains(&file.path))
+        .map(logger::propagate(|file: &YamlFile| {
+            parse_yaml_file(file.clone(), swagger2.as_ref(), &component_kinds)
+        }))
+        .collect()
+}
+
+// NOTE: parse する前の yaml を parse と同じ形 (swagger 2.0 の場合は 3.0 に変換したもの) で返す。変換レポートで使う
+pub fn upconvert_yaml_files(files: &[YamlFile]) -> Vec<(PathBuf, yaml::Hash)> {
+    let swagger2 = Swagger2Context::detect(files);
+
+    files
+        .par_iter()
+        .map(logger::propagate(|file: &YamlFile| {
+            let hash = file
+                .content
+                .first()
+                .and_then(|c| c.as_hash())
+                .cloned()
+                .expect("invalid yaml file");
+            let hash = match swagger2.as_ref() {
+                Some(context) => context.upconvert(hash, &file.path),
+                None => hash,
+            };
+            (file.path.clone(), hash)
+        }))
+        .collect()
+}
+
+// NOTE: ファイルごとの parse は互いに独立しているので並列に行う (結果の順序は入力の順序のまま)
+pub fn parse_yaml_files(files: Vec<YamlFile>) -> Vec<OpenAPIFileNode> {
+    let swagger2 = Swagger2Context::detect(&files);
+    let component_kinds = collect_component_file_kinds(&files);
+
+    files
+        .into_par_iter()
+        .map(logger::propagate(|file| {
+            parse_yaml_file(file, swagger2.as_ref(), &component_kinds)
+        }))
+        .collect()
 }
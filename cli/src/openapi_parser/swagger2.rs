@@ -0,0 +1,1053 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use yaml_rust::{yaml, Yaml};
+
+use crate::file_source::normalize_path;
+use crate::logger;
+use crate::openapi_parser::common::get_value;
+use crate::yaml_loader::YamlFile;
+
+// NOTE: Swagger 2.0 のドキュメントを OpenAPI 3.0 の形に書き換えてから既存の parser に渡す
+//       分割されたファイルは単体では 2.0 か判断できないので、ルートの swagger キーで判定して全ファイルに適用する
+
+const OPERATION_KEYS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+const PARAMETER_SCHEMA_KEYS: [&str; 16] = [
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+];
+
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+const RESPONSE_KEYS: [&str; 4] = ["description", "schema", "headers", "examples"];
+
+// NOTE: ルート以外のファイルでも、これらのセクションを持つものはルートと同じように components に移す
+const SECTION_KEYS: [&str; 4] = [
+    "definitions",
+    "parameters",
+    "responses",
+    "securityDefinitions",
+];
+
+fn key(s: &str) -> Yaml {
+    Yaml::String(s.to_string())
+}
+
+fn get_string_vec(hash: &yaml::Hash, k: &str) -> Option<Vec<String>> {
+    hash.get(&key(k)).and_then(|v| v.as_vec()).map(|v| {
+        v.iter()
+            .filter_map(|s| s.as_str().map(|s| s.to_string()))
+            .collect()
+    })
+}
+
+// NOTE: 既存の parser は content type を 1 つしか扱えないので、json を優先して 1 つに絞る
+fn preferred_media_type(media_types: &[String]) -> String {
+    media_types
+        .iter()
+        .find(|m| m.as_str() == DEFAULT_MEDIA_TYPE)
+        .or(media_types.first())
+        .cloned()
+        .unwrap_or(DEFAULT_MEDIA_TYPE.to_string())
+}
+
+fn convert_ref(ref_path: &str) -> String {
+    [
+        ("#/definitions/", "#/components/schemas/"),
+        ("#/parameters/", "#/components/parameters/"),
+        ("#/responses/", "#/components/responses/"),
+    ]
+    .iter()
+    .find(|(from, _)| ref_path.contains(from))
+    .map(|(from, to)| ref_path.replace(from, to))
+    .unwrap_or(ref_path.to_string())
+}
+
+fn convert_schema_keywords(yaml: Yaml) -> Yaml {
+    match yaml {
+        Yaml::Hash(hash) => {
+            let is_file = hash.get(&key("type")).and_then(|t| t.as_str()) == Some("file");
+            let mut result = yaml::Hash::new();
+            for (k, v) in hash.into_iter() {
+                match (k.as_str(), v) {
+                    (Some("$ref"), Yaml::String(ref_path)) => {
+                        result.insert(k, Yaml::String(convert_ref(&ref_path)));
+                    }
+                    (Some("x-nullable"), v) => {
+                        result.insert(key("nullable"), v);
+                    }
+                    (Some("type"), _) if is_file => {
+                        result.insert(key("type"), key("string"));
+                        result.insert(key("format"), key("binary"));
+                    }
+                    (_, v) => {
+                        result.insert(k, convert_schema_keywords(v));
+                    }
+                }
+            }
+            Yaml::Hash(result)
+        }
+        Yaml::Array(array) => Yaml::Array(array.into_iter().map(convert_schema_keywords).collect()),
+        yaml => yaml,
+    }
+}
+
+fn build_content(media_type: String, schema: Yaml, example: Option<Yaml>) -> Yaml {
+    let mut media = yaml::Hash::new();
+    media.insert(key("schema"), schema);
+    if let Some(example) = example {
+        media.insert(key("example"), example);
+    }
+    let mut content = yaml::Hash::new();
+    content.insert(Yaml::String(media_type), Yaml::Hash(media));
+
+    Yaml::Hash(content)
+}
+
+fn split_parameter_schema(parameter: &yaml::Hash) -> (yaml::Hash, yaml::Hash) {
+    let mut rest = yaml::Hash::new();
+    let mut schema = yaml::Hash::new();
+    for (k, v) in parameter.iter() {
+        match k.as_str() {
+            Some(k_str) if PARAMETER_SCHEMA_KEYS.contains(&k_str) => {
+                schema.insert(k.clone(), v.clone());
+            }
+            _ => {
+                rest.insert(k.clone(), v.clone());
+            }
+        }
+    }
+
+    (rest, schema)
+}
+
+fn convert_collection_format(parameter: &mut yaml::Hash, collection_format: Option<Yaml>) {
+    let is_query = parameter.get(&key("in")).and_then(|v| v.as_str()) == Some("query");
+    let (style, explode) = match collection_format.as_ref().and_then(|c| c.as_str()) {
+        Some("multi") => ("form", true),
+        Some("ssv") => ("spaceDelimited", false),
+        Some("pipes") => ("pipeDelimited", false),
+        Some("csv") => ("form", false),
+        _ => return,
+    };
+    if is_query {
+        parameter.insert(key("style"), key(style));
+        parameter.insert(key("explode"), Yaml::Boolean(explode));
+    }
+}
+
+fn convert_parameter(parameter: &yaml::Hash) -> yaml::Hash {
+    if parameter.contains_key(&key("$ref")) {
+        return parameter.clone();
+    }
+
+    let (mut rest, mut schema) = split_parameter_schema(parameter);
+    let collection_format = rest.remove(&key("collectionFormat"));
+    rest.remove(&key("allowEmptyValue"));
+    if let Some(items) = schema
+        .get_mut(&key("items"))
+        .and_then(|i| i.as_hash().cloned())
+    {
+        let (_, items_schema) = split_parameter_schema(&items);
+        schema.insert(key("items"), Yaml::Hash(items_schema));
+    }
+    convert_collection_format(&mut rest, collection_format);
+    if !schema.is_empty() {
+        rest.insert(key("schema"), Yaml::Hash(schema));
+    }
+
+    rest
+}
+
+fn build_body_request(parameter: &yaml::Hash, consumes: &[String]) -> yaml::Hash {
+    let mut request_body = yaml::Hash::new();
+    if let Some(description) = parameter.get(&key("description")) {
+        request_body.insert(key("description"), description.clone());
+    }
+    if let Some(required) = parameter.get(&key("required")) {
+        request_body.insert(key("required"), required.clone());
+    }
+    let schema = parameter
+        .get(&key("schema"))
+        .cloned()
+        .expect("body parameter must have schema");
+    request_body.insert(
+        key("content"),
+        build_content(preferred_media_type(consumes), schema, None),
+    );
+
+    request_body
+}
+
+fn build_form_request(parameters: &[&yaml::Hash], consumes: &[String]) -> yaml::Hash {
+    let mut properties = yaml::Hash::new();
+    let mut required = vec![];
+    let mut has_file = false;
+    for parameter in parameters {
+        let name = get_value::<String>(parameter, "name").expect("formData must have name");
+        let (rest, mut schema) = split_parameter_schema(parameter);
+        has_file |= schema.get(&key("type")).and_then(|t| t.as_str()) == Some("file");
+        if let Some(description) = rest.get(&key("description")) {
+            schema.insert(key("description"), description.clone());
+        }
+        if get_value::<bool>(parameter, "required") == Some(true) {
+            required.push(Yaml::String(name.clone()));
+        }
+        properties.insert(Yaml::String(name), Yaml::Hash(schema));
+    }
+
+    let media_type = if has_file || consumes.iter().any(|c| c == "multipart/form-data") {
+        "multipart/form-data"
+    } else {
+        "application/x-www-form-urlencoded"
+    };
+    let mut schema = yaml::Hash::new();
+    schema.insert(key("type"), key("object"));
+    schema.insert(key("properties"), Yaml::Hash(properties));
+    if !required.is_empty() {
+        schema.insert(key("required"), Yaml::Array(required));
+    }
+
+    let mut request_body = yaml::Hash::new();
+    request_body.insert(
+        key("content"),
+        build_content(media_type.to_string(), Yaml::Hash(schema), None),
+    );
+
+    request_body
+}
+
+fn convert_header(header: &yaml::Hash) -> yaml::Hash {
+    let (mut rest, schema) = split_parameter_schema(header);
+    rest.remove(&key("collectionFormat"));
+    rest.insert(key("schema"), Yaml::Hash(schema));
+
+    rest
+}
+
+fn convert_response(response: &yaml::Hash, produces: &[String]) -> yaml::Hash {
+    if response.contains_key(&key("$ref")) {
+        return response.clone();
+    }
+
+    let media_type = preferred_media_type(produces);
+    let example = response
+        .get(&key("examples"))
+        .and_then(|e| e.as_hash())
+        .and_then(|e| e.get(&Yaml::String(media_type.clone())))
+        .cloned();
+
+    let mut result = yaml::Hash::new();
+    for (k, v) in response.iter() {
+        match (k.as_str(), v) {
+            (Some("schema"), schema) => {
+                result.insert(
+                    key("content"),
+                    build_content(media_type.clone(), schema.clone(), example.clone()),
+                );
+            }
+            (Some("examples"), _) => {}
+            (Some("headers"), Yaml::Hash(headers)) => {
+                let headers = headers
+                    .iter()
+                    .map(|(name, header)| {
+                        let header = header.as_hash().map(convert_header).unwrap_or_default();
+                        (name.clone(), Yaml::Hash(header))
+                    })
+                    .collect();
+                result.insert(k.clone(), Yaml::Hash(headers));
+            }
+            _ => {
+                result.insert(k.clone(), v.clone());
+            }
+        }
+    }
+
+    result
+}
+
+// NOTE: 200: のように引用符なしで書いたステータスコードは Integer になるので、文字列に揃える
+fn convert_status(status: &Yaml) -> Yaml {
+    match status {
+        Yaml::Integer(code) => Yaml::String(code.to_string()),
+        status => status.clone(),
+    }
+}
+
+fn convert_responses(responses: &yaml::Hash, produces: &[String]) -> yaml::Hash {
+    responses
+        .iter()
+        .map(|(status, response)| {
+            let response = response
+                .as_hash()
+                .map(|r| convert_response(r, produces))
+                .expect("invalid response");
+            (convert_status(status), Yaml::Hash(response))
+        })
+        .collect()
+}
+
+fn is_request_body_parameter(parameter: &yaml::Hash) -> bool {
+    matches!(
+        get_value::<String>(parameter, "in").as_deref(),
+        Some("body" | "formData")
+    )
+}
+
+fn is_parameter(hash: &yaml::Hash) -> bool {
+    hash.contains_key(&key("in")) && hash.contains_key(&key("name"))
+}
+
+fn is_response(hash: &yaml::Hash) -> bool {
+    hash.contains_key(&key("description"))
+        && hash
+            .keys()
+            .all(|k| k.as_str().map(|k| RESPONSE_KEYS.contains(&k)) == Some(true))
+}
+
+fn has_section(hash: &yaml::Hash) -> bool {
+    SECTION_KEYS
+        .iter()
+        .any(|k| hash.get(&key(k)).and_then(|v| v.as_hash()).is_some())
+}
+
+fn convert_parameters(parameters: &yaml::Array) -> Yaml {
+    Yaml::Array(
+        parameters
+            .iter()
+            .filter_map(|p| p.as_hash())
+            .map(|p| Yaml::Hash(convert_parameter(p)))
+            .collect(),
+    )
+}
+
+#[derive(Debug)]
+pub struct Swagger2Context {
+    consumes: Vec<String>,
+    produces: Vec<String>,
+    // NOTE: ルートの parameters のうち body と formData のもの。3.0 では requestBody になるので $ref を展開する
+    body_parameters: yaml::Hash,
+    // NOTE: body と formData の parameter を 1 つだけ書いた分割ファイル。ルートのものと同じく $ref を展開する
+    body_parameter_files: HashMap<PathBuf, yaml::Hash>,
+}
+
+impl Swagger2Context {
+    pub fn detect(files: &[YamlFile]) -> Option<Self> {
+        files
+            .iter()
+            .filter_map(|file| file.content.first().and_then(|c| c.as_hash()))
+            .find(|hash| get_value::<String>(hash, "swagger").as_deref() == Some("2.0"))
+            .map(|hash| Swagger2Context {
+                consumes: get_string_vec(hash, "consumes").unwrap_or_default(),
+                produces: get_string_vec(hash, "produces").unwrap_or_default(),
+                body_parameters: hash
+                    .get(&key("parameters"))
+                    .and_then(|p| p.as_hash())
+                    .map(|parameters| {
+                        parameters
+                            .iter()
+                            .filter(|(_, p)| {
+                                p.as_hash().map(is_request_body_parameter) == Some(true)
+                            })
+                            .map(|(name, p)| (name.clone(), p.clone()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                body_parameter_files: files
+                    .iter()
+                    .filter_map(|file| {
+                        file.content
+                            .first()
+                            .and_then(|c| c.as_hash())
+                            .filter(|hash| is_parameter(hash) && is_request_body_parameter(hash))
+                            .map(|hash| (normalize_path(&file.path), hash.clone()))
+                    })
+                    .collect(),
+            })
+    }
+
+    // NOTE: 別ファイルからルートを参照する場合 (../swagger.yaml#/parameters/X) も parameters の名前で解決する
+    //       ファイルへの参照 (../parameters/pet.yaml) は参照元のディレクトリからの相対パスで解決する
+    fn resolve_body_parameter<'a>(
+        &'a self,
+        parameter: &'a yaml::Hash,
+        dir: &Path,
+    ) -> &'a yaml::Hash {
+        let ref_path = match get_value::<String>(parameter, "$ref") {
+            Some(ref_path) => ref_path,
+            None => return parameter,
+        };
+        let resolved = match ref_path.split_once('#') {
+            Some((_, pointer)) => pointer
+                .strip_prefix("/parameters/")
+                .and_then(|name| self.body_parameters.get(&key(name)))
+                .and_then(|p| p.as_hash()),
+            None => self
+                .body_parameter_files
+                .get(&normalize_path(&dir.join(&ref_path))),
+        };
+
+        resolved.unwrap_or(parameter)
+    }
+
+    fn convert_operation(&self, operation: &yaml::Hash, dir: &Path) -> yaml::Hash {
+        let consumes = get_string_vec(operation, "consumes").unwrap_or(self.consumes.clone());
+        let produces = get_string_vec(operation, "produces").unwrap_or(self.produces.clone());
+
+        let mut result = yaml::Hash::new();
+        for (k, v) in operation.iter() {
+            match (k.as_str(), v) {
+                (Some("consumes"), _) | (Some("produces"), _) | (Some("schemes"), _) => {}
+                (Some("parameters"), Yaml::Array(parameters)) => {
+                    let parameters = parameters
+                        .iter()
+                        .filter_map(|p| p.as_hash())
+                        .map(|p| self.resolve_body_parameter(p, dir))
+                        .collect::<Vec<_>>();
+                    let position = |p: &yaml::Hash| get_value::<String>(p, "in");
+
+                    let body = parameters
+                        .iter()
+                        .find(|p| position(p).as_deref() == Some("body"));
+                    let form = parameters
+                        .iter()
+                        .filter(|p| position(p).as_deref() == Some("formData"))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let others = parameters
+                        .iter()
+                        .filter(|p| !matches!(position(p).as_deref(), Some("body" | "formData")))
+                        .map(|p| Yaml::Hash(convert_parameter(p)))
+                        .collect::<Vec<_>>();
+
+                    if !others.is_empty() {
+                        result.insert(key("parameters"), Yaml::Array(others));
+                    }
+                    if let Some(body) = body {
+                        result.insert(
+                            key("requestBody"),
+                            Yaml::Hash(build_body_request(body, &consumes)),
+                        );
+                    } else if !form.is_empty() {
+                        result.insert(
+                            key("requestBody"),
+                            Yaml::Hash(build_form_request(&form, &consumes)),
+                        );
+                    }
+                }
+                (Some("responses"), Yaml::Hash(responses)) => {
+                    result.insert(
+                        k.clone(),
+                        Yaml::Hash(convert_responses(responses, &produces)),
+                    );
+                }
+                _ => {
+                    result.insert(k.clone(), v.clone());
+                }
+            }
+        }
+
+        result
+    }
+
+    fn convert_path_item(&self, path_item: &yaml::Hash, dir: &Path) -> yaml::Hash {
+        path_item
+            .iter()
+            .map(|(k, v)| match (k.as_str(), v) {
+                (Some(op), Yaml::Hash(operation)) if OPERATION_KEYS.contains(&op) => (
+                    k.clone(),
+                    Yaml::Hash(self.convert_operation(operation, dir)),
+                ),
+                (Some("parameters"), Yaml::Array(parameters)) => {
+                    (k.clone(), convert_parameters(parameters))
+                }
+                _ => (k.clone(), v.clone()),
+            })
+            .collect()
+    }
+
+    fn build_servers(&self, hash: &yaml::Hash) -> Option<Yaml> {
+        let host = get_value::<String>(hash, "host")?;
+        let base_path = get_value::<String>(hash, "basePath").unwrap_or_default();
+        let schemes = get_string_vec(hash, "schemes").unwrap_or(vec!["https".to_string()]);
+
+        let servers = schemes
+            .iter()
+            .map(|scheme| {
+                let mut server = yaml::Hash::new();
+                server.insert(
+                    key("url"),
+                    Yaml::String(format!("{}://{}{}", scheme, host, base_path)),
+                );
+                Yaml::Hash(server)
+            })
+            .collect();
+
+        Some(Yaml::Array(servers))
+    }
+
+    fn convert_security_scheme(scheme: &yaml::Hash) -> yaml::Hash {
+        let mut result = yaml::Hash::new();
+        match get_value::<String>(scheme, "type").as_deref() {
+            Some("basic") => {
+                result.insert(key("type"), key("http"));
+                result.insert(key("scheme"), key("basic"));
+            }
+            Some("oauth2") => {
+                let flow = match get_value::<String>(scheme, "flow").as_deref() {
+                    Some("implicit") => "implicit",
+                    Some("password") => "password",
+                    Some("application") => "clientCredentials",
+                    _ => "authorizationCode",
+                };
+                let mut flow_hash = yaml::Hash::new();
+                for k in ["authorizationUrl", "tokenUrl", "scopes"] {
+                    if let Some(v) = scheme.get(&key(k)) {
+                        flow_hash.insert(key(k), v.clone());
+                    }
+                }
+                let mut flows = yaml::Hash::new();
+                flows.insert(key(flow), Yaml::Hash(flow_hash));
+
+                result.insert(key("type"), key("oauth2"));
+                result.insert(key("flows"), Yaml::Hash(flows));
+            }
+            _ => {
+                result = scheme.clone();
+            }
+        }
+        if let Some(description) = scheme.get(&key("description")) {
+            result.insert(key("description"), description.clone());
+        }
+
+        result
+    }
+
+    fn convert_root(&self, hash: yaml::Hash, dir: &Path) -> yaml::Hash {
+        let mut result = yaml::Hash::new();
+        let mut components = yaml::Hash::new();
+        let servers = self.build_servers(&hash);
+
+        for (k, v) in hash.iter() {
+            match (k.as_str(), v) {
+                (Some("swagger"), _) => {
+                    result.insert(key("openapi"), key("3.0.3"));
+                }
+                (Some("info"), _) => {
+                    result.insert(k.clone(), v.clone());
+                    if let Some(servers) = &servers {
+                        result.insert(key("servers"), servers.clone());
+                    }
+                }
+                (Some("host" | "basePath" | "schemes" | "consumes" | "produces"), _) => {}
+                (Some("paths"), Yaml::Hash(paths)) => {
+                    let paths = paths
+                        .iter()
+                        .map(|(path, item)| match item.as_hash() {
+                            Some(item) if !item.contains_key(&key("$ref")) => {
+                                (path.clone(), Yaml::Hash(self.convert_path_item(item, dir)))
+                            }
+                            _ => (path.clone(), item.clone()),
+                        })
+                        .collect();
+                    result.insert(k.clone(), Yaml::Hash(paths));
+                }
+                (Some("definitions"), _) => {
+                    components.insert(key("schemas"), v.clone());
+                }
+                (Some("parameters"), Yaml::Hash(parameters)) => {
+                    let parameters = parameters
+                        .iter()
+                        .filter(|(name, _)| !self.body_parameters.contains_key(name))
+                        .map(|(name, p)| {
+                            let p = p.as_hash().map(convert_parameter).unwrap_or_default();
+                            (name.clone(), Yaml::Hash(p))
+                        })
+                        .collect::<yaml::Hash>();
+                    if !parameters.is_empty() {
+                        components.insert(key("parameters"), Yaml::Hash(parameters));
+                    }
+                }
+                (Some("responses"), Yaml::Hash(responses)) => {
+                    components.insert(
+                        key("responses"),
+                        Yaml::Hash(convert_responses(responses, &self.produces)),
+                    );
+                }
+                // NOTE: 3.0 の securitySchemes と同じく @useAuth には変換しない。変換レポートで失われたものとして報告する
+                (Some("securityDefinitions"), Yaml::Hash(schemes)) => {
+                    let schemes = schemes
+                        .iter()
+                        .map(|(name, s)| {
+                            let s = s
+                                .as_hash()
+                                .map(Self::convert_security_scheme)
+                                .unwrap_or_default();
+                            (name.clone(), Yaml::Hash(s))
+                        })
+                        .collect();
+                    components.insert(key("securitySchemes"), Yaml::Hash(schemes));
+                }
+                _ => {
+                    result.insert(k.clone(), v.clone());
+                }
+            }
+        }
+        if !components.is_empty() {
+            result.insert(key("components"), Yaml::Hash(components));
+        }
+
+        result
+    }
+
+    // NOTE: 変換レポート用の upconvert でも同じ警告が出ないように、parse するときだけ呼ぶ
+    pub fn warn_unconverted_keys(&self, hash: &yaml::Hash) {
+        if hash.contains_key(&key("securityDefinitions")) {
+            logger::warning!("securityDefinitions are not converted, no @useAuth is emitted");
+        }
+    }
+
+    // NOTE: 分割されたファイルは中身の形から、ルート (またはその一部)、parameter、response、path item のどれかとして変換する
+    pub fn upconvert(&self, hash: yaml::Hash, path: &Path) -> yaml::Hash {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let hash = if hash.contains_key(&key("swagger")) || has_section(&hash) {
+            self.convert_root(hash, dir)
+        } else if is_parameter(&hash) && is_request_body_parameter(&hash) {
+            // NOTE: 参照する operation の requestBody に展開するので、ファイル自体は何も出力しない
+            yaml::Hash::new()
+        } else if is_parameter(&hash) {
+            convert_parameter(&hash)
+        } else if is_response(&hash) {
+            convert_response(&hash, &self.produces)
+        } else {
+            self.convert_path_item(&hash, dir)
+        };
+
+        match convert_schema_keywords(Yaml::Hash(hash)) {
+            Yaml::Hash(hash) => hash,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use yaml_rust::YamlLoader;
+
+    use super::*;
+
+    fn load_file(path: &str, yaml: &str) -> YamlFile {
+        YamlFile {
+            path: PathBuf::from(path),
+            content: Box::new(YamlLoader::load_from_str(yaml).unwrap()),
+        }
+    }
+
+    fn load_hash(yaml: &str) -> yaml::Hash {
+        YamlLoader::load_from_str(yaml).unwrap()[0]
+            .as_hash()
+            .unwrap()
+            .clone()
+    }
+
+    fn upconvert(files: &[YamlFile]) -> Vec<yaml::Hash> {
+        let context = Swagger2Context::detect(files).unwrap();
+        files
+            .iter()
+            .map(|file| context.upconvert(file.content[0].as_hash().unwrap().clone(), &file.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_upconvert_responses_and_definitions() {
+        let files = vec![load_file(
+            "/api/swagger.yaml",
+            r##"
+            swagger: "2.0"
+            info:
+              title: Petstore
+              version: 1.0.0
+            produces:
+              - application/json
+            paths:
+              /pets:
+                get:
+                  responses:
+                    200:
+                      description: ok
+                      schema:
+                        $ref: "#/definitions/Pet"
+                    404:
+                      $ref: "#/responses/NotFound"
+            definitions:
+              Pet:
+                type: object
+                properties:
+                  tag:
+                    type: string
+                    x-nullable: true
+            responses:
+              NotFound:
+                description: not found
+            "##,
+        )];
+
+        assert_eq!(
+            upconvert(&files)[0],
+            load_hash(
+                r##"
+                openapi: 3.0.3
+                info:
+                  title: Petstore
+                  version: 1.0.0
+                paths:
+                  /pets:
+                    get:
+                      responses:
+                        "200":
+                          description: ok
+                          content:
+                            application/json:
+                              schema:
+                                $ref: "#/components/schemas/Pet"
+                        "404":
+                          $ref: "#/components/responses/NotFound"
+                components:
+                  schemas:
+                    Pet:
+                      type: object
+                      properties:
+                        tag:
+                          type: string
+                          nullable: true
+                  responses:
+                    NotFound:
+                      description: not found
+                "##
+            )
+        );
+    }
+
+    #[test]
+    fn test_upconvert_body_parameters() {
+        let files = vec![
+            load_file(
+                "/api/swagger.yaml",
+                r##"
+                swagger: "2.0"
+                info:
+                  title: Petstore
+                  version: 1.0.0
+                consumes:
+                  - application/json
+                paths:
+                  /pets:
+                    $ref: ./paths/pets.yaml
+                parameters:
+                  PetBody:
+                    in: body
+                    name: pet
+                    required: true
+                    schema:
+                      $ref: "#/definitions/Pet"
+                  Limit:
+                    in: query
+                    name: limit
+                    type: integer
+                "##,
+            ),
+            load_file(
+                "/api/paths/pets.yaml",
+                r##"
+                post:
+                  parameters:
+                    - $ref: "../swagger.yaml#/parameters/PetBody"
+                    - $ref: "../swagger.yaml#/parameters/Limit"
+                  responses:
+                    201:
+                      description: created
+                put:
+                  parameters:
+                    - in: formData
+                      name: name
+                      type: string
+                      required: true
+                    - in: formData
+                      name: photo
+                      type: file
+                  responses:
+                    204:
+                      description: updated
+                "##,
+            ),
+        ];
+
+        let hashes = upconvert(&files);
+
+        assert_eq!(
+            hashes[0][&key("components")],
+            Yaml::Hash(load_hash(
+                r##"
+                parameters:
+                  Limit:
+                    in: query
+                    name: limit
+                    schema:
+                      type: integer
+                "##
+            ))
+        );
+        assert_eq!(
+            hashes[1],
+            load_hash(
+                r##"
+                post:
+                  parameters:
+                    - $ref: "../swagger.yaml#/components/parameters/Limit"
+                  requestBody:
+                    required: true
+                    content:
+                      application/json:
+                        schema:
+                          $ref: "#/components/schemas/Pet"
+                  responses:
+                    "201":
+                      description: created
+                put:
+                  requestBody:
+                    content:
+                      multipart/form-data:
+                        schema:
+                          type: object
+                          properties:
+                            name:
+                              type: string
+                            photo:
+                              type: string
+                              format: binary
+                          required:
+                            - name
+                  responses:
+                    "204":
+                      description: updated
+                "##
+            )
+        );
+    }
+
+    #[test]
+    fn test_upconvert_split_files() {
+        let files = vec![
+            load_file(
+                "/api/swagger.yaml",
+                r##"
+                swagger: "2.0"
+                info:
+                  title: Petstore
+                  version: 1.0.0
+                produces:
+                  - application/json
+                paths:
+                  /pets:
+                    $ref: ./paths/pets.yaml
+                "##,
+            ),
+            load_file(
+                "/api/paths/pets.yaml",
+                r##"
+                get:
+                  parameters:
+                    - $ref: ../parameters/limit.yaml
+                    - $ref: ../common.yaml#/parameters/Offset
+                  responses:
+                    200:
+                      $ref: ../responses/PetList.yaml
+                post:
+                  parameters:
+                    - $ref: ../parameters/pet_body.yaml
+                  responses:
+                    201:
+                      description: created
+                "##,
+            ),
+            load_file(
+                "/api/parameters/limit.yaml",
+                r##"
+                in: query
+                name: limit
+                type: array
+                items:
+                  type: integer
+                collectionFormat: multi
+                "##,
+            ),
+            load_file(
+                "/api/parameters/pet_body.yaml",
+                r##"
+                in: body
+                name: pet
+                required: true
+                schema:
+                  $ref: ../definitions/Pet.yaml
+                "##,
+            ),
+            load_file(
+                "/api/responses/PetList.yaml",
+                r##"
+                description: ok
+                schema:
+                  type: array
+                  items:
+                    $ref: ../definitions/Pet.yaml
+                headers:
+                  X-Rate-Limit:
+                    type: integer
+                "##,
+            ),
+            load_file(
+                "/api/common.yaml",
+                r##"
+                parameters:
+                  Offset:
+                    in: query
+                    name: offset
+                    type: integer
+                responses:
+                  NotFound:
+                    description: not found
+                    schema:
+                      $ref: "#/definitions/Error"
+                definitions:
+                  Error:
+                    type: object
+                "##,
+            ),
+            load_file(
+                "/api/definitions/Pet.yaml",
+                r##"
+                type: object
+                description: pet
+                properties:
+                  tag:
+                    type: string
+                    x-nullable: true
+                "##,
+            ),
+        ];
+
+        let hashes = upconvert(&files);
+
+        assert_eq!(
+            hashes[1],
+            load_hash(
+                r##"
+                get:
+                  parameters:
+                    - $ref: ../parameters/limit.yaml
+                    - $ref: "../common.yaml#/components/parameters/Offset"
+                  responses:
+                    "200":
+                      $ref: ../responses/PetList.yaml
+                post:
+                  requestBody:
+                    required: true
+                    content:
+                      application/json:
+                        schema:
+                          $ref: ../definitions/Pet.yaml
+                  responses:
+                    "201":
+                      description: created
+                "##
+            )
+        );
+        assert_eq!(
+            hashes[2],
+            load_hash(
+                r##"
+                in: query
+                name: limit
+                style: form
+                explode: true
+                schema:
+                  type: array
+                  items:
+                    type: integer
+                "##
+            )
+        );
+        assert!(hashes[3].is_empty());
+        assert_eq!(
+            hashes[4],
+            load_hash(
+                r##"
+                description: ok
+                content:
+                  application/json:
+                    schema:
+                      type: array
+                      items:
+                        $ref: ../definitions/Pet.yaml
+                headers:
+                  X-Rate-Limit:
+                    schema:
+                      type: integer
+                "##
+            )
+        );
+        assert_eq!(
+            hashes[5],
+            load_hash(
+                r##"
+                components:
+                  parameters:
+                    Offset:
+                      in: query
+                      name: offset
+                      schema:
+                        type: integer
+                  responses:
+                    NotFound:
+                      description: not found
+                      content:
+                        application/json:
+                          schema:
+                            $ref: "#/components/schemas/Error"
+                  schemas:
+                    Error:
+                      type: object
+                "##
+            )
+        );
+        assert_eq!(
+            hashes[6],
+            load_hash(
+                r##"
+                type: object
+                description: pet
+                properties:
+                  tag:
+                    type: string
+                    nullable: true
+                "##
+            )
+        );
+    }
+}
@@ -1,2 +1,3 @@
 pub mod node;
 pub mod node_builder;
+pub mod printer;
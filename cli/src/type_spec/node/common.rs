@@ -1,3 +1,32 @@
+// NOTE: \ を先にエスケープしないと、後から付けた \" や \n の \ まで二重にエスケープしてしまう
+//       ${ は TypeSpec の文字列補間になるので $ もエスケープする
 pub fn string_literal(str: &str) -> String {
-    format!("\"{}\"", str)
+    let mut result = String::from("\"");
+    let mut chars = str.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '$' if chars.peek() == Some(&'{') => result.push_str("\\$"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_string_literal() {
+        assert_eq!(string_literal("a\"b"), "\"a\\\"b\"");
+        assert_eq!(string_literal("^\\d+\n$"), "\"^\\\\d+\\n$\"");
+        assert_eq!(string_literal("${id}"), "\"\\${id}\"");
+    }
 }
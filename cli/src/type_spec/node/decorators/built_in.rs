@@ -2,7 +2,11 @@ use std::fmt::Display;
 
 use crate::type_spec::node::common::string_literal;
 use crate::type_spec::node::decorators::{LibInfo, TypeSpecDecorator};
-use crate::type_spec::node::{NameSpaceDecorator, RecordPropertyDecorator};
+use crate::type_spec::node::{
+    EnumDecorator, EnumMemberDecorator, ModelDecorator, NameSpaceDecorator, ParameterDecorator,
+    RecordPropertyDecorator, ScalarDecorator,
+};
+use crate::type_spec::printer::{decorator_doc, object_doc, print_flat, text, Doc};
 
 #[derive(Debug)]
 pub struct MinLengthDecorator {
@@ -26,8 +30,12 @@ impl LibInfo for MinLengthDecorator {
 
 impl TypeSpecDecorator for MinLengthDecorator {}
 
+impl ScalarDecorator for MinLengthDecorator {}
+
 impl RecordPropertyDecorator for MinLengthDecorator {}
 
+impl ParameterDecorator for MinLengthDecorator {}
+
 #[derive(Debug)]
 pub struct MaxLengthDecorator {
     pub value: usize,
@@ -50,8 +58,12 @@ impl LibInfo for MaxLengthDecorator {
 
 impl TypeSpecDecorator for MaxLengthDecorator {}
 
+impl ScalarDecorator for MaxLengthDecorator {}
+
 impl RecordPropertyDecorator for MaxLengthDecorator {}
 
+impl ParameterDecorator for MaxLengthDecorator {}
+
 #[derive(Debug)]
 pub struct PatternDecorator {
     pub value: String,
@@ -59,11 +71,7 @@ pub struct PatternDecorator {
 
 impl Display for PatternDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "@pattern({})",
-            string_literal(&self.value.replace("\\", "\\\\"))
-        )
+        write!(f, "@pattern({})", string_literal(&self.value))
     }
 }
 
@@ -78,16 +86,26 @@ impl LibInfo for PatternDecorator {
 
 impl TypeSpecDecorator for PatternDecorator {}
 
+impl ScalarDecorator for PatternDecorator {}
+
 impl RecordPropertyDecorator for PatternDecorator {}
 
+impl ParameterDecorator for PatternDecorator {}
+
+// NOTE: OpenAPI の exclusiveMinimum が true の場合は @minValueExclusive にする
 #[derive(Debug)]
 pub struct MinimumDecorator {
-    pub value: f64,
+    pub value: String,
+    pub exclusive: bool,
 }
 
 impl Display for MinimumDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "@minimum({})", self.value)
+        if self.exclusive {
+            write!(f, "@minValueExclusive({})", self.value)
+        } else {
+            write!(f, "@minValue({})", self.value)
+        }
     }
 }
 
@@ -102,16 +120,24 @@ impl LibInfo for MinimumDecorator {
 
 impl TypeSpecDecorator for MinimumDecorator {}
 
+impl ScalarDecorator for MinimumDecorator {}
+
 impl RecordPropertyDecorator for MinimumDecorator {}
 
+// NOTE: OpenAPI の exclusiveMaximum が true の場合は @maxValueExclusive にする
 #[derive(Debug)]
 pub struct MaximumDecorator {
-    pub value: f64,
+    pub value: String,
+    pub exclusive: bool,
 }
 
 impl Display for MaximumDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "@maximum({})", self.value)
+        if self.exclusive {
+            write!(f, "@maxValueExclusive({})", self.value)
+        } else {
+            write!(f, "@maxValue({})", self.value)
+        }
     }
 }
 
@@ -126,6 +152,8 @@ impl LibInfo for MaximumDecorator {
 
 impl TypeSpecDecorator for MaximumDecorator {}
 
+impl ScalarDecorator for MaximumDecorator {}
+
 impl RecordPropertyDecorator for MaximumDecorator {}
 
 #[derive(Debug)]
@@ -135,7 +163,7 @@ pub struct FormatDecorator {
 
 impl Display for FormatDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "@format({})", self.value)
+        write!(f, "@format({})", string_literal(&self.value))
     }
 }
 
@@ -150,22 +178,154 @@ impl LibInfo for FormatDecorator {
 
 impl TypeSpecDecorator for FormatDecorator {}
 
+impl ScalarDecorator for FormatDecorator {}
+
 impl RecordPropertyDecorator for FormatDecorator {}
 
+impl ParameterDecorator for FormatDecorator {}
+
+#[derive(Debug)]
+pub struct SecretDecorator;
+
+impl Display for SecretDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@secret")
+    }
+}
+
+impl LibInfo for SecretDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        None
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl TypeSpecDecorator for SecretDecorator {}
+
+impl ScalarDecorator for SecretDecorator {}
+
+impl RecordPropertyDecorator for SecretDecorator {}
+
+impl ParameterDecorator for SecretDecorator {}
+
+#[derive(Debug)]
+pub struct EncodeDecorator {
+    pub value: String,
+}
+
+impl Display for EncodeDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@encode({})", self.value)
+    }
+}
+
+impl LibInfo for EncodeDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        None
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl TypeSpecDecorator for EncodeDecorator {}
+
+impl ScalarDecorator for EncodeDecorator {}
+
+impl ParameterDecorator for EncodeDecorator {}
+
+impl RecordPropertyDecorator for EncodeDecorator {}
+
+#[derive(Debug)]
+pub struct FriendlyNameDecorator {
+    pub value: String,
+}
+
+impl Display for FriendlyNameDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@friendlyName({})", string_literal(&self.value))
+    }
+}
+
+impl LibInfo for FriendlyNameDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        None
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl TypeSpecDecorator for FriendlyNameDecorator {}
+
+impl ModelDecorator for FriendlyNameDecorator {}
+
+impl EnumDecorator for FriendlyNameDecorator {}
+
+#[derive(Debug)]
+pub struct ErrorDecorator;
+
+impl Display for ErrorDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@error")
+    }
+}
+
+impl LibInfo for ErrorDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        None
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl TypeSpecDecorator for ErrorDecorator {}
+
+impl ModelDecorator for ErrorDecorator {}
+
+#[derive(Debug)]
+pub struct DocDecorator {
+    pub value: String,
+}
+
+impl Display for DocDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@doc({})", string_literal(&self.value))
+    }
+}
+
+impl LibInfo for DocDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        None
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl TypeSpecDecorator for DocDecorator {}
+
+impl ScalarDecorator for DocDecorator {}
+
+impl ModelDecorator for DocDecorator {}
+
+impl RecordPropertyDecorator for DocDecorator {}
+
+impl ParameterDecorator for DocDecorator {}
+
+impl EnumMemberDecorator for DocDecorator {}
+
 #[derive(Debug)]
 pub struct ServiceDecorator {
     pub title: String,
-    pub version: String,
 }
 
 impl Display for ServiceDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "@service({{ title: {}, version: {} }})",
-            string_literal(&self.title),
-            string_literal(&self.version)
-        )
+        write!(f, "{}", print_flat(&self.to_doc()))
     }
 }
 
@@ -178,6 +338,16 @@ impl LibInfo for ServiceDecorator {
     }
 }
 
-impl TypeSpecDecorator for ServiceDecorator {}
+impl TypeSpecDecorator for ServiceDecorator {
+    fn to_doc(&self) -> Doc {
+        decorator_doc(
+            "service",
+            vec![object_doc(vec![(
+                "title".to_string(),
+                text(&string_literal(&self.title)),
+            )])],
+        )
+    }
+}
 
 impl NameSpaceDecorator for ServiceDecorator {}
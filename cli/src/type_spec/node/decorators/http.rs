@@ -1,14 +1,45 @@
 use std::fmt::Display;
 
+use crate::type_spec::node::common::string_literal;
 use crate::type_spec::node::decorators::{LibInfo, TypeSpecDecorator};
 use crate::type_spec::node::{ParameterDecorator, RecordPropertyDecorator};
+use crate::type_spec::printer::{decorator_doc, object_doc, print_flat, text, Doc};
+
+// NOTE: 名前以外のオプションがない場合は名前だけを文字列で渡す。オプションは TypeSpec 1.x の object value (#{...}) で渡す
+fn parameter_decorator_doc(
+    decorator: &str,
+    name: &Option<String>,
+    options: Vec<(&str, String)>,
+) -> Doc {
+    if options.is_empty() {
+        return decorator_doc(
+            decorator,
+            name.iter()
+                .map(|name| text(&string_literal(name)))
+                .collect(),
+        );
+    }
+
+    let options = name
+        .iter()
+        .map(|name| ("name", string_literal(name)))
+        .chain(options)
+        .map(|(key, value)| (key.to_string(), text(&value)))
+        .collect();
+    decorator_doc(decorator, vec![object_doc(options)])
+}
 
 #[derive(Debug)]
-pub struct PathDecorator;
+pub struct PathDecorator {
+    pub name: Option<String>,
+    pub style: Option<String>,
+    pub explode: Option<bool>,
+    pub allow_reserved: Option<bool>,
+}
 
 impl Display for PathDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "@path")
+        write!(f, "{}", print_flat(&self.to_doc()))
     }
 }
 
@@ -21,16 +52,73 @@ impl LibInfo for PathDecorator {
     }
 }
 
-impl TypeSpecDecorator for PathDecorator {}
+impl TypeSpecDecorator for PathDecorator {
+    fn to_doc(&self) -> Doc {
+        let mut options = vec![];
+        if let Some(allow_reserved) = self.allow_reserved {
+            options.push(("allowReserved", allow_reserved.to_string()));
+        }
+        if let Some(explode) = self.explode {
+            options.push(("explode", explode.to_string()));
+        }
+        if let Some(style) = &self.style {
+            options.push(("style", string_literal(style)));
+        }
+
+        parameter_decorator_doc("path", &self.name, options)
+    }
+}
 
 impl ParameterDecorator for PathDecorator {}
 
+impl RecordPropertyDecorator for PathDecorator {}
+
 #[derive(Debug)]
-pub struct HeaderDecorator;
+pub struct QueryDecorator {
+    pub name: Option<String>,
+    pub explode: Option<bool>,
+}
+
+impl Display for QueryDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", print_flat(&self.to_doc()))
+    }
+}
+
+impl LibInfo for QueryDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        Some("@typespec/http")
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        Some("TypeSpec.Http")
+    }
+}
+
+impl TypeSpecDecorator for QueryDecorator {
+    fn to_doc(&self) -> Doc {
+        let options = self
+            .explode
+            .iter()
+            .map(|explode| ("explode", explode.to_string()))
+            .collect();
+
+        parameter_decorator_doc("query", &self.name, options)
+    }
+}
+
+impl ParameterDecorator for QueryDecorator {}
+
+impl RecordPropertyDecorator for QueryDecorator {}
+
+#[derive(Debug)]
+pub struct HeaderDecorator {
+    pub name: Option<String>,
+    pub explode: Option<bool>,
+}
 
 impl Display for HeaderDecorator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "@header")
+        write!(f, "{}", print_flat(&self.to_doc()))
     }
 }
 
@@ -43,12 +131,52 @@ impl LibInfo for HeaderDecorator {
     }
 }
 
-impl TypeSpecDecorator for HeaderDecorator {}
+impl TypeSpecDecorator for HeaderDecorator {
+    fn to_doc(&self) -> Doc {
+        let options = self
+            .explode
+            .iter()
+            .map(|explode| ("explode", explode.to_string()))
+            .collect();
+
+        parameter_decorator_doc("header", &self.name, options)
+    }
+}
 
 impl ParameterDecorator for HeaderDecorator {}
 
 impl RecordPropertyDecorator for HeaderDecorator {}
 
+#[derive(Debug)]
+pub struct CookieDecorator {
+    pub name: Option<String>,
+}
+
+impl Display for CookieDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", print_flat(&self.to_doc()))
+    }
+}
+
+impl LibInfo for CookieDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        Some("@typespec/http")
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        Some("TypeSpec.Http")
+    }
+}
+
+impl TypeSpecDecorator for CookieDecorator {
+    fn to_doc(&self) -> Doc {
+        parameter_decorator_doc("cookie", &self.name, vec![])
+    }
+}
+
+impl ParameterDecorator for CookieDecorator {}
+
+impl RecordPropertyDecorator for CookieDecorator {}
+
 #[derive(Debug)]
 pub struct StatusCodeDecorator;
 
@@ -91,4 +219,61 @@ impl LibInfo for BodyDecorator {
 
 impl TypeSpecDecorator for BodyDecorator {}
 
+impl ParameterDecorator for BodyDecorator {}
+
 impl RecordPropertyDecorator for BodyDecorator {}
+
+#[derive(Debug)]
+pub struct MultipartBodyDecorator;
+
+impl Display for MultipartBodyDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@multipartBody")
+    }
+}
+
+impl LibInfo for MultipartBodyDecorator {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        Some("@typespec/http")
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        Some("TypeSpec.Http")
+    }
+}
+
+impl TypeSpecDecorator for MultipartBodyDecorator {}
+
+impl ParameterDecorator for MultipartBodyDecorator {}
+
+impl RecordPropertyDecorator for MultipartBodyDecorator {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parameter_decorator_options() {
+        let query = QueryDecorator {
+            name: Some("status[]".to_string()),
+            explode: Some(true),
+        };
+        assert_eq!(
+            query.to_string(),
+            "@query(#{ name: \"status[]\", explode: true })"
+        );
+
+        let path = PathDecorator {
+            name: None,
+            style: None,
+            explode: None,
+            allow_reserved: Some(true),
+        };
+        assert_eq!(path.to_string(), "@path(#{ allowReserved: true })");
+
+        let header = HeaderDecorator {
+            name: Some("X-Request-Id".to_string()),
+            explode: None,
+        };
+        assert_eq!(header.to_string(), "@header(\"X-Request-Id\")");
+    }
+}
@@ -12,9 +12,16 @@ pub use method_decorator_node::*;
 pub use openapi::*;
 pub use route_decorator_node::*;
 
+use crate::type_spec::printer::{text, Doc};
+
 pub trait LibInfo {
     fn get_lib_name(&self) -> Option<&'static str>;
     fn get_namespace(&self) -> Option<&'static str>;
 }
 
-pub trait TypeSpecDecorator: Debug + Display + LibInfo {}
+// NOTE: object の引数を持つ decorator は、収まらないときに object の中で改行するよう to_doc を実装する
+pub trait TypeSpecDecorator: Debug + Display + LibInfo + Send + Sync {
+    fn to_doc(&self) -> Doc {
+        text(&self.to_string())
+    }
+}
@@ -1,24 +1,33 @@
-use std::collections::HashMap;
 use std::fmt::Display;
 
 use crate::type_spec::node::decorators::{LibInfo, TypeSpecDecorator};
 use crate::type_spec::node::*;
-
-fn hash_map_to_string(map: &HashMap<String, String>) -> String {
-    let properties = map
-        .iter()
-        .map(|(k, v)| format!("{}: {}", k, v))
-        .collect::<Vec<String>>()
-        .join(",\n");
-
-    format!("{{\n{}\n}}", properties)
+use crate::type_spec::printer::{decorator_doc, object_doc, print_flat, text, Doc, ToDoc};
+
+// NOTE: key_order に含まれるキーは元の YAML の順に、含まれないキーは追加した順のまま後ろに並べる
+fn properties_doc(mut properties: Vec<(&str, Doc)>, key_order: &[String]) -> Doc {
+    properties.sort_by_key(|(k, _)| {
+        key_order
+            .iter()
+            .position(|key| key == k)
+            .unwrap_or(key_order.len())
+    });
+
+    object_doc(
+        properties
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
 }
 
 #[derive(Debug)]
 pub struct AdditionalInfoNode {
+    pub version: String,
     pub contact: Option<ContactNode>,
     pub license: Option<LicenseNode>,
     pub terms_of_service: Option<String>,
+    pub key_order: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -26,6 +35,7 @@ pub struct ContactNode {
     pub email: Option<String>,
     pub name: Option<String>,
     pub url: Option<String>,
+    pub key_order: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -36,56 +46,101 @@ pub struct LicenseNode {
 
 impl Display for AdditionalInfoNode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut map = HashMap::new();
+        write!(f, "{}", print_flat(&TypeSpecDecorator::to_doc(self)))
+    }
+}
 
-        if let Some(contact) = &self.contact {
-            map.insert("contact".to_string(), contact.to_string());
+impl ToDoc for ContactNode {
+    fn to_doc(&self) -> Doc {
+        let mut properties = vec![];
+
+        if let Some(name) = &self.name {
+            properties.push(("name", text(&string_literal(name))));
         }
-        if let Some(license) = &self.license {
-            map.insert("license".to_string(), license.to_string());
+        if let Some(url) = &self.url {
+            properties.push(("url", text(&string_literal(url))));
         }
-        if let Some(terms_of_service) = &self.terms_of_service {
-            map.insert(
-                "termsOfService".to_string(),
-                string_literal(&terms_of_service),
-            );
+        if let Some(email) = &self.email {
+            properties.push(("email", text(&string_literal(email))));
         }
 
-        write!(f, "@info({})", hash_map_to_string(&map))
+        properties_doc(properties, &self.key_order)
     }
 }
 
-impl Display for ContactNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut map = HashMap::new();
+impl ToDoc for LicenseNode {
+    fn to_doc(&self) -> Doc {
+        let mut properties = vec![("name", text(&string_literal(&self.name)))];
+        if let Some(url) = &self.url {
+            properties.push(("url", text(&string_literal(url))));
+        }
 
-        if let Some(email) = &self.email {
-            map.insert("email".to_string(), string_literal(&email));
+        properties_doc(properties, &[])
+    }
+}
+
+impl LibInfo for AdditionalInfoNode {
+    fn get_lib_name(&self) -> Option<&'static str> {
+        Some("@typespec/openapi")
+    }
+    fn get_namespace(&self) -> Option<&'static str> {
+        Some("TypeSpec.OpenAPI")
+    }
+}
+
+impl TypeSpecDecorator for AdditionalInfoNode {
+    fn to_doc(&self) -> Doc {
+        let mut properties = vec![("version", text(&string_literal(&self.version)))];
+
+        if let Some(contact) = &self.contact {
+            properties.push(("contact", contact.to_doc()));
         }
-        if let Some(name) = &self.name {
-            map.insert("name".to_string(), string_literal(&name));
+        if let Some(license) = &self.license {
+            properties.push(("license", license.to_doc()));
         }
-        if let Some(url) = &self.url {
-            map.insert("url".to_string(), string_literal(&url));
+        if let Some(terms_of_service) = &self.terms_of_service {
+            properties.push(("termsOfService", text(&string_literal(terms_of_service))));
         }
 
-        write!(f, "{}", hash_map_to_string(&map))
+        decorator_doc("info", vec![properties_doc(properties, &self.key_order)])
     }
 }
 
-impl Display for LicenseNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut map = HashMap::new();
-        map.insert("name".to_string(), string_literal(&self.name));
-        if let Some(url) = &self.url {
-            map.insert("url".to_string(), string_literal(url));
+impl NameSpaceDecorator for AdditionalInfoNode {}
+
+#[derive(Debug)]
+pub enum ExtensionValueNode {
+    String(String),
+    Object(Vec<(String, ExtensionValueNode)>),
+}
+
+impl ToDoc for ExtensionValueNode {
+    fn to_doc(&self) -> Doc {
+        match self {
+            ExtensionValueNode::String(s) => text(&string_literal(s)),
+            ExtensionValueNode::Object(properties) => object_doc(
+                properties
+                    .iter()
+                    .map(|(k, v)| (RecordPropertyKey::from(k).to_string(), v.to_doc()))
+                    .collect(),
+            ),
         }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtensionDecorator {
+    pub key: String,
+    pub value: ExtensionValueNode,
+}
 
-        write!(f, "{}", hash_map_to_string(&map))
+impl Display for ExtensionDecorator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", print_flat(&TypeSpecDecorator::to_doc(self)))
     }
 }
 
-impl LibInfo for AdditionalInfoNode {
+impl LibInfo for ExtensionDecorator {
     fn get_lib_name(&self) -> Option<&'static str> {
         Some("@typespec/openapi")
     }
@@ -94,6 +149,15 @@ impl LibInfo for AdditionalInfoNode {
     }
 }
 
-impl TypeSpecDecorator for AdditionalInfoNode {}
+impl TypeSpecDecorator for ExtensionDecorator {
+    fn to_doc(&self) -> Doc {
+        decorator_doc(
+            "extension",
+            vec![text(&string_literal(&self.key)), self.value.to_doc()],
+        )
+    }
+}
+
+impl OperationDecorator for ExtensionDecorator {}
 
-impl NameSpaceDecorator for AdditionalInfoNode {}
+impl ModelDecorator for ExtensionDecorator {}
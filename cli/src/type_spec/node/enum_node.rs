@@ -1,22 +1,77 @@
 use std::fmt::Display;
 
 use crate::type_spec::node::common::string_literal;
+use crate::type_spec::node::decorators::TypeSpecDecorator;
+use crate::type_spec::node::IdentifierNode;
+use crate::type_spec::printer::{
+    block_doc, decorated_doc, statements_doc, text, Doc, Member, ToDoc,
+};
 
 #[derive(Debug)]
 pub struct EnumNode {
-    pub title: String,
-    pub items: Box<Vec<String>>,
+    pub decorators: Box<Vec<Box<dyn EnumDecorator>>>,
+    pub title: IdentifierNode,
+    pub items: Box<Vec<EnumMemberNode>>,
 }
 
-impl Display for EnumNode {
+impl ToDoc for EnumNode {
+    fn to_doc(&self) -> Doc {
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            Doc::Concat(vec![
+                text(&format!("enum {} ", self.title)),
+                block_doc((!self.items.is_empty()).then(|| {
+                    statements_doc(
+                        self.items.iter().map(|item| item.to_member()).collect(),
+                        false,
+                        true,
+                    )
+                })),
+            ]),
+        )
+    }
+}
+
+pub trait EnumDecorator: TypeSpecDecorator {}
+
+#[derive(Debug)]
+pub enum EnumMemberValue {
+    String(String),
+    Integer(i64),
+}
+
+impl Display for EnumMemberValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let items = self
-            .items
-            .iter()
-            .map(|item| string_literal(item))
-            .collect::<Vec<String>>()
-            .join(",\n");
-
-        write!(f, "enum {} {{\n{}\n}}", self.title, items)
+        match self {
+            EnumMemberValue::String(s) => write!(f, "{}", string_literal(s)),
+            EnumMemberValue::Integer(i) => write!(f, "{}", i),
+        }
     }
 }
+
+#[derive(Debug)]
+pub struct EnumMemberNode {
+    pub decorators: Box<Vec<Box<dyn EnumMemberDecorator>>>,
+    pub name: IdentifierNode,
+    pub value: EnumMemberValue,
+}
+
+impl EnumMemberNode {
+    pub fn to_member(&self) -> Member {
+        Member {
+            doc: self.to_doc(),
+            is_multiline: !self.decorators.is_empty(),
+        }
+    }
+}
+
+impl ToDoc for EnumMemberNode {
+    fn to_doc(&self) -> Doc {
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            text(&format!("{}: {}", self.name, self.value)),
+        )
+    }
+}
+
+pub trait EnumMemberDecorator: TypeSpecDecorator {}
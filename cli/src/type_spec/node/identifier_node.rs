@@ -1,19 +1,124 @@
 use std::fmt::Display;
 
+// NOTE: TypeSpec の識別子のルール
+//       - 予約語や識別子に使えない名前でも backtick で囲めばそのまま使える
+//       - model などの宣言名は OpenAPI 側の名前にもなるので、使えない文字を含む場合は名前を変えて
+//         @friendlyName で元の名前を残す
+const RESERVED_KEYWORDS: [&str; 28] = [
+    "alias",
+    "const",
+    "dec",
+    "else",
+    "enum",
+    "extends",
+    "extern",
+    "false",
+    "fn",
+    "if",
+    "import",
+    "init",
+    "interface",
+    "is",
+    "model",
+    "namespace",
+    "never",
+    "op",
+    "projection",
+    "return",
+    "scalar",
+    "true",
+    "typeof",
+    "union",
+    "unknown",
+    "using",
+    "valueof",
+    "void",
+];
+
+// NOTE: TypeSpec の識別子は Unicode の文字を使えるので、ASCII 以外の文字 (ペット など) もそのまま残す
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+pub fn is_reserved_keyword(name: &str) -> bool {
+    RESERVED_KEYWORDS.contains(&name)
+}
+
+pub fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if is_identifier_start(c) => chars.all(is_identifier_char),
+        _ => false,
+    }
+}
+
+pub fn escape_identifier(name: &str) -> String {
+    if is_valid_identifier(name) && !is_reserved_keyword(name) {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('`', "\\`"))
+    }
+}
+
+pub fn sanitize_identifier(name: &str) -> String {
+    if is_valid_identifier(name) {
+        return name.to_string();
+    }
+
+    let sanitized = name
+        .split(|c: char| !is_identifier_char(c))
+        .filter(|s| !s.is_empty())
+        .enumerate()
+        .map(|(i, s)| {
+            if i == 0 {
+                return s.to_string();
+            }
+            let mut chars = s.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+
+    match sanitized.chars().next() {
+        Some(c) if is_identifier_start(c) => sanitized,
+        _ => format!("_{}", sanitized),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IdentifierNode {
     pub name: String,
+    // NOTE: 別の名前空間のモデルを参照する場合は完全修飾名で出力する
+    pub namespace: Option<String>,
+}
+
+impl IdentifierNode {
+    pub fn is_renamed(&self) -> bool {
+        !is_valid_identifier(&self.name)
+    }
 }
 
 impl From<String> for IdentifierNode {
     fn from(name: String) -> Self {
-        IdentifierNode { name }
+        IdentifierNode {
+            name,
+            namespace: None,
+        }
     }
 }
 
 impl From<&String> for IdentifierNode {
     fn from(name: &String) -> Self {
-        IdentifierNode { name: name.clone() }
+        IdentifierNode {
+            name: name.clone(),
+            namespace: None,
+        }
     }
 }
 
@@ -21,12 +126,48 @@ impl<'a> From<&'a str> for IdentifierNode {
     fn from(name: &'a str) -> Self {
         IdentifierNode {
             name: name.to_string(),
+            namespace: None,
         }
     }
 }
 
 impl Display for IdentifierNode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name)
+        let name = escape_identifier(&sanitize_identifier(&self.name));
+        match &self.namespace {
+            Some(namespace) => write!(f, "{}.{}", namespace, name),
+            None => write!(f, "{}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_identifier_node_display() {
+        assert_eq!(IdentifierNode::from("Pet").to_string(), "Pet");
+        assert_eq!(IdentifierNode::from("model").to_string(), "`model`");
+        assert_eq!(IdentifierNode::from("Pet Owner").to_string(), "PetOwner");
+        assert_eq!(IdentifierNode::from("pet.v1").to_string(), "petV1");
+        assert_eq!(IdentifierNode::from("123abc").to_string(), "_123abc");
+        assert_eq!(IdentifierNode::from("ペット").to_string(), "ペット");
+        assert_eq!(
+            IdentifierNode::from("飼い主 情報").to_string(),
+            "飼い主情報"
+        );
+        assert_eq!(
+            IdentifierNode::from("café-au-lait").to_string(),
+            "caféAuLait"
+        );
+        assert_eq!(
+            IdentifierNode {
+                name: "pet.v1".to_string(),
+                namespace: Some("PetService.Models".to_string()),
+            }
+            .to_string(),
+            "PetService.Models.petV1"
+        );
     }
 }
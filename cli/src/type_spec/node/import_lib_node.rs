@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use crate::type_spec::node::common::string_literal;
+use crate::type_spec::printer::{join, text, Doc, ToDoc};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct ImportLibNode {
@@ -12,16 +13,14 @@ pub struct ImportLibNodes {
     pub items: Box<Vec<ImportLibNode>>,
 }
 
-impl Display for ImportLibNodes {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
+impl ToDoc for ImportLibNodes {
+    fn to_doc(&self) -> Doc {
+        join(
             self.items
                 .iter()
-                .map(|node| format!("{}", node))
-                .collect::<Vec<_>>()
-                .join("\n")
+                .map(|node| text(&node.to_string()))
+                .collect(),
+            Doc::HardLine,
         )
     }
 }
@@ -30,6 +29,23 @@ impl ImportLibNode {
     pub fn new(lib_name: String) -> Self {
         ImportLibNode { lib_name }
     }
+
+    pub fn is_relative(&self) -> bool {
+        self.lib_name.starts_with('.')
+    }
+}
+
+// NOTE: ライブラリの import を相対パスの import より先に並べ、それぞれの中では名前順にする
+impl Ord for ImportLibNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.is_relative(), &self.lib_name).cmp(&(other.is_relative(), &other.lib_name))
+    }
+}
+
+impl PartialOrd for ImportLibNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl From<String> for ImportLibNode {
@@ -53,3 +69,27 @@ impl Display for ImportLibNode {
         write!(f, "import {};", string_literal(&self.lib_name))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_import_lib_node_order() {
+        let mut imports = [
+            ImportLibNode::from("./b.tsp"),
+            ImportLibNode::from("@typespec/openapi"),
+            ImportLibNode::from("../a.tsp"),
+            ImportLibNode::from("@typespec/http"),
+        ];
+        imports.sort();
+
+        assert_eq!(
+            imports
+                .iter()
+                .map(|node| node.lib_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["@typespec/http", "@typespec/openapi", "../a.tsp", "./b.tsp"]
+        );
+    }
+}
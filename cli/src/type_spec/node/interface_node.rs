@@ -1,7 +1,10 @@
-use std::fmt::{format, Debug, Display};
+use std::fmt::Debug;
 
 use crate::type_spec::node::decorators::TypeSpecDecorator;
-use crate::type_spec::node::OperationNode;
+use crate::type_spec::node::{IdentifierNode, OperationNode};
+use crate::type_spec::printer::{
+    block_doc, decorated_doc, statements_doc, text, Doc, Member, ToDoc,
+};
 
 pub trait InterfaceDecorator: TypeSpecDecorator {}
 
@@ -12,29 +15,28 @@ pub struct InterfaceNode {
     pub operations: Box<Vec<OperationNode>>,
 }
 
-impl Display for InterfaceNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = vec![];
-        if self.decorators.len() > 0 {
-            result.push(
-                self.decorators
-                    .iter()
-                    .map(|d| d.to_string())
-                    .collect::<Vec<_>>()
-                    .join("\n"),
-            );
-        }
-        result.push(format!("interface {} {{", self.name));
-        if self.operations.len() > 0 {
-            result.push(
+impl ToDoc for InterfaceNode {
+    fn to_doc(&self) -> Doc {
+        let operations = (!self.operations.is_empty()).then(|| {
+            statements_doc(
                 self.operations
                     .iter()
-                    .map(|op| format!("{}", op))
-                    .collect::<Vec<_>>()
-                    .join("\n"),
-            );
-        }
-        result.push("}".to_string());
-        write!(f, "{}", result.join("\n"))
+                    .map(|op| Member {
+                        doc: op.to_doc(),
+                        is_multiline: !op.decorators.is_empty(),
+                    })
+                    .collect(),
+                false,
+                false,
+            )
+        });
+
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            Doc::Concat(vec![
+                text(&format!("interface {} ", IdentifierNode::from(&self.name))),
+                block_doc(operations),
+            ]),
+        )
     }
 }
@@ -9,6 +9,7 @@ mod model_alias_node;
 mod model_node;
 mod namespace_node;
 mod operation_node;
+mod scalar_node;
 mod type_node;
 mod type_spec_file_node;
 mod type_spec_node;
@@ -26,7 +27,9 @@ pub use model_alias_node::*;
 pub use model_node::*;
 pub use namespace_node::*;
 pub use operation_node::*;
+pub use scalar_node::*;
 pub use type_node::*;
 pub use type_spec_file_node::*;
 pub use type_spec_node::*;
+pub use union_node::*;
 pub use using_namespace_node::*;
@@ -1,6 +1,5 @@
-use std::fmt::Display;
-
 use crate::type_spec::node::{IdentifierNode, ModelContentNode};
+use crate::type_spec::printer::{text, typed_doc, Doc, ToDoc};
 
 #[derive(Debug)]
 pub struct ModelAliasNode {
@@ -8,8 +7,12 @@ pub struct ModelAliasNode {
     pub alias_type: ModelContentNode,
 }
 
-impl Display for ModelAliasNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "alias {} = {};", self.identifier, self.alias_type)
+impl ToDoc for ModelAliasNode {
+    fn to_doc(&self) -> Doc {
+        typed_doc(
+            text(&format!("alias {} =", self.identifier)),
+            self.alias_type.to_variant_docs(),
+            ";",
+        )
     }
 }
@@ -3,19 +3,32 @@ use std::fmt::Display;
 use crate::type_spec::node::decorators::TypeSpecDecorator;
 use crate::type_spec::node::type_node::TypeNode;
 use crate::type_spec::node::*;
+use crate::type_spec::printer::{
+    block_doc, decorated_doc, join, list_doc, statements_doc, text, typed_doc, union_doc, Doc,
+    ListItem, Member, ToDoc,
+};
 
 #[derive(Debug)]
 pub struct ModelNode {
-    pub name: String,
+    pub decorators: Box<Vec<Box<dyn ModelDecorator>>>,
+    pub name: IdentifierNode,
     pub record: RecordModelNode,
 }
 
-impl Display for ModelNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "model {} {}", self.name, self.record)
+impl ToDoc for ModelNode {
+    fn to_doc(&self) -> Doc {
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            Doc::Concat(vec![
+                text(&format!("model {} ", self.name)),
+                self.record.to_doc(),
+            ]),
+        )
     }
 }
 
+pub trait ModelDecorator: TypeSpecDecorator {}
+
 #[derive(Debug)]
 pub enum ModelContentNode {
     Record(RecordModelNode),
@@ -25,33 +38,62 @@ pub enum ModelContentNode {
     Union(Vec<ModelContentNode>),
     StringLiteral(String),
     IntegerLiteral(i64),
+    NumberLiteral(f64),
+    BooleanLiteral(bool),
     Intersect(Vec<ModelContentNode>),
+    Tuple(Vec<ModelContentNode>),
+    // NOTE: HttpPart<T> のようなライブラリのテンプレートを参照する
+    Template(String, Vec<ModelContentNode>),
 }
 
-impl Display for ModelContentNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ModelContentNode {
+    // NOTE: `key?: value;` のように型が続く位置では、union を prefix の後ろで改行できるよう variant ごとに分ける
+    pub fn to_variant_docs(&self) -> Vec<Doc> {
         match self {
-            ModelContentNode::Record(record) => write!(f, "{}", record),
-            ModelContentNode::Array(array) => write!(f, "{}", array),
-            ModelContentNode::Type(t) => write!(f, "{}", t),
-            ModelContentNode::ModelRef(id) => write!(f, "{}", id),
-            ModelContentNode::Union(nodes) => {
-                let nodes = nodes
-                    .iter()
-                    .map(|n| format!("{}", n))
-                    .collect::<Vec<String>>();
+            ModelContentNode::Union(nodes) => nodes.iter().map(|n| n.to_doc()).collect(),
+            _ => vec![self.to_doc()],
+        }
+    }
+}
 
-                write!(f, "{}", nodes.join(" | "))
+impl ToDoc for ModelContentNode {
+    fn to_doc(&self) -> Doc {
+        match self {
+            ModelContentNode::Record(record) => record.to_doc(),
+            ModelContentNode::Array(array) => array.to_doc(),
+            ModelContentNode::Type(t) => text(&t.to_string()),
+            ModelContentNode::ModelRef(id) => text(&id.to_string()),
+            ModelContentNode::Union(nodes) => {
+                union_doc(nodes.iter().map(|n| n.to_doc()).collect(), false)
             }
-            ModelContentNode::StringLiteral(s) => write!(f, "{}", string_literal(s)),
-            ModelContentNode::IntegerLiteral(i) => write!(f, "{}", i),
+            ModelContentNode::StringLiteral(s) => text(&string_literal(s)),
+            ModelContentNode::IntegerLiteral(i) => text(&i.to_string()),
+            ModelContentNode::NumberLiteral(n) => text(&n.to_string()),
+            ModelContentNode::BooleanLiteral(b) => text(&b.to_string()),
             ModelContentNode::Intersect(intersect) => {
-                let nodes = intersect
-                    .iter()
-                    .map(|n| format!("{}", n))
-                    .collect::<Vec<String>>();
-
-                write!(f, "{}", nodes.join(" & "))
+                join(intersect.iter().map(|n| n.to_doc()).collect(), text(" & "))
+            }
+            ModelContentNode::Tuple(items) => list_doc(
+                "[",
+                "]",
+                items.iter().map(|n| n.to_doc().into()).collect(),
+                false,
+            ),
+            ModelContentNode::Template(name, arguments) => {
+                let mut arguments = arguments.iter().map(|n| n.to_doc()).collect::<Vec<_>>();
+                // NOTE: 引数が 1 つだけの場合は <> の中で改行しない
+                let arguments = match arguments.len() {
+                    0 => return text(name),
+                    1 => Doc::Concat(vec![text("<"), arguments.remove(0), text(">")]),
+                    _ => list_doc(
+                        "<",
+                        ">",
+                        arguments.into_iter().map(ListItem::from).collect(),
+                        false,
+                    ),
+                };
+
+                Doc::Concat(vec![text(name), arguments])
             }
         }
     }
@@ -62,15 +104,21 @@ pub struct RecordModelNode {
     pub properties: Box<Vec<RecordPropertyNode>>,
 }
 
-impl Display for RecordModelNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let properties = self
-            .properties
-            .iter()
-            .map(|p| format!("{}", p))
-            .collect::<Vec<String>>()
-            .join("\n");
-        write!(f, "{{\n{}\n}}", properties)
+impl ToDoc for RecordModelNode {
+    fn to_doc(&self) -> Doc {
+        block_doc((!self.properties.is_empty()).then(|| {
+            statements_doc(
+                self.properties
+                    .iter()
+                    .map(|p| Member {
+                        doc: p.to_doc(),
+                        is_multiline: !p.decorators.is_empty(),
+                    })
+                    .collect(),
+                false,
+                false,
+            )
+        }))
     }
 }
 
@@ -82,25 +130,18 @@ pub struct RecordPropertyNode {
     pub required: bool,
 }
 
-impl Display for RecordPropertyNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let decorators = self
-            .decorators
-            .iter()
-            .map(|d| format!("{}", d))
-            .collect::<Vec<String>>()
-            .join("\n");
+impl ToDoc for RecordPropertyNode {
+    fn to_doc(&self) -> Doc {
         let separator = if self.required { ":" } else { "?:" };
 
-        if self.decorators.is_empty() {
-            write!(f, "{}{} {};", &self.key, separator, &self.value)
-        } else {
-            write!(
-                f,
-                "{}\n{}{} {};",
-                &decorators, &self.key, separator, &self.value
-            )
-        }
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            typed_doc(
+                text(&format!("{}{}", &self.key, separator)),
+                self.value.to_variant_docs(),
+                ";",
+            ),
+        )
     }
 }
 
@@ -116,7 +157,18 @@ impl Display for RecordPropertyKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             RecordPropertyKey::Identifier(id) => write!(f, "{}", id),
-            RecordPropertyKey::String(s) => write!(f, "\"{}\"", s),
+            RecordPropertyKey::String(s) => write!(f, "{}", string_literal(s)),
+        }
+    }
+}
+
+// NOTE: 識別子として書けないプロパティ名は文字列のキーにしてそのままの名前で出力する
+impl From<&String> for RecordPropertyKey {
+    fn from(key: &String) -> Self {
+        if is_valid_identifier(key) {
+            RecordPropertyKey::Identifier(IdentifierNode::from(key))
+        } else {
+            RecordPropertyKey::String(key.clone())
         }
     }
 }
@@ -126,8 +178,20 @@ pub struct ArrayModelNode {
     pub item_type: Box<ModelContentNode>,
 }
 
-impl Display for ArrayModelNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}[]", self.item_type)
+impl ToDoc for ArrayModelNode {
+    fn to_doc(&self) -> Doc {
+        match self.item_type.as_ref() {
+            ModelContentNode::Union(nodes) => Doc::Concat(vec![
+                text("("),
+                join(nodes.iter().map(|n| n.to_doc()).collect(), text(" | ")),
+                text(")[]"),
+            ]),
+            ModelContentNode::Intersect(nodes) => Doc::Concat(vec![
+                text("("),
+                join(nodes.iter().map(|n| n.to_doc()).collect(), text(" & ")),
+                text(")[]"),
+            ]),
+            item_type => Doc::Concat(vec![item_type.to_doc(), text("[]")]),
+        }
     }
 }
@@ -1,7 +1,8 @@
-use std::fmt::{Debug, Display};
+use std::fmt::Debug;
 
 use super::TypeSpecNode;
 use crate::type_spec::node::decorators::TypeSpecDecorator;
+use crate::type_spec::printer::{block_doc, decorated_doc, statements_doc, text, Doc, ToDoc};
 
 #[derive(Debug)]
 pub struct NamespaceNode {
@@ -10,31 +11,23 @@ pub struct NamespaceNode {
     pub contents: Box<Vec<TypeSpecNode>>,
 }
 
-impl Display for NamespaceNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = vec![];
-        if self.decorators.len() > 0 {
-            result.push(
-                self.decorators
-                    .iter()
-                    .map(|d| d.to_string())
-                    .collect::<Vec<String>>()
-                    .join("\n"),
-            );
-        }
-        result.push(format!("namespace {} {{", self.name));
-        if self.contents.len() > 0 {
-            result.push(
-                self.contents
-                    .iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<String>>()
-                    .join("\n"),
-            );
-        }
-        result.push("}".to_string());
+impl ToDoc for NamespaceNode {
+    fn to_doc(&self) -> Doc {
+        let contents = (!self.contents.is_empty()).then(|| {
+            statements_doc(
+                self.contents.iter().map(|c| c.to_member()).collect(),
+                true,
+                false,
+            )
+        });
 
-        write!(f, "{}", result.join("\n"))
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            Doc::Concat(vec![
+                text(&format!("namespace {} ", self.name)),
+                block_doc(contents),
+            ]),
+        )
     }
 }
 
@@ -1,7 +1,10 @@
-use std::fmt::{Debug, Display};
+use std::fmt::Debug;
 
 use crate::type_spec::node::decorators::TypeSpecDecorator;
-use crate::type_spec::node::ModelContentNode;
+use crate::type_spec::node::{escape_identifier, ModelContentNode};
+use crate::type_spec::printer::{
+    decorated_doc, list_doc, parameter_doc, text, typed_doc, Doc, ListItem, ToDoc,
+};
 
 pub trait OperationDecorator: TypeSpecDecorator {}
 
@@ -9,37 +12,50 @@ pub trait OperationDecorator: TypeSpecDecorator {}
 pub struct OperationNode {
     pub name: String,
     pub decorators: Box<Vec<Box<dyn OperationDecorator>>>,
-    pub parameters: Box<Vec<ParameterNode>>,
+    pub parameters: Box<Vec<OperationParameterNode>>,
     pub responses: Box<Vec<ModelContentNode>>,
 }
 
-impl Display for OperationNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = vec![];
-        if self.decorators.len() > 0 {
-            result.push(
-                self.decorators
-                    .iter()
-                    .map(|d| format!("{}", d))
-                    .collect::<Vec<_>>()
-                    .join(" "),
-            );
-        }
-        let parameters = self
-            .parameters
-            .iter()
-            .map(|p| format!("{}", p))
-            .collect::<Vec<_>>()
-            .join(", ");
+impl ToDoc for OperationNode {
+    fn to_doc(&self) -> Doc {
+        let parameters = list_doc(
+            "(",
+            ")",
+            self.parameters.iter().map(|p| p.to_list_item()).collect(),
+            true,
+        );
         let responses = self
             .responses
             .iter()
-            .map(|r| format!("{}", r))
-            .collect::<Vec<_>>()
-            .join(" | ");
-        result.push(format!("op {}({}): {};", self.name, parameters, responses));
+            .flat_map(|r| r.to_variant_docs())
+            .collect();
+
+        // NOTE: operation は interface の中にだけ出力するので、tsp format と同じく op を省略する
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            typed_doc(
+                Doc::Concat(vec![text(&self.name), parameters, text(":")]),
+                responses,
+                ";",
+            ),
+        )
+    }
+}
 
-        write!(f, "{}", result.join(" "))
+#[derive(Debug)]
+pub enum OperationParameterNode {
+    Parameter(ParameterNode),
+    Spread(ModelContentNode),
+}
+
+impl OperationParameterNode {
+    fn to_list_item(&self) -> ListItem {
+        match self {
+            OperationParameterNode::Parameter(node) => node.to_list_item(),
+            OperationParameterNode::Spread(node) => {
+                Doc::Concat(vec![text("..."), node.to_doc()]).into()
+            }
+        }
     }
 }
 
@@ -48,23 +64,21 @@ pub struct ParameterNode {
     pub decorators: Box<Vec<Box<dyn ParameterDecorator>>>,
     pub name: String,
     pub type_model: ModelContentNode,
+    pub required: bool,
 }
 
-impl Display for ParameterNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = vec![];
-        if self.decorators.len() > 0 {
-            result.push(
-                self.decorators
-                    .iter()
-                    .map(|d| format!("{}", d))
-                    .collect::<Vec<_>>()
-                    .join(" "),
-            );
-        }
-        result.push(format!("{}: {}", self.name, self.type_model));
+impl ParameterNode {
+    fn to_list_item(&self) -> ListItem {
+        let separator = if self.required { ":" } else { "?:" };
 
-        write!(f, "{}", result.join(" "))
+        parameter_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            typed_doc(
+                text(&format!("{}{}", escape_identifier(&self.name), separator)),
+                self.type_model.to_variant_docs(),
+                "",
+            ),
+        )
     }
 }
 
@@ -0,0 +1,21 @@
+use crate::type_spec::node::decorators::TypeSpecDecorator;
+use crate::type_spec::node::{IdentifierNode, TypeNode};
+use crate::type_spec::printer::{decorated_doc, text, Doc, ToDoc};
+
+#[derive(Debug)]
+pub struct ScalarNode {
+    pub decorators: Box<Vec<Box<dyn ScalarDecorator>>>,
+    pub name: IdentifierNode,
+    pub base: TypeNode,
+}
+
+impl ToDoc for ScalarNode {
+    fn to_doc(&self) -> Doc {
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            text(&format!("scalar {} extends {};", self.name, self.base)),
+        )
+    }
+}
+
+pub trait ScalarDecorator: TypeSpecDecorator {}
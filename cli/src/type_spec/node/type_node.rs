@@ -9,8 +9,13 @@ pub enum TypeNode {
     Float64,
     PlainDate,
     UtcDateTime,
+    PlainTime,
+    Duration,
     Bytes,
     String,
+    Null,
+    // NOTE: 設定の type_mappings で指定された型をそのまま出力する
+    Mapped(String),
 }
 
 impl Display for TypeNode {
@@ -23,8 +28,12 @@ impl Display for TypeNode {
             TypeNode::Float64 => write!(f, "float64"),
             TypeNode::PlainDate => write!(f, "plainDate"),
             TypeNode::UtcDateTime => write!(f, "utcDateTime"),
+            TypeNode::PlainTime => write!(f, "plainTime"),
+            TypeNode::Duration => write!(f, "duration"),
             TypeNode::Bytes => write!(f, "bytes"),
             TypeNode::String => write!(f, "string"),
+            TypeNode::Null => write!(f, "null"),
+            TypeNode::Mapped(name) => write!(f, "{}", name),
         }
     }
 }
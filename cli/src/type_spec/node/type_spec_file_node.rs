@@ -1,7 +1,7 @@
-use std::fmt::Display;
 use std::path::PathBuf;
 
 use crate::type_spec::node::*;
+use crate::type_spec::printer::{format, join, statements_doc, text, Doc, PrintOptions, ToDoc};
 
 #[derive(Debug)]
 pub struct TypeSpecFileNode {
@@ -25,39 +25,39 @@ impl TypeSpecFileNode {
             contents: Box::new(contents),
         }
     }
+
+    pub fn print(&self, options: &PrintOptions) -> String {
+        format(&self.to_doc(), options)
+    }
 }
 
-impl Display for TypeSpecFileNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = vec![];
-        if self.imports.len() > 0 {
-            let imports = self
-                .imports
-                .iter()
-                .map(|i| format!("{}", i))
-                .collect::<Vec<String>>()
-                .join("\n");
-            result.push(imports);
+// NOTE: import、using、宣言の間には空行を入れる
+impl ToDoc for TypeSpecFileNode {
+    fn to_doc(&self) -> Doc {
+        let mut sections = vec![];
+        if !self.imports.is_empty() {
+            sections.push(join(
+                self.imports.iter().map(|i| text(&i.to_string())).collect(),
+                Doc::HardLine,
+            ));
         }
-        if self.namespaces.len() > 0 {
-            let namespaces = self
-                .namespaces
-                .iter()
-                .map(|u| format!("{}", u))
-                .collect::<Vec<String>>()
-                .join("\n");
-            result.push(namespaces);
+        if !self.namespaces.is_empty() {
+            sections.push(join(
+                self.namespaces
+                    .iter()
+                    .map(|u| text(&u.to_string()))
+                    .collect(),
+                Doc::HardLine,
+            ));
         }
-        if self.contents.len() > 0 {
-            let contents = self
-                .contents
-                .iter()
-                .map(|c| format!("{}", c))
-                .collect::<Vec<String>>()
-                .join("\n\n");
-            result.push(contents);
+        if !self.contents.is_empty() {
+            sections.push(statements_doc(
+                self.contents.iter().map(|c| c.to_member()).collect(),
+                true,
+                false,
+            ));
         }
 
-        write!(f, "{}", result.join("\n\n"))
+        join(sections, Doc::Concat(vec![Doc::HardLine, Doc::HardLine]))
     }
 }
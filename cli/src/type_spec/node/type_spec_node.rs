@@ -1,7 +1,6 @@
-use std::fmt::Display;
-
 use crate::type_spec::node::enum_node::EnumNode;
 use crate::type_spec::node::*;
+use crate::type_spec::printer::{Doc, Member, ToDoc};
 
 #[derive(Debug)]
 pub enum TypeSpecNode {
@@ -10,18 +9,42 @@ pub enum TypeSpecNode {
     Interface(InterfaceNode),
     Model(ModelNode),
     Enum(EnumNode),
+    Union(UnionNode),
     ModelAlias(ModelAliasNode),
+    Scalar(ScalarNode),
+}
+
+impl TypeSpecNode {
+    pub fn to_member(&self) -> Member {
+        let is_multiline = match self {
+            TypeSpecNode::Imports(_) => false,
+            TypeSpecNode::NameSpace(node) => !node.decorators.is_empty(),
+            TypeSpecNode::Interface(node) => !node.decorators.is_empty(),
+            TypeSpecNode::Model(node) => !node.decorators.is_empty(),
+            TypeSpecNode::Enum(node) => !node.decorators.is_empty(),
+            TypeSpecNode::Union(node) => !node.decorators.is_empty(),
+            TypeSpecNode::ModelAlias(_) => false,
+            TypeSpecNode::Scalar(node) => !node.decorators.is_empty(),
+        };
+
+        Member {
+            doc: self.to_doc(),
+            is_multiline,
+        }
+    }
 }
 
-impl Display for TypeSpecNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ToDoc for TypeSpecNode {
+    fn to_doc(&self) -> Doc {
         match self {
-            TypeSpecNode::Imports(node) => write!(f, "{}", node),
-            TypeSpecNode::NameSpace(node) => write!(f, "{}", node),
-            TypeSpecNode::Interface(node) => write!(f, "{}", node),
-            TypeSpecNode::Model(node) => write!(f, "{}", node),
-            TypeSpecNode::Enum(node) => write!(f, "{}", node),
-            TypeSpecNode::ModelAlias(node) => write!(f, "{}", node),
+            TypeSpecNode::Imports(node) => node.to_doc(),
+            TypeSpecNode::NameSpace(node) => node.to_doc(),
+            TypeSpecNode::Interface(node) => node.to_doc(),
+            TypeSpecNode::Model(node) => node.to_doc(),
+            TypeSpecNode::Enum(node) => node.to_doc(),
+            TypeSpecNode::Union(node) => node.to_doc(),
+            TypeSpecNode::ModelAlias(node) => node.to_doc(),
+            TypeSpecNode::Scalar(node) => node.to_doc(),
         }
     }
 }
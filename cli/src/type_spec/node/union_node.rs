@@ -1,6 +1,35 @@
-use std::fmt::Debug;
+use crate::type_spec::node::{EnumDecorator, EnumMemberNode, IdentifierNode, TypeNode};
+use crate::type_spec::printer::{
+    block_doc, decorated_doc, statements_doc, text, Doc, Member, ToDoc,
+};
 
+// NOTE: 値が増えうる enum は union にして、基底の型を最後の variant に置く
 #[derive(Debug)]
-pub struct UnionNode<T: Debug> {
-    items: Box<Vec<T>>,
+pub struct UnionNode {
+    pub decorators: Box<Vec<Box<dyn EnumDecorator>>>,
+    pub name: IdentifierNode,
+    pub variants: Box<Vec<EnumMemberNode>>,
+    pub fallback: TypeNode,
+}
+
+impl ToDoc for UnionNode {
+    fn to_doc(&self) -> Doc {
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| variant.to_member())
+            .chain(std::iter::once(Member {
+                doc: text(&self.fallback.to_string()),
+                is_multiline: false,
+            }))
+            .collect();
+
+        decorated_doc(
+            self.decorators.iter().map(|d| d.to_doc()).collect(),
+            Doc::Concat(vec![
+                text(&format!("union {} ", self.name)),
+                block_doc(Some(statements_doc(variants, false, true))),
+            ]),
+        )
+    }
 }
@@ -0,0 +1,150 @@
+use crate::compiler::CompilerEnv;
+use crate::openapi_parser::node as openapi_node;
+use crate::type_spec::node as type_spec_node;
+use crate::type_spec::node_builder::interface_node::to_pascal_case_with_hyphens;
+use crate::type_spec::node_builder::model_node::{
+    build_model_content_node, build_string_decorators, StringDecorator,
+};
+use crate::type_spec::node_builder::operation_node::{
+    build_links_extension_value_node, build_parameter_property_node,
+    build_request_body_property_nodes, build_response_property_nodes,
+};
+
+// NOTE: components の各セクションは名前空間が分かれているので、種類ごとの接尾辞を付けて区別する
+pub fn build_component_model_name(kind: &openapi_node::ComponentKind, name: &str) -> String {
+    let suffix = match kind {
+        openapi_node::ComponentKind::Parameter => "Parameter",
+        openapi_node::ComponentKind::Response => "Response",
+        openapi_node::ComponentKind::Header => "Header",
+        openapi_node::ComponentKind::RequestBody => "RequestBody",
+    };
+    let name = type_spec_node::sanitize_identifier(&to_pascal_case_with_hyphens(name));
+
+    if name.ends_with(suffix) {
+        name
+    } else {
+        format!("{}{}", name, suffix)
+    }
+}
+
+// NOTE: 別ファイルの component をそのまま参照しているだけのものは、参照先のファイルで出力される
+pub fn is_redundant_component_ref(component: &openapi_node::ComponentNode) -> bool {
+    match &component.content {
+        openapi_node::ComponentContentNode::Ref(ref_node) => {
+            build_component_model_name(&component.kind, &ref_node.component_name)
+                == build_component_model_name(&component.kind, &component.name)
+        }
+        _ => false,
+    }
+}
+
+fn build_doc_decorators(
+    description: &Option<String>,
+) -> Vec<Box<dyn type_spec_node::ModelDecorator>> {
+    description
+        .iter()
+        .map(|description| -> Box<dyn type_spec_node::ModelDecorator> {
+            Box::new(type_spec_node::decorators::DocDecorator {
+                value: description.clone(),
+            })
+        })
+        .collect()
+}
+
+// NOTE: ヘッダー名は component のキーではなく参照元のキーで決まるので、@header は参照元で付ける
+//       component には型と説明だけを残し、scalar にできない型は alias にする
+fn build_header_component_node(
+    name: type_spec_node::IdentifierNode,
+    header: &openapi_node::HeaderNode,
+) -> type_spec_node::TypeSpecNode {
+    match build_model_content_node(&header.schema) {
+        type_spec_node::ModelContentNode::Type(base) => {
+            let mut decorators: Vec<Box<dyn type_spec_node::ScalarDecorator>> = vec![];
+            if let Some(description) = &header.description {
+                decorators.push(Box::new(type_spec_node::decorators::DocDecorator {
+                    value: description.clone(),
+                }));
+            }
+            decorators.extend(
+                build_string_decorators(&header.schema)
+                    .into_iter()
+                    .map(StringDecorator::into_scalar_decorator),
+            );
+
+            type_spec_node::TypeSpecNode::Scalar(type_spec_node::ScalarNode {
+                decorators: Box::new(decorators),
+                name,
+                base,
+            })
+        }
+        alias_type => type_spec_node::TypeSpecNode::ModelAlias(type_spec_node::ModelAliasNode {
+            identifier: name,
+            alias_type,
+        }),
+    }
+}
+
+pub fn build_component_node(
+    component: &openapi_node::ComponentNode,
+    env: &CompilerEnv,
+) -> Option<type_spec_node::TypeSpecNode> {
+    let name = type_spec_node::IdentifierNode::from(build_component_model_name(
+        &component.kind,
+        &component.name,
+    ));
+    let (decorators, properties) = match &component.content {
+        openapi_node::ComponentContentNode::Ref(ref_node) => {
+            if is_redundant_component_ref(component) {
+                return None;
+            }
+            return Some(type_spec_node::TypeSpecNode::ModelAlias(
+                type_spec_node::ModelAliasNode {
+                    identifier: name,
+                    alias_type: type_spec_node::ModelContentNode::ModelRef(
+                        type_spec_node::IdentifierNode {
+                            name: build_component_model_name(
+                                &component.kind,
+                                &ref_node.component_name,
+                            ),
+                            namespace: ref_node.namespace.clone(),
+                        },
+                    ),
+                },
+            ));
+        }
+        openapi_node::ComponentContentNode::Parameter(parameter) => {
+            (vec![], vec![build_parameter_property_node(parameter)])
+        }
+        openapi_node::ComponentContentNode::Response(response) => {
+            let mut decorators = build_doc_decorators(&response.description);
+            if env.error_model_names.contains(&name.name) {
+                decorators.push(Box::new(type_spec_node::decorators::ErrorDecorator {}));
+            }
+            if let Some(value) = build_links_extension_value_node(&response.links) {
+                decorators.push(Box::new(type_spec_node::decorators::ExtensionDecorator {
+                    key: "x-links".to_string(),
+                    value,
+                }));
+            }
+
+            (decorators, build_response_property_nodes(response))
+        }
+        openapi_node::ComponentContentNode::Header(header) => {
+            return Some(build_header_component_node(name, header));
+        }
+        openapi_node::ComponentContentNode::RequestBody(request_body) => (
+            build_doc_decorators(&request_body.description),
+            build_request_body_property_nodes(request_body),
+        ),
+    };
+
+    Some(type_spec_node::TypeSpecNode::Model(
+        type_spec_node::ModelNode {
+            decorators: Box::new(decorators),
+            name,
+            record: type_spec_node::RecordModelNode {
+                properties: Box::new(properties),
+            },
+        },
+    ))
+}
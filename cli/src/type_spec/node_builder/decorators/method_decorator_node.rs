@@ -1,2 +0,0 @@
-use crate::openapi_parser::node::Operation;
-use crate::type_spec::node::decorators::{Method, MethodDecoratorNode};
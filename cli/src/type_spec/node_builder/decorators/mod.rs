@@ -1,4 +1 @@
-mod method_decorator_node;
 mod routes_decorator_node;
-
-use crate::type_spec::node::decorators::LibInfo;
@@ -1,18 +1,249 @@
+use std::collections::HashSet;
+
 use crate::openapi_parser::node as openapi_node;
 use crate::type_spec::node as type_spec_node;
 
+struct EnumSource<'a> {
+    title: Option<&'a String>,
+    values: Vec<type_spec_node::EnumMemberValue>,
+    varnames: Option<&'a Vec<String>>,
+    descriptions: Option<&'a Vec<String>>,
+}
+
+impl<'a> EnumSource<'a> {
+    fn from_data_model_node(node: &'a openapi_node::DataModelNode) -> Option<Self> {
+        match node {
+            openapi_node::DataModelNode::String(string_node) => {
+                string_node.string_enum.as_ref().map(|values| EnumSource {
+                    title: string_node.title.as_ref(),
+                    values: values
+                        .iter()
+                        .map(|v| type_spec_node::EnumMemberValue::String(v.clone()))
+                        .collect(),
+                    varnames: string_node.x_enum_varnames.as_ref(),
+                    descriptions: string_node.x_enum_descriptions.as_ref(),
+                })
+            }
+            openapi_node::DataModelNode::Integer(integer_node) => {
+                integer_node.integer_enum.as_ref().map(|values| EnumSource {
+                    title: integer_node.title.as_ref(),
+                    values: values
+                        .iter()
+                        .map(|v| type_spec_node::EnumMemberValue::Integer(*v))
+                        .collect(),
+                    varnames: integer_node.x_enum_varnames.as_ref(),
+                    descriptions: integer_node.x_enum_descriptions.as_ref(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn is_enum_data_model_node(node: &openapi_node::DataModelNode) -> bool {
+    EnumSource::from_data_model_node(node).is_some()
+}
+
+fn build_member_name(value: &type_spec_node::EnumMemberValue) -> String {
+    match value {
+        type_spec_node::EnumMemberValue::String(s) => type_spec_node::sanitize_identifier(s),
+        type_spec_node::EnumMemberValue::Integer(i) if *i < 0 => format!("ValueMinus{}", -i),
+        type_spec_node::EnumMemberValue::Integer(i) => format!("Value{}", i),
+    }
+}
+
+fn build_member_nodes(source: EnumSource) -> Vec<type_spec_node::EnumMemberNode> {
+    let mut names = HashSet::new();
+
+    source
+        .values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let name = source
+                .varnames
+                .and_then(|varnames| varnames.get(i))
+                .map(|name| type_spec_node::sanitize_identifier(name))
+                .unwrap_or_else(|| build_member_name(&value));
+            // NOTE: 変換後の名前が重複した場合は連番を付けて区別する
+            let name = (1..)
+                .map(|n| {
+                    if n == 1 {
+                        name.clone()
+                    } else {
+                        format!("{}{}", name, n)
+                    }
+                })
+                .find(|name| !names.contains(name))
+                .unwrap();
+            names.insert(name.clone());
+
+            let mut decorators: Vec<Box<dyn type_spec_node::EnumMemberDecorator>> = vec![];
+            if let Some(description) = source.descriptions.and_then(|d| d.get(i)) {
+                decorators.push(Box::new(type_spec_node::decorators::DocDecorator {
+                    value: description.clone(),
+                }));
+            }
+
+            type_spec_node::EnumMemberNode {
+                decorators: Box::new(decorators),
+                name: type_spec_node::IdentifierNode::from(name),
+                value,
+            }
+        })
+        .collect()
+}
+
+fn build_title(
+    source: &EnumSource,
+    current_file_name: &str,
+) -> (
+    type_spec_node::IdentifierNode,
+    Vec<Box<dyn type_spec_node::EnumDecorator>>,
+) {
+    let title = type_spec_node::IdentifierNode::from(
+        source
+            .title
+            .cloned()
+            .unwrap_or(current_file_name.replace(".tsp", "")),
+    );
+
+    let mut decorators: Vec<Box<dyn type_spec_node::EnumDecorator>> = vec![];
+    if title.is_renamed() {
+        decorators.push(Box::new(
+            type_spec_node::decorators::FriendlyNameDecorator {
+                value: title.name.clone(),
+            },
+        ));
+    }
+
+    (title, decorators)
+}
+
 pub fn build_enum_node(
-    string_node: &openapi_node::StringNode,
+    data_model_node: &openapi_node::DataModelNode,
     current_file_name: &str,
 ) -> type_spec_node::EnumNode {
-    let title = string_node
-        .title
-        .to_owned()
-        .unwrap_or(current_file_name.to_string());
-    let items = string_node.string_enum.to_owned().unwrap_or(vec![]);
+    let source = EnumSource::from_data_model_node(data_model_node).expect("Invalid enum node");
+    let (title, decorators) = build_title(&source, current_file_name);
 
     type_spec_node::EnumNode {
+        decorators: Box::new(decorators),
         title,
-        items: Box::new(items),
+        items: Box::new(build_member_nodes(source)),
+    }
+}
+
+pub fn build_open_enum_node(
+    data_model_node: &openapi_node::DataModelNode,
+    current_file_name: &str,
+) -> type_spec_node::UnionNode {
+    let source = EnumSource::from_data_model_node(data_model_node).expect("Invalid enum node");
+    let (name, decorators) = build_title(&source, current_file_name);
+    let fallback = match data_model_node {
+        openapi_node::DataModelNode::Integer(_) => type_spec_node::TypeNode::Int32,
+        _ => type_spec_node::TypeNode::String,
+    };
+
+    type_spec_node::UnionNode {
+        decorators: Box::new(decorators),
+        name,
+        variants: Box::new(build_member_nodes(source)),
+        fallback,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use yaml_rust::YamlLoader;
+
+    use super::*;
+    use crate::type_spec::printer::{print_doc, ToDoc};
+
+    fn load_node(yaml: &str) -> openapi_node::DataModelNode {
+        let hash = YamlLoader::load_from_str(yaml).unwrap()[0]
+            .as_hash()
+            .unwrap()
+            .clone();
+        openapi_node::build_data_model_node(&hash, None).unwrap()
+    }
+
+    #[test]
+    fn test_build_integer_enum_node() {
+        let node = load_node("type: integer\nenum: [1, -2, 3]\n");
+
+        assert_eq!(
+            print_doc(&build_enum_node(&node, "Priority.tsp").to_doc(), 2, 80),
+            "enum Priority {\n  Value1: 1,\n  ValueMinus2: -2,\n  Value3: 3,\n}"
+        );
+        assert_eq!(
+            print_doc(&build_open_enum_node(&node, "Priority.tsp").to_doc(), 2, 80),
+            "union Priority {\n  Value1: 1,\n  ValueMinus2: -2,\n  Value3: 3,\n  int32,\n}"
+        );
+    }
+
+    #[test]
+    fn test_build_enum_node_with_extensions() {
+        let node = load_node(
+            "title: Status
+type: string
+enum: [active, in-active, in active, 1st]
+x-enum-varnames: [Active]
+x-enum-descriptions: [currently active, no longer active]
+",
+        );
+
+        // NOTE: x-enum-varnames が足りない member は値から名前を作り、重なった名前には連番を付ける
+        assert_eq!(
+            print_doc(&build_enum_node(&node, "status.tsp").to_doc(), 2, 80),
+            "enum Status {
+  @doc(\"currently active\")
+  Active: \"active\",
+
+  @doc(\"no longer active\")
+  inActive: \"in-active\",
+
+  inActive2: \"in active\",
+  _1st: \"1st\",
+}"
+        );
+        assert_eq!(
+            print_doc(&build_open_enum_node(&node, "status.tsp").to_doc(), 2, 80),
+            "union Status {
+  @doc(\"currently active\")
+  Active: \"active\",
+
+  @doc(\"no longer active\")
+  inActive: \"in-active\",
+
+  inActive2: \"in active\",
+  _1st: \"1st\",
+  string,
+}"
+        );
+    }
+
+    #[test]
+    fn test_build_integer_enum_node_with_extensions() {
+        let node = load_node(
+            "type: integer
+enum: [1, 2, 3]
+x-enum-varnames: [Low, High, high]
+x-enum-descriptions: [low priority, high priority]
+",
+        );
+
+        assert_eq!(
+            print_doc(&build_enum_node(&node, "Priority.tsp").to_doc(), 2, 80),
+            "enum Priority {
+  @doc(\"low priority\")
+  Low: 1,
+
+  @doc(\"high priority\")
+  High: 2,
+
+  high: 3,
+}"
+        );
     }
 }
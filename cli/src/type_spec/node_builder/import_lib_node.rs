@@ -1,9 +1,7 @@
 use crate::openapi_parser::node as openapi_node;
 use crate::type_spec::node as type_spec_node;
 
-pub fn build_import_lib_nodes(
-    paths: &Vec<openapi_node::PathNode>,
-) -> type_spec_node::ImportLibNodes {
+pub fn build_import_lib_nodes(paths: &[openapi_node::PathNode]) -> type_spec_node::ImportLibNodes {
     let paths = paths
         .iter()
         .map(|path_node| {
@@ -7,55 +7,255 @@ use crate::openapi_parser::node as openapi_node;
 use crate::type_spec::node as type_spec_node;
 use crate::type_spec::node_builder::operation_node::{
     build_import_lib_nodes_from_operation_node, build_operation_node,
+    build_using_namespace_nodes_from_operation_node, get_operation_name, to_lower_camel_case,
 };
 
-fn to_pascal_case(s: &str) -> String {
+pub fn to_pascal_case(s: &str) -> String {
     let s = s.replace("{", "").replace("}", "");
     let re = Regex::new(r"(^((\w))|(_\w))").unwrap();
     re.replace_all(s.as_str(), |caps: &regex::Captures| {
         let s = &caps[1];
-        if s.starts_with("_") {
-            s[1..].to_uppercase()
-        } else {
-            s.to_uppercase()
-        }
+        s.strip_prefix('_').unwrap_or(s).to_uppercase()
     })
     .to_string()
 }
 
+// NOTE: interface の名前は従来どおり _ だけで区切る。新しく付ける名前 (component や切り出した model) は - も区切りにする
+pub fn to_pascal_case_with_hyphens(s: &str) -> String {
+    to_pascal_case(&s.replace('-', "_"))
+}
+
+// NOTE: callback の runtime expression は path parameter に置き換えて route のテンプレートにする
+fn build_callback_route(expression: &str) -> (String, Vec<(String, String)>) {
+    let re = Regex::new(r"\{([^}]+)\}").unwrap();
+    let mut parameters: Vec<(String, String)> = vec![];
+    let route = re
+        .replace_all(expression, |caps: &regex::Captures| {
+            let expression = caps[1].to_string();
+            let name = expression
+                .rsplit(['/', '.', '#', '$'])
+                .find(|s| !s.is_empty())
+                .map(to_lower_camel_case)
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "url".to_string());
+            let count = parameters
+                .iter()
+                .filter(|(n, _)| n.starts_with(&name))
+                .count();
+            let name = if count == 0 {
+                name
+            } else {
+                format!("{}{}", name, count + 1)
+            };
+            parameters.push((name.clone(), expression));
+
+            format!("{{{}}}", name)
+        })
+        .to_string();
+
+    (route, parameters)
+}
+
+fn build_callback_parameter_node(
+    name: &str,
+    expression: &str,
+) -> type_spec_node::OperationParameterNode {
+    let decorators: Vec<Box<dyn type_spec_node::ParameterDecorator>> = vec![
+        Box::new(type_spec_node::decorators::DocDecorator {
+            value: expression.to_string(),
+        }),
+        Box::new(type_spec_node::decorators::PathDecorator {
+            name: None,
+            style: None,
+            explode: None,
+            allow_reserved: Some(true),
+        }),
+    ];
+
+    type_spec_node::OperationParameterNode::Parameter(type_spec_node::ParameterNode {
+        decorators: Box::new(decorators),
+        name: name.to_string(),
+        type_model: type_spec_node::ModelContentNode::Type(type_spec_node::TypeNode::String),
+        required: true,
+    })
+}
+
+fn build_callback_interface_nodes(
+    operation: &openapi_node::OperationNode,
+    base_name: &str,
+    env: &CompilerEnv,
+) -> Vec<type_spec_node::InterfaceNode> {
+    let base_name = match &operation.operation_id {
+        Some(operation_id) => to_pascal_case(operation_id),
+        None => format!(
+            "{}{}",
+            base_name,
+            to_pascal_case(get_operation_name(&operation.op, env))
+        ),
+    };
+    let mut interfaces = vec![];
+
+    for callback in operation.callbacks.iter() {
+        let (route, parameters) = build_callback_route(&callback.expression);
+        let name = format!("{}{}Callback", base_name, to_pascal_case(&callback.name));
+        let operations = callback
+            .operations
+            .iter()
+            .map(|op| {
+                let mut node = build_operation_node(op, env);
+                let callback_parameters = parameters
+                    .iter()
+                    .map(|(name, expression)| build_callback_parameter_node(name, expression));
+                node.parameters.splice(0..0, callback_parameters);
+                node
+            })
+            .collect::<Vec<_>>();
+
+        interfaces.push(type_spec_node::InterfaceNode {
+            name: name.clone(),
+            decorators: Box::new(vec![Box::new(
+                type_spec_node::decorators::RouteDecoratorNode { path: route },
+            )]),
+            operations: Box::new(operations),
+        });
+        callback.operations.iter().for_each(|op| {
+            interfaces.extend(build_callback_interface_nodes(op, &name, env));
+        });
+    }
+
+    interfaces
+}
+
+// NOTE: webhook と callback は通常の API と混ざらないように、それぞれ入れ子の namespace にまとめる
+fn build_nested_namespace_node(
+    name: &str,
+    interfaces: Vec<type_spec_node::InterfaceNode>,
+) -> type_spec_node::NamespaceNode {
+    type_spec_node::NamespaceNode {
+        decorators: Box::new(vec![]),
+        name: name.to_string(),
+        contents: Box::new(
+            interfaces
+                .into_iter()
+                .map(type_spec_node::TypeSpecNode::Interface)
+                .collect(),
+        ),
+    }
+}
+
+// NOTE: webhook はパスを持たないので、名前を route にして他の webhook と区別する
+fn build_webhook_interface_node(
+    name: &str,
+    operations: &[&openapi_node::OperationNode],
+    env: &CompilerEnv,
+) -> type_spec_node::InterfaceNode {
+    type_spec_node::InterfaceNode {
+        name: format!("{}Webhook", to_pascal_case(name)),
+        decorators: Box::new(vec![Box::new(
+            type_spec_node::decorators::RouteDecoratorNode {
+                path: format!("/{}", name),
+            },
+        )]),
+        operations: Box::new(
+            operations
+                .iter()
+                .map(|op| build_operation_node(op, env))
+                .collect(),
+        ),
+    }
+}
+
+pub fn build_webhook_namespace_node(
+    webhook: &openapi_node::WebhookNode,
+    env: &CompilerEnv,
+) -> type_spec_node::NamespaceNode {
+    let operations = webhook.operations.iter().collect::<Vec<_>>();
+    let interface_name = format!("{}Webhook", to_pascal_case(&webhook.name));
+    let mut contents = vec![type_spec_node::TypeSpecNode::NameSpace(
+        build_nested_namespace_node(
+            "Webhooks",
+            vec![build_webhook_interface_node(
+                &webhook.name,
+                &operations,
+                env,
+            )],
+        ),
+    )];
+    let callbacks = operations
+        .iter()
+        .flat_map(|op| build_callback_interface_nodes(op, &interface_name, env))
+        .collect::<Vec<_>>();
+    if !callbacks.is_empty() {
+        contents.push(type_spec_node::TypeSpecNode::NameSpace(
+            build_nested_namespace_node("Callbacks", callbacks),
+        ));
+    }
+
+    type_spec_node::NamespaceNode {
+        decorators: Box::new(vec![]),
+        name: env.namespace.clone(),
+        contents: Box::new(contents),
+    }
+}
+
 pub fn build_wrapped_interface_node(
     operations: &Vec<&openapi_node::OperationNode>,
     current_file_name: &str,
     env: &CompilerEnv,
 ) -> type_spec_node::NamespaceNode {
-    let route = env
-        .path_file_map
-        .get(&current_file_name.replace(".tsp", ".yaml"))
-        .expect("Failed to get route");
-    let interface_name = route.as_str()[1..]
-        .split("/")
-        .map(|s| to_pascal_case(s))
-        .collect::<Vec<_>>()
-        .join("");
-    let decorators: Vec<Box<dyn type_spec_node::InterfaceDecorator>> =
-        vec![Box::new(type_spec_node::decorators::RouteDecoratorNode {
-            path: route.to_owned(),
-        })];
-    let operations = operations
-        .iter()
-        .map(|op| build_operation_node(op))
-        .collect::<Vec<_>>();
+    let yaml_file_name = current_file_name.replace(".tsp", ".yaml");
+    let mut contents = vec![];
+    let interface_name = if let Some(route) = env.path_file_map.get(&yaml_file_name) {
+        let interface_name = route.as_str()[1..]
+            .split("/")
+            .map(to_pascal_case)
+            .collect::<Vec<_>>()
+            .join("");
+        let decorators: Vec<Box<dyn type_spec_node::InterfaceDecorator>> =
+            vec![Box::new(type_spec_node::decorators::RouteDecoratorNode {
+                path: route.to_owned(),
+            })];
+        let operations = operations
+            .iter()
+            .map(|op| build_operation_node(op, env))
+            .collect::<Vec<_>>();
+        contents.push(type_spec_node::TypeSpecNode::Interface(
+            type_spec_node::InterfaceNode {
+                name: interface_name.clone(),
+                decorators: Box::new(decorators),
+                operations: Box::new(operations),
+            },
+        ));
 
-    let interface = type_spec_node::InterfaceNode {
-        name: interface_name,
-        decorators: Box::new(decorators),
-        operations: Box::new(operations),
+        interface_name
+    } else {
+        let webhook = env
+            .webhook_file_map
+            .get(&yaml_file_name)
+            .expect("Failed to get route");
+        let interface = build_webhook_interface_node(webhook, operations, env);
+        let interface_name = interface.name.clone();
+        contents.push(type_spec_node::TypeSpecNode::NameSpace(
+            build_nested_namespace_node("Webhooks", vec![interface]),
+        ));
+
+        interface_name
     };
 
+    let callbacks = operations
+        .iter()
+        .flat_map(|op| build_callback_interface_nodes(op, &interface_name, env))
+        .collect::<Vec<_>>();
+    if !callbacks.is_empty() {
+        contents.push(type_spec_node::TypeSpecNode::NameSpace(
+            build_nested_namespace_node("Callbacks", callbacks),
+        ));
+    }
+
     type_spec_node::NamespaceNode {
         decorators: Box::new(vec![]),
         name: env.namespace.clone(),
-        contents: Box::new(vec![type_spec_node::TypeSpecNode::Interface(interface)]),
+        contents: Box::new(contents),
     }
 }
 
@@ -99,5 +299,39 @@ pub fn build_using_namespace_nodes_from_interface_node(
         }
     }
 
+    interface_node.operations.iter().for_each(|operation| {
+        namespaces.extend(build_using_namespace_nodes_from_operation_node(operation))
+    });
+
     namespaces
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("list_pets"), "ListPets");
+        assert_eq!(to_pascal_case("pets-{petId}"), "Pets-petId");
+        assert_eq!(to_pascal_case_with_hyphens("x-rate-limit"), "XRateLimit");
+    }
+
+    #[test]
+    fn test_build_callback_route() {
+        assert_eq!(
+            build_callback_route("{$request.body#/callbackUrl}"),
+            (
+                "{callbackUrl}".to_string(),
+                vec![(
+                    "callbackUrl".to_string(),
+                    "$request.body#/callbackUrl".to_string()
+                )]
+            )
+        );
+        assert_eq!(
+            build_callback_route("{$request.query.url}/events/{$request.header.url}").0,
+            "{url}/events/{url2}"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod component_node;
 mod decorators;
 mod enum_node;
 mod import_lib_node;
@@ -9,10 +10,8 @@ mod operation_node;
 mod type_spec_file_node;
 mod type_spec_node;
 
-use enum_node::*;
-use interface_node::*;
-use model_alias_node::*;
+pub use component_node::{build_component_model_name, is_redundant_component_ref};
+pub use interface_node::{to_pascal_case, to_pascal_case_with_hyphens};
 use namespace_node::*;
-use operation_node::*;
 pub use type_spec_file_node::*;
 use type_spec_node::*;
@@ -7,10 +7,11 @@ use crate::type_spec::node::{self as type_spec_node, IdentifierNode};
 
 pub fn build_model_alias_node(
     data_mode_node: &openapi_node::DataModelNode,
+    env: &CompilerEnv,
 ) -> type_spec_node::ModelAliasNode {
     let title = data_mode_node
         .title()
-        .unwrap_or("UnknownModelAlias".to_string());
+        .unwrap_or_else(|| env.options.naming.fallback_model_alias.clone());
     let alias_type = build_model_content_node(data_mode_node);
 
     type_spec_node::ModelAliasNode {
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use pathdiff::diff_paths;
 
 use crate::compiler::CompilerEnv;
-use crate::openapi_parser::{build_data_model_node, node as openapi_node, ComponentRefNode};
+use crate::openapi_parser::node as openapi_node;
 use crate::type_spec::node as type_spec_node;
 use crate::type_spec::node::IdentifierNode;
 
@@ -15,29 +15,79 @@ fn build_array_node(array: &openapi_node::ArrayNode) -> type_spec_node::ModelCon
 
 fn build_union_node(one_of: &openapi_node::OneOfNode) -> type_spec_node::ModelContentNode {
     type_spec_node::ModelContentNode::Union(
-        one_of
-            .items
-            .iter()
-            .map(|item| build_model_content_node(item))
-            .collect(),
+        one_of.items.iter().map(build_model_content_node).collect(),
     )
 }
 
 fn build_intersection_node(all_of: &openapi_node::AllOfNode) -> type_spec_node::ModelContentNode {
     type_spec_node::ModelContentNode::Intersect(
-        all_of
-            .items
-            .iter()
-            .map(|item| build_model_content_node(item))
-            .collect(),
+        all_of.items.iter().map(build_model_content_node).collect(),
+    )
+}
+
+fn build_tuple_node(tuple: &openapi_node::TupleNode) -> type_spec_node::ModelContentNode {
+    type_spec_node::ModelContentNode::Tuple(
+        tuple.items.iter().map(build_model_content_node).collect(),
     )
 }
 
+fn build_const_node(const_node: &openapi_node::ConstNode) -> type_spec_node::ModelContentNode {
+    build_const_value_node(&const_node.value)
+}
+
+fn build_const_value_node(value: &openapi_node::ConstValue) -> type_spec_node::ModelContentNode {
+    match value {
+        openapi_node::ConstValue::String(s) => {
+            type_spec_node::ModelContentNode::StringLiteral(s.clone())
+        }
+        openapi_node::ConstValue::Integer(i) => {
+            type_spec_node::ModelContentNode::IntegerLiteral(*i)
+        }
+        openapi_node::ConstValue::Number(n) => type_spec_node::ModelContentNode::NumberLiteral(*n),
+        openapi_node::ConstValue::Boolean(b) => {
+            type_spec_node::ModelContentNode::BooleanLiteral(*b)
+        }
+        openapi_node::ConstValue::Null => build_type_node(type_spec_node::TypeNode::Null),
+        openapi_node::ConstValue::Array(items) => type_spec_node::ModelContentNode::Tuple(
+            items.iter().map(build_const_value_node).collect(),
+        ),
+        openapi_node::ConstValue::Object(entries) => {
+            type_spec_node::ModelContentNode::Record(type_spec_node::RecordModelNode {
+                properties: Box::new(
+                    entries
+                        .iter()
+                        .map(|(key, value)| type_spec_node::RecordPropertyNode {
+                            decorators: Box::new(vec![]),
+                            key: type_spec_node::RecordPropertyKey::from(key),
+                            value: build_const_value_node(value),
+                            required: true,
+                        })
+                        .collect(),
+                ),
+            })
+        }
+    }
+}
+
+fn build_nullable_node(node: type_spec_node::ModelContentNode) -> type_spec_node::ModelContentNode {
+    match node {
+        type_spec_node::ModelContentNode::Union(mut items) => {
+            items.push(build_type_node(type_spec_node::TypeNode::Null));
+            type_spec_node::ModelContentNode::Union(items)
+        }
+        node => type_spec_node::ModelContentNode::Union(vec![
+            node,
+            build_type_node(type_spec_node::TypeNode::Null),
+        ]),
+    }
+}
+
 fn build_model_ref_node(ref_node: &openapi_node::RefNode) -> type_spec_node::ModelContentNode {
     if let openapi_node::RefNode::ComponentRef(component_ref) = ref_node {
-        type_spec_node::ModelContentNode::ModelRef(IdentifierNode::from(
-            &component_ref.component_name,
-        ))
+        type_spec_node::ModelContentNode::ModelRef(IdentifierNode {
+            name: component_ref.component_name.clone(),
+            namespace: component_ref.namespace.clone(),
+        })
     } else {
         panic!("Unexpected ref node: {:?}", ref_node)
     }
@@ -50,8 +100,8 @@ fn build_array_property_node(
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
-        value: build_array_node(&value),
+        key: type_spec_node::RecordPropertyKey::from(key),
+        value: build_array_node(value),
         required,
     }
 }
@@ -63,8 +113,8 @@ fn build_object_property_node(
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
-        value: build_record_model_node(&value),
+        key: type_spec_node::RecordPropertyKey::from(key),
+        value: build_record_model_node(value),
         required,
     }
 }
@@ -76,7 +126,7 @@ fn build_union_property_node(
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
+        key: type_spec_node::RecordPropertyKey::from(key),
         value: build_union_node(value),
         required,
     }
@@ -84,7 +134,7 @@ fn build_union_property_node(
 
 fn build_string_literal_enum_property_node(
     key: &String,
-    values: &Vec<String>,
+    values: &[String],
     required: bool,
 ) -> type_spec_node::RecordPropertyNode {
     let value = type_spec_node::ModelContentNode::Union(
@@ -95,31 +145,158 @@ fn build_string_literal_enum_property_node(
     );
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
+        key: type_spec_node::RecordPropertyKey::from(key),
         value,
         required,
     }
 }
 
-fn build_formatted_string_property_node(
-    key: &String,
-    value: &openapi_node::StringFormat,
-    required: bool,
-) -> type_spec_node::RecordPropertyNode {
-    let type_value = match value {
+fn build_string_format_type_node(format: &openapi_node::StringFormat) -> type_spec_node::TypeNode {
+    match format {
         openapi_node::StringFormat::Date => type_spec_node::TypeNode::PlainDate,
         openapi_node::StringFormat::DateTime => type_spec_node::TypeNode::UtcDateTime,
+        openapi_node::StringFormat::Time => type_spec_node::TypeNode::PlainTime,
+        openapi_node::StringFormat::Duration => type_spec_node::TypeNode::Duration,
         openapi_node::StringFormat::Byte => type_spec_node::TypeNode::Bytes,
         openapi_node::StringFormat::Binary => type_spec_node::TypeNode::Bytes,
-    };
-    type_spec_node::RecordPropertyNode {
-        decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
-        value: build_type_node(type_value),
-        required,
+        _ => type_spec_node::TypeNode::String,
     }
 }
 
+// NOTE: 文字列の制約は property と parameter のどちらにも付けるので、出力先の trait に合わせて箱詰めする
+pub(crate) enum StringDecorator {
+    Format(type_spec_node::decorators::FormatDecorator),
+    Secret(type_spec_node::decorators::SecretDecorator),
+    Pattern(type_spec_node::decorators::PatternDecorator),
+    MinLength(type_spec_node::decorators::MinLengthDecorator),
+    MaxLength(type_spec_node::decorators::MaxLengthDecorator),
+}
+
+impl StringDecorator {
+    pub(crate) fn into_parameter_decorator(self) -> Box<dyn type_spec_node::ParameterDecorator> {
+        match self {
+            StringDecorator::Format(d) => Box::new(d),
+            StringDecorator::Secret(d) => Box::new(d),
+            StringDecorator::Pattern(d) => Box::new(d),
+            StringDecorator::MinLength(d) => Box::new(d),
+            StringDecorator::MaxLength(d) => Box::new(d),
+        }
+    }
+
+    pub(crate) fn into_record_property_decorator(
+        self,
+    ) -> Box<dyn type_spec_node::RecordPropertyDecorator> {
+        match self {
+            StringDecorator::Format(d) => Box::new(d),
+            StringDecorator::Secret(d) => Box::new(d),
+            StringDecorator::Pattern(d) => Box::new(d),
+            StringDecorator::MinLength(d) => Box::new(d),
+            StringDecorator::MaxLength(d) => Box::new(d),
+        }
+    }
+
+    pub(crate) fn into_scalar_decorator(self) -> Box<dyn type_spec_node::ScalarDecorator> {
+        match self {
+            StringDecorator::Format(d) => Box::new(d),
+            StringDecorator::Secret(d) => Box::new(d),
+            StringDecorator::Pattern(d) => Box::new(d),
+            StringDecorator::MinLength(d) => Box::new(d),
+            StringDecorator::MaxLength(d) => Box::new(d),
+        }
+    }
+}
+
+fn build_string_format_decorator(format: &openapi_node::StringFormat) -> Option<StringDecorator> {
+    match format {
+        openapi_node::StringFormat::Password => Some(StringDecorator::Secret(
+            type_spec_node::decorators::SecretDecorator,
+        )),
+        openapi_node::StringFormat::Uuid
+        | openapi_node::StringFormat::Email
+        | openapi_node::StringFormat::Uri
+        | openapi_node::StringFormat::Url
+        | openapi_node::StringFormat::Hostname
+        | openapi_node::StringFormat::Ipv4
+        | openapi_node::StringFormat::Ipv6
+        | openapi_node::StringFormat::Other(_) => Some(StringDecorator::Format(
+            type_spec_node::decorators::FormatDecorator {
+                value: format.to_string(),
+            },
+        )),
+        _ => None,
+    }
+}
+
+fn build_string_type_node(value: &openapi_node::StringNode) -> type_spec_node::ModelContentNode {
+    if let Some(values) = &value.string_enum {
+        return type_spec_node::ModelContentNode::Union(
+            values
+                .iter()
+                .map(|value| type_spec_node::ModelContentNode::StringLiteral(value.clone()))
+                .collect(),
+        );
+    }
+
+    if let Some(mapped_type) = &value.mapped_type {
+        return build_type_node(type_spec_node::TypeNode::Mapped(mapped_type.clone()));
+    }
+    let type_value = value
+        .format
+        .as_ref()
+        .map(build_string_format_type_node)
+        .unwrap_or(type_spec_node::TypeNode::String);
+
+    build_type_node(type_value)
+}
+
+// NOTE: model の property だけでなく、parameter や response header の string にも同じ制約を付ける
+pub(crate) fn build_string_decorators(
+    schema: &openapi_node::DataModelNode,
+) -> Vec<StringDecorator> {
+    match schema {
+        openapi_node::DataModelNode::String(value) if value.string_enum.is_none() => {
+            build_string_node_decorators(value)
+        }
+        _ => vec![],
+    }
+}
+
+fn build_string_node_decorators(value: &openapi_node::StringNode) -> Vec<StringDecorator> {
+    let mut decorators = vec![];
+    if let Some(decorator) = value
+        .format
+        .as_ref()
+        .filter(|_| value.mapped_type.is_none())
+        .and_then(build_string_format_decorator)
+    {
+        decorators.push(decorator);
+    }
+    // NOTE: plainDate や bytes などの string 以外の scalar には @pattern などを付けられない
+    if let type_spec_node::ModelContentNode::Type(type_spec_node::TypeNode::String) =
+        build_string_type_node(value)
+    {
+        if let Some(pattern) = &value.pattern {
+            decorators.push(StringDecorator::Pattern(
+                type_spec_node::decorators::PatternDecorator {
+                    value: pattern.clone(),
+                },
+            ));
+        }
+        if let Some(min_length) = &value.min_length {
+            decorators.push(StringDecorator::MinLength(
+                type_spec_node::decorators::MinLengthDecorator { value: *min_length },
+            ));
+        }
+        if let Some(max_length) = &value.max_length {
+            decorators.push(StringDecorator::MaxLength(
+                type_spec_node::decorators::MaxLengthDecorator { value: *max_length },
+            ));
+        }
+    }
+
+    decorators
+}
+
 fn build_string_property_node(
     key: &String,
     value: &openapi_node::StringNode,
@@ -128,39 +305,59 @@ fn build_string_property_node(
     if let Some(values) = &value.string_enum {
         return build_string_literal_enum_property_node(key, values, required);
     }
-    if let Some(str_format) = &value.format {
-        return build_formatted_string_property_node(key, str_format, required);
-    }
 
-    let mut decorators: Vec<Box<dyn type_spec_node::RecordPropertyDecorator>> = vec![];
+    let decorators = build_string_node_decorators(value)
+        .into_iter()
+        .map(StringDecorator::into_record_property_decorator)
+        .collect::<Vec<_>>();
 
-    if let Some(pattern) = &value.pattern {
-        decorators.push(Box::new(type_spec_node::decorators::PatternDecorator {
-            value: pattern.clone(),
-        }));
+    type_spec_node::RecordPropertyNode {
+        decorators: Box::new(decorators),
+        key: type_spec_node::RecordPropertyKey::from(key),
+        value: build_string_type_node(value),
+        required,
     }
-    if let Some(format) = &value.format {
-        decorators.push(Box::new(type_spec_node::decorators::FormatDecorator {
-            value: format.to_string(),
-        }));
+}
+
+fn build_integer_type_node(value: &openapi_node::IntegerNode) -> type_spec_node::ModelContentNode {
+    if let Some(values) = &value.integer_enum {
+        return type_spec_node::ModelContentNode::Union(
+            values
+                .iter()
+                .map(|value| type_spec_node::ModelContentNode::IntegerLiteral(*value))
+                .collect(),
+        );
+    }
+
+    match value.format {
+        Some(openapi_node::IntegerFormat::Int64) => {
+            build_type_node(type_spec_node::TypeNode::Int64)
+        }
+        _ => build_type_node(type_spec_node::TypeNode::Int32),
     }
-    if let Some(min_length) = &value.min_length {
-        decorators.push(Box::new(type_spec_node::decorators::MinLengthDecorator {
-            value: min_length.clone(),
+}
+
+fn build_numeric_bound_decorators(
+    minimum: &Option<String>,
+    exclusive_minimum: Option<bool>,
+    maximum: &Option<String>,
+    exclusive_maximum: Option<bool>,
+) -> Vec<Box<dyn type_spec_node::RecordPropertyDecorator>> {
+    let mut decorators: Vec<Box<dyn type_spec_node::RecordPropertyDecorator>> = vec![];
+    if let Some(minimum) = minimum {
+        decorators.push(Box::new(type_spec_node::decorators::MinimumDecorator {
+            value: minimum.clone(),
+            exclusive: exclusive_minimum.unwrap_or(false),
         }));
     }
-    if let Some(max_length) = &value.max_length {
-        decorators.push(Box::new(type_spec_node::decorators::MaxLengthDecorator {
-            value: max_length.clone(),
+    if let Some(maximum) = maximum {
+        decorators.push(Box::new(type_spec_node::decorators::MaximumDecorator {
+            value: maximum.clone(),
+            exclusive: exclusive_maximum.unwrap_or(false),
         }));
     }
 
-    type_spec_node::RecordPropertyNode {
-        decorators: Box::new(decorators),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
-        value: build_type_node(type_spec_node::TypeNode::String),
-        required,
-    }
+    decorators
 }
 
 fn build_integer_property_node(
@@ -168,10 +365,22 @@ fn build_integer_property_node(
     value: &openapi_node::IntegerNode,
     required: bool,
 ) -> type_spec_node::RecordPropertyNode {
+    // NOTE: enum は literal の union になるので、範囲の制約は付けない
+    let decorators = if value.integer_enum.is_some() {
+        vec![]
+    } else {
+        build_numeric_bound_decorators(
+            &value.minimum,
+            value.exclusive_minimum,
+            &value.maximum,
+            value.exclusive_maximum,
+        )
+    };
+
     type_spec_node::RecordPropertyNode {
-        decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
-        value: build_type_node(type_spec_node::TypeNode::Int32),
+        decorators: Box::new(decorators),
+        key: type_spec_node::RecordPropertyKey::from(key),
+        value: build_integer_type_node(value),
         required,
     }
 }
@@ -182,8 +391,13 @@ fn build_number_property_node(
     required: bool,
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
-        decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
+        decorators: Box::new(build_numeric_bound_decorators(
+            &value.minimum,
+            value.exclusive_minimum,
+            &value.maximum,
+            value.exclusive_maximum,
+        )),
+        key: type_spec_node::RecordPropertyKey::from(key),
         value: build_number_type_node(value),
         required,
     }
@@ -196,7 +410,7 @@ fn build_boolean_property_node(
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
+        key: type_spec_node::RecordPropertyKey::from(key),
         value: build_type_node(type_spec_node::TypeNode::Boolean),
         required,
     }
@@ -209,7 +423,7 @@ fn build_intersection_property_node(
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
+        key: type_spec_node::RecordPropertyKey::from(key),
         value: build_intersection_node(value),
         required,
     }
@@ -222,46 +436,97 @@ fn build_model_ref_property_node(
 ) -> type_spec_node::RecordPropertyNode {
     type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![]),
-        key: type_spec_node::RecordPropertyKey::Identifier(IdentifierNode::from(key)),
+        key: type_spec_node::RecordPropertyKey::from(key),
         value: build_model_ref_node(value),
         required,
     }
 }
 
-fn build_record_model_node(obj: &openapi_node::ObjectNode) -> type_spec_node::ModelContentNode {
-    let properties = obj
-        .properties
+fn build_tuple_property_node(
+    key: &String,
+    value: &openapi_node::TupleNode,
+    required: bool,
+) -> type_spec_node::RecordPropertyNode {
+    type_spec_node::RecordPropertyNode {
+        decorators: Box::new(vec![]),
+        key: type_spec_node::RecordPropertyKey::from(key),
+        value: build_tuple_node(value),
+        required,
+    }
+}
+
+fn build_const_property_node(
+    key: &String,
+    value: &openapi_node::ConstNode,
+    required: bool,
+) -> type_spec_node::RecordPropertyNode {
+    let decorators = value
+        .description
         .iter()
-        .map(|property| match &property.value {
-            openapi_node::DataModelNode::Array(array) => {
-                build_array_property_node(&property.key, array, property.required)
-            }
-            openapi_node::DataModelNode::Object(obj) => {
-                build_object_property_node(&property.key, obj, property.required)
-            }
-            openapi_node::DataModelNode::OneOf(items) => {
-                build_union_property_node(&property.key, items, property.required)
-            }
-            openapi_node::DataModelNode::String(str) => {
-                build_string_property_node(&property.key, str, property.required)
-            }
-            openapi_node::DataModelNode::Integer(int) => {
-                build_integer_property_node(&property.key, int, property.required)
-            }
-            openapi_node::DataModelNode::Number(num) => {
-                build_number_property_node(&property.key, num, property.required)
-            }
-            openapi_node::DataModelNode::Boolean(bool) => {
-                build_boolean_property_node(&property.key, bool, property.required)
-            }
-            openapi_node::DataModelNode::AllOf(items) => {
-                build_intersection_property_node(&property.key, items, property.required)
-            }
-            openapi_node::DataModelNode::Ref(ref_node) => {
-                build_model_ref_property_node(&property.key, ref_node, property.required)
-            }
-        })
-        .collect();
+        .map(
+            |description| -> Box<dyn type_spec_node::RecordPropertyDecorator> {
+                Box::new(type_spec_node::decorators::DocDecorator {
+                    value: description.clone(),
+                })
+            },
+        )
+        .collect::<Vec<_>>();
+
+    type_spec_node::RecordPropertyNode {
+        decorators: Box::new(decorators),
+        key: type_spec_node::RecordPropertyKey::from(key),
+        value: build_const_node(value),
+        required,
+    }
+}
+
+fn build_property_node(
+    property: &openapi_node::PropertyNode,
+) -> type_spec_node::RecordPropertyNode {
+    let mut node = match &property.value {
+        openapi_node::DataModelNode::Array(array) => {
+            build_array_property_node(&property.key, array, property.required)
+        }
+        openapi_node::DataModelNode::Object(obj) => {
+            build_object_property_node(&property.key, obj, property.required)
+        }
+        openapi_node::DataModelNode::OneOf(items) => {
+            build_union_property_node(&property.key, items, property.required)
+        }
+        openapi_node::DataModelNode::String(str) => {
+            build_string_property_node(&property.key, str, property.required)
+        }
+        openapi_node::DataModelNode::Integer(int) => {
+            build_integer_property_node(&property.key, int, property.required)
+        }
+        openapi_node::DataModelNode::Number(num) => {
+            build_number_property_node(&property.key, num, property.required)
+        }
+        openapi_node::DataModelNode::Boolean(bool) => {
+            build_boolean_property_node(&property.key, bool, property.required)
+        }
+        openapi_node::DataModelNode::AllOf(items) => {
+            build_intersection_property_node(&property.key, items, property.required)
+        }
+        openapi_node::DataModelNode::Ref(ref_node) => {
+            build_model_ref_property_node(&property.key, ref_node, property.required)
+        }
+        openapi_node::DataModelNode::Const(const_node) => {
+            build_const_property_node(&property.key, const_node, property.required)
+        }
+        openapi_node::DataModelNode::Tuple(tuple) => {
+            build_tuple_property_node(&property.key, tuple, property.required)
+        }
+    };
+    if property.value.nullable() {
+        node.value = build_nullable_node(node.value);
+    }
+
+    node
+}
+
+fn build_record_model_node(obj: &openapi_node::ObjectNode) -> type_spec_node::ModelContentNode {
+    let properties = obj.properties.iter().map(build_property_node).collect();
 
     type_spec_node::ModelContentNode::Record(type_spec_node::RecordModelNode {
         properties: Box::new(properties),
@@ -290,29 +555,57 @@ fn build_number_type_node(t: &openapi_node::NumberNode) -> type_spec_node::Model
 pub fn build_model_content_node(
     data_mode_node: &openapi_node::DataModelNode,
 ) -> type_spec_node::ModelContentNode {
-    match data_mode_node {
+    let node = match data_mode_node {
         openapi_node::DataModelNode::Array(array) => build_array_node(array),
         openapi_node::DataModelNode::Object(obj) => build_record_model_node(obj),
         openapi_node::DataModelNode::OneOf(one_of) => build_union_node(one_of),
-        openapi_node::DataModelNode::String(_) => build_type_node(type_spec_node::TypeNode::String),
-        openapi_node::DataModelNode::Integer(_) => build_type_node(type_spec_node::TypeNode::Int32),
+        openapi_node::DataModelNode::String(str) => build_string_type_node(str),
+        openapi_node::DataModelNode::Integer(int) => build_integer_type_node(int),
         openapi_node::DataModelNode::Number(num) => build_number_type_node(num),
         openapi_node::DataModelNode::Boolean(_) => {
             build_type_node(type_spec_node::TypeNode::Boolean)
         }
         openapi_node::DataModelNode::AllOf(all_of) => build_intersection_node(all_of),
         openapi_node::DataModelNode::Ref(ref_node) => build_model_ref_node(ref_node),
+        openapi_node::DataModelNode::Const(const_node) => build_const_node(const_node),
+        openapi_node::DataModelNode::Tuple(tuple) => build_tuple_node(tuple),
+    };
+
+    if data_mode_node.nullable() {
+        build_nullable_node(node)
+    } else {
+        node
     }
 }
 
-pub fn build_model_node(object_node: &openapi_node::ObjectNode) -> type_spec_node::ModelNode {
+pub fn build_model_node(
+    object_node: &openapi_node::ObjectNode,
+    env: &CompilerEnv,
+) -> type_spec_node::ModelNode {
     let name = object_node
         .title
         .clone()
-        .unwrap_or("UnknownModel".to_string());
+        .unwrap_or_else(|| env.options.naming.fallback_model.clone());
 
     if let type_spec_node::ModelContentNode::Record(record) = build_record_model_node(object_node) {
-        type_spec_node::ModelNode { name, record }
+        let mut decorators: Vec<Box<dyn type_spec_node::ModelDecorator>> = vec![];
+        if env.error_model_names.contains(&name) {
+            decorators.push(Box::new(type_spec_node::decorators::ErrorDecorator {}));
+        }
+        let name = IdentifierNode::from(name);
+        if name.is_renamed() {
+            decorators.push(Box::new(
+                type_spec_node::decorators::FriendlyNameDecorator {
+                    value: name.name.clone(),
+                },
+            ));
+        }
+
+        type_spec_node::ModelNode {
+            decorators: Box::new(decorators),
+            name,
+            record,
+        }
     } else {
         panic!("Invalid model node");
     }
@@ -323,7 +616,7 @@ fn get_import_path(
     current_file_path: &PathBuf,
     env: &CompilerEnv,
 ) -> Option<String> {
-    let target_path_str = env.object_file_path_map.get(&identifier_node.name);
+    let target_path_str = env.find_object_file_path(identifier_node, current_file_path);
 
     if let Some(target_path_str) = target_path_str {
         if current_file_path.eq(&PathBuf::from(target_path_str)) {
@@ -371,7 +664,9 @@ pub fn build_import_lib_nodes_from_model_content_node(
                 env,
             ))
         }),
-        type_spec_node::ModelContentNode::Intersect(intersect) => {
+        type_spec_node::ModelContentNode::Intersect(intersect)
+        | type_spec_node::ModelContentNode::Tuple(intersect)
+        | type_spec_node::ModelContentNode::Template(_, intersect) => {
             intersect.iter().for_each(|node| {
                 result.extend(build_import_lib_nodes_from_model_content_node(
                     node,
@@ -399,6 +694,13 @@ fn build_import_lib_nodes_from_record_model_node(
     let mut result = vec![];
 
     record_node.properties.iter().for_each(|property| {
+        result.extend(
+            property
+                .decorators
+                .iter()
+                .filter_map(|node| node.get_lib_name())
+                .map(type_spec_node::ImportLibNode::from),
+        );
         result.extend(build_import_lib_nodes_from_model_content_node(
             &property.value,
             current_file_path,
@@ -414,5 +716,36 @@ pub fn build_import_lib_nodes_from_model_node(
     current_file_path: &PathBuf,
     env: &CompilerEnv,
 ) -> Vec<type_spec_node::ImportLibNode> {
-    build_import_lib_nodes_from_record_model_node(&model_node.record, current_file_path, env)
+    let mut result = model_node
+        .decorators
+        .iter()
+        .filter_map(|node| node.get_lib_name())
+        .map(type_spec_node::ImportLibNode::from)
+        .collect::<Vec<_>>();
+    result.extend(build_import_lib_nodes_from_record_model_node(
+        &model_node.record,
+        current_file_path,
+        env,
+    ));
+
+    result
+}
+
+pub fn build_using_namespace_nodes_from_model_node(
+    model_node: &type_spec_node::ModelNode,
+) -> Vec<type_spec_node::UsingNamespaceNode> {
+    model_node
+        .decorators
+        .iter()
+        .filter_map(|node| node.get_namespace())
+        .chain(
+            model_node
+                .record
+                .properties
+                .iter()
+                .flat_map(|property| property.decorators.iter())
+                .filter_map(|node| node.get_namespace()),
+        )
+        .map(|namespace| type_spec_node::UsingNamespaceNode::new(namespace.to_string()))
+        .collect()
 }
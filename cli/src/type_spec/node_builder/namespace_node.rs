@@ -1,4 +1,3 @@
-use super::build_import_lib_nodes_from_type_spec_node;
 use crate::compiler::CompilerEnv;
 use crate::openapi_parser::node as openapi_node;
 use crate::type_spec::node as type_spec_node;
@@ -9,17 +8,24 @@ pub fn build_namespace_node(
 ) -> type_spec_node::NamespaceNode {
     let service = type_spec_node::decorators::ServiceDecorator {
         title: info_node.title.clone(),
-        version: info_node.version.clone(),
-    };
-    let contact = type_spec_node::decorators::ContactNode {
-        email: Some(info_node.contact.email.clone()),
-        name: Some(info_node.contact.name.clone()),
-        url: Some(info_node.contact.url.clone()),
     };
+    let contact =
+        info_node
+            .contact
+            .as_ref()
+            .map(|contact| type_spec_node::decorators::ContactNode {
+                email: contact.email.clone(),
+                name: contact.name.clone(),
+                url: contact.url.clone(),
+                key_order: contact.key_order.clone(),
+            });
+    // NOTE: TypeSpec 1.0 では @service に version を指定できないので、@info に移す
     let info = type_spec_node::decorators::AdditionalInfoNode {
-        contact: Some(contact),
+        version: info_node.version.clone(),
+        contact,
         license: None,
-        terms_of_service: Some(info_node.terms_of_service.clone()),
+        terms_of_service: info_node.terms_of_service.clone(),
+        key_order: info_node.key_order.clone(),
     };
     let decorators: Vec<Box<dyn type_spec_node::NameSpaceDecorator>> =
         vec![Box::new(service), Box::new(info)];
@@ -1,13 +1,14 @@
 use std::path::PathBuf;
 
-use log::warn;
-
 use crate::compiler::CompilerEnv;
-use crate::openapi_parser::{node as openapi_node, ParameterPosition};
+use crate::logger::warning;
+use crate::openapi_parser::{node as openapi_node, ParameterPosition, ParameterStyle};
 use crate::type_spec::node as type_spec_node;
 use crate::type_spec::node::OperationDecorator;
+use crate::type_spec::node_builder::component_node::build_component_model_name;
 use crate::type_spec::node_builder::model_node::{
     build_import_lib_nodes_from_model_content_node, build_model_content_node,
+    build_string_decorators, StringDecorator,
 };
 
 impl From<&openapi_node::Operation> for type_spec_node::decorators::MethodDecoratorNode {
@@ -34,120 +35,778 @@ impl From<&openapi_node::Operation> for type_spec_node::decorators::MethodDecora
     }
 }
 
-impl From<&openapi_node::ParameterNode> for type_spec_node::ParameterNode {
-    fn from(parameter: &openapi_node::ParameterNode) -> Self {
-        let mut decorators: Vec<Box<dyn type_spec_node::ParameterDecorator>> = vec![];
-        match parameter.position {
-            ParameterPosition::Path => {
-                decorators.push(Box::new(type_spec_node::decorators::PathDecorator {}));
-            }
-            ParameterPosition::Header => {
-                decorators.push(Box::new(type_spec_node::decorators::HeaderDecorator {}));
+pub fn to_lower_camel_case(name: &str) -> String {
+    let name = type_spec_node::sanitize_identifier(name);
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+fn build_parameter_name(name: &str) -> String {
+    if type_spec_node::is_valid_identifier(name) {
+        return name.to_string();
+    }
+
+    to_lower_camel_case(name)
+}
+
+// NOTE: @header は名前を省略するとプロパティ名を kebab-case にした名前になるので、
+//       小文字だけの名前以外は元の名前を引数で渡す
+fn build_explicit_name(
+    position: &ParameterPosition,
+    property_name: &str,
+    wire_name: &str,
+) -> Option<String> {
+    let is_implicit = match position {
+        ParameterPosition::Header => {
+            property_name == wire_name && !wire_name.chars().any(|c| c.is_ascii_uppercase())
+        }
+        _ => property_name == wire_name,
+    };
+
+    (!is_implicit).then(|| wire_name.to_string())
+}
+
+fn build_parameter_names(parameter: &openapi_node::ParameterNode) -> (String, Option<String>) {
+    let name = match parameter.position {
+        ParameterPosition::Header => to_lower_camel_case(&parameter.name),
+        _ => build_parameter_name(&parameter.name),
+    };
+    let explicit_name = build_explicit_name(&parameter.position, &name, &parameter.name);
+
+    (name, explicit_name)
+}
+
+fn is_collection_schema(schema: &openapi_node::DataModelNode) -> bool {
+    matches!(
+        schema,
+        openapi_node::DataModelNode::Array(_)
+            | openapi_node::DataModelNode::Tuple(_)
+            | openapi_node::DataModelNode::Object(_)
+    )
+}
+
+enum HttpParameterDecorator {
+    Path(type_spec_node::decorators::PathDecorator),
+    Query(type_spec_node::decorators::QueryDecorator),
+    Header(type_spec_node::decorators::HeaderDecorator),
+    Cookie(type_spec_node::decorators::CookieDecorator),
+    Encode(type_spec_node::decorators::EncodeDecorator),
+}
+
+impl HttpParameterDecorator {
+    fn into_parameter_decorator(self) -> Box<dyn type_spec_node::ParameterDecorator> {
+        match self {
+            HttpParameterDecorator::Path(d) => Box::new(d),
+            HttpParameterDecorator::Query(d) => Box::new(d),
+            HttpParameterDecorator::Header(d) => Box::new(d),
+            HttpParameterDecorator::Cookie(d) => Box::new(d),
+            HttpParameterDecorator::Encode(d) => Box::new(d),
+        }
+    }
+
+    fn into_record_property_decorator(self) -> Box<dyn type_spec_node::RecordPropertyDecorator> {
+        match self {
+            HttpParameterDecorator::Path(d) => Box::new(d),
+            HttpParameterDecorator::Query(d) => Box::new(d),
+            HttpParameterDecorator::Header(d) => Box::new(d),
+            HttpParameterDecorator::Cookie(d) => Box::new(d),
+            HttpParameterDecorator::Encode(d) => Box::new(d),
+        }
+    }
+}
+
+fn build_path_style(parameter: &openapi_node::ParameterNode) -> Option<String> {
+    match &parameter.style {
+        None | Some(ParameterStyle::Simple) => None,
+        Some(ParameterStyle::Label) => Some("label".to_string()),
+        Some(ParameterStyle::Matrix) => Some("matrix".to_string()),
+        Some(style) => {
+            warning!("unsupported path parameter style: {:?}", style);
+            None
+        }
+    }
+}
+
+fn build_http_parameter_decorators(
+    parameter: &openapi_node::ParameterNode,
+    explicit_name: Option<String>,
+) -> Vec<HttpParameterDecorator> {
+    let mut decorators = vec![];
+
+    match parameter.position {
+        ParameterPosition::Path => {
+            decorators.push(HttpParameterDecorator::Path(
+                type_spec_node::decorators::PathDecorator {
+                    name: explicit_name,
+                    style: build_path_style(parameter),
+                    explode: parameter.explode.filter(|explode| *explode),
+                    allow_reserved: parameter.allow_reserved.filter(|allow| *allow),
+                },
+            ));
+        }
+        ParameterPosition::Query => {
+            match &parameter.style {
+                Some(ParameterStyle::SpaceDelimited) => {
+                    decorators.push(HttpParameterDecorator::Encode(
+                        type_spec_node::decorators::EncodeDecorator {
+                            value: "ArrayEncoding.spaceDelimited".to_string(),
+                        },
+                    ));
+                }
+                Some(ParameterStyle::PipeDelimited) => {
+                    decorators.push(HttpParameterDecorator::Encode(
+                        type_spec_node::decorators::EncodeDecorator {
+                            value: "ArrayEncoding.pipeDelimited".to_string(),
+                        },
+                    ));
+                }
+                None | Some(ParameterStyle::Form) => {}
+                Some(style) => warning!("unsupported query parameter style: {:?}", style),
             }
-            ParameterPosition::Cookie => {
-                warn!("cookie parameter is not supported")
+            if parameter.allow_reserved == Some(true) {
+                warning!(
+                    "allowReserved is not supported for query parameter: {}",
+                    parameter.name
+                );
             }
-            _ => {}
-        }
-
-        let (name, type_model) = if parameter.name.ends_with("[]") {
-            (
-                parameter.name.replace("[]", ""),
-                build_model_content_node(&openapi_node::DataModelNode::Array(
-                    openapi_node::ArrayNode {
-                        title: None,
-                        items: Box::new(parameter.schema.clone()),
-                    },
-                )),
-            )
-        } else {
-            (
-                parameter.name.clone(),
-                build_model_content_node(&parameter.schema),
+            // NOTE: OpenAPI の form は explode: true が既定だが、TypeSpec の @query は false が既定
+            let is_form = matches!(parameter.style, None | Some(ParameterStyle::Form));
+            let explode = is_form
+                && parameter.explode.unwrap_or(true)
+                && is_collection_schema(&parameter.schema);
+            decorators.push(HttpParameterDecorator::Query(
+                type_spec_node::decorators::QueryDecorator {
+                    name: explicit_name,
+                    explode: explode.then_some(true),
+                },
+            ));
+        }
+        ParameterPosition::Header => {
+            decorators.push(HttpParameterDecorator::Header(
+                type_spec_node::decorators::HeaderDecorator {
+                    name: explicit_name,
+                    explode: parameter.explode.filter(|explode| *explode),
+                },
+            ));
+        }
+        ParameterPosition::Cookie => {
+            decorators.push(HttpParameterDecorator::Cookie(
+                type_spec_node::decorators::CookieDecorator {
+                    name: explicit_name,
+                },
+            ));
+        }
+    }
+
+    decorators
+}
+
+fn is_required_parameter(parameter: &openapi_node::ParameterNode) -> bool {
+    match parameter.position {
+        ParameterPosition::Path => true,
+        _ => parameter.required.unwrap_or(false),
+    }
+}
+
+impl From<&openapi_node::ParameterNode> for type_spec_node::ParameterNode {
+    fn from(parameter: &openapi_node::ParameterNode) -> Self {
+        let (name, explicit_name) = build_parameter_names(parameter);
+        let decorators = build_http_parameter_decorators(parameter, explicit_name)
+            .into_iter()
+            .map(HttpParameterDecorator::into_parameter_decorator)
+            .chain(
+                build_string_decorators(&parameter.schema)
+                    .into_iter()
+                    .map(StringDecorator::into_parameter_decorator),
             )
-        };
+            .collect::<Vec<_>>();
 
         type_spec_node::ParameterNode {
             decorators: Box::new(decorators),
             name,
-            type_model,
+            type_model: build_model_content_node(&parameter.schema),
+            required: is_required_parameter(parameter),
         }
     }
 }
 
-fn build_response_node(response: &openapi_node::ResponseNode) -> type_spec_node::ModelContentNode {
-    let mut properties = vec![];
+pub fn build_parameter_property_node(
+    parameter: &openapi_node::ParameterNode,
+) -> type_spec_node::RecordPropertyNode {
+    let (name, explicit_name) = build_parameter_names(parameter);
 
-    properties.push(type_spec_node::RecordPropertyNode {
+    let mut decorators: Vec<Box<dyn type_spec_node::RecordPropertyDecorator>> = vec![];
+    if let Some(description) = &parameter.description {
+        decorators.push(Box::new(type_spec_node::decorators::DocDecorator {
+            value: description.clone(),
+        }));
+    }
+    decorators.extend(
+        build_http_parameter_decorators(parameter, explicit_name)
+            .into_iter()
+            .map(HttpParameterDecorator::into_record_property_decorator),
+    );
+    decorators.extend(
+        build_string_decorators(&parameter.schema)
+            .into_iter()
+            .map(StringDecorator::into_record_property_decorator),
+    );
+
+    type_spec_node::RecordPropertyNode {
+        decorators: Box::new(decorators),
+        key: type_spec_node::RecordPropertyKey::Identifier(type_spec_node::IdentifierNode::from(
+            name,
+        )),
+        value: build_model_content_node(&parameter.schema),
+        required: is_required_parameter(parameter),
+    }
+}
+
+fn build_header_property_node(
+    name: &str,
+    header: &openapi_node::HeaderNode,
+) -> type_spec_node::RecordPropertyNode {
+    let property_name = to_lower_camel_case(name);
+    let explicit_name = build_explicit_name(&ParameterPosition::Header, &property_name, name);
+
+    let mut decorators: Vec<Box<dyn type_spec_node::RecordPropertyDecorator>> = vec![];
+    if let Some(description) = &header.description {
+        decorators.push(Box::new(type_spec_node::decorators::DocDecorator {
+            value: description.clone(),
+        }));
+    }
+    decorators.push(Box::new(type_spec_node::decorators::HeaderDecorator {
+        name: explicit_name,
+        explode: None,
+    }));
+    decorators.extend(
+        build_string_decorators(&header.schema)
+            .into_iter()
+            .map(StringDecorator::into_record_property_decorator),
+    );
+
+    type_spec_node::RecordPropertyNode {
+        decorators: Box::new(decorators),
+        key: type_spec_node::RecordPropertyKey::Identifier(type_spec_node::IdentifierNode::from(
+            property_name,
+        )),
+        value: build_model_content_node(&header.schema),
+        required: header.required.unwrap_or(false),
+    }
+}
+
+// NOTE: 4XX のような範囲は @minValue と @maxValue で表す。default はステータスコードを持たず、response model の @error で表す
+fn build_status_code_property_node(
+    status: &openapi_node::ResponseStatus,
+) -> Option<type_spec_node::RecordPropertyNode> {
+    let mut decorators: Vec<Box<dyn type_spec_node::RecordPropertyDecorator>> =
+        vec![Box::new(type_spec_node::decorators::StatusCodeDecorator {})];
+    let value = match status {
+        openapi_node::ResponseStatus::Code(code) => {
+            type_spec_node::ModelContentNode::IntegerLiteral((*code).into())
+        }
+        openapi_node::ResponseStatus::Range(class) => {
+            decorators.push(Box::new(type_spec_node::decorators::MinimumDecorator {
+                value: (class * 100).to_string(),
+                exclusive: false,
+            }));
+            decorators.push(Box::new(type_spec_node::decorators::MaximumDecorator {
+                value: (class * 100 + 99).to_string(),
+                exclusive: false,
+            }));
+            type_spec_node::ModelContentNode::Type(type_spec_node::TypeNode::Int32)
+        }
+        openapi_node::ResponseStatus::Default => return None,
+    };
+
+    Some(type_spec_node::RecordPropertyNode {
+        decorators: Box::new(decorators),
+        key: type_spec_node::RecordPropertyKey::Identifier(type_spec_node::IdentifierNode::from(
+            "statusCode",
+        )),
+        value,
+        required: true,
+    })
+}
+
+fn build_content_type_property_node(
+    content_type: &openapi_node::ContentType,
+) -> type_spec_node::RecordPropertyNode {
+    type_spec_node::RecordPropertyNode {
         decorators: Box::new(vec![Box::new(
-            type_spec_node::decorators::StatusCodeDecorator {},
+            type_spec_node::decorators::HeaderDecorator {
+                name: None,
+                explode: None,
+            },
         )]),
         key: type_spec_node::RecordPropertyKey::Identifier(type_spec_node::IdentifierNode::from(
-            "statusCode",
+            "contentType",
         )),
-        value: type_spec_node::ModelContentNode::IntegerLiteral(response.status.get_code().into()),
+        value: type_spec_node::ModelContentNode::StringLiteral(content_type.to_string()),
         required: true,
-    });
+    }
+}
+
+fn build_body_property_node(
+    schema: &openapi_node::DataModelNode,
+) -> type_spec_node::RecordPropertyNode {
+    type_spec_node::RecordPropertyNode {
+        decorators: Box::new(vec![Box::new(type_spec_node::decorators::BodyDecorator {})]),
+        key: type_spec_node::RecordPropertyKey::Identifier(type_spec_node::IdentifierNode::from(
+            "body",
+        )),
+        value: build_model_content_node(schema),
+        required: true,
+    }
+}
+
+pub fn build_response_property_nodes(
+    response: &openapi_node::ResponseObjectNode,
+) -> Vec<type_spec_node::RecordPropertyNode> {
+    let mut properties = vec![];
 
+    if let Some(status_code) = response
+        .status_code
+        .as_ref()
+        .and_then(build_status_code_property_node)
+    {
+        properties.push(status_code);
+    }
     if let Some(content_type) = &response.content_type {
-        properties.push(type_spec_node::RecordPropertyNode {
-            decorators: Box::new(vec![Box::new(
-                type_spec_node::decorators::HeaderDecorator {},
-            )]),
-            key: type_spec_node::RecordPropertyKey::Identifier(
-                type_spec_node::IdentifierNode::from("contentType"),
-            ),
-            value: type_spec_node::ModelContentNode::StringLiteral(content_type.to_string()),
-            required: true,
+        properties.push(build_content_type_property_node(content_type));
+    }
+    response
+        .headers
+        .iter()
+        .for_each(|header| match &header.header {
+            openapi_node::ReferenceOr::Item(node) => {
+                properties.push(build_header_property_node(&header.name, node));
+            }
+            openapi_node::ReferenceOr::Ref(ref_node) => {
+                panic!("Unresolved header ref: {}", ref_node.component_name)
+            }
         });
+    if let Some(body) = &response.schema {
+        properties.push(build_body_property_node(body));
     }
 
-    if let Some(body) = &response.schema {
-        properties.push(type_spec_node::RecordPropertyNode {
-            decorators: Box::new(vec![Box::new(type_spec_node::decorators::BodyDecorator {})]),
-            key: type_spec_node::RecordPropertyKey::Identifier(
-                type_spec_node::IdentifierNode::from("body"),
+    properties
+}
+
+// NOTE: application/json は TypeSpec の既定値なので content-type を明示しない
+fn is_default_content_type(content_type: &openapi_node::ContentType) -> bool {
+    matches!(content_type, openapi_node::ContentType::ApplicationJson)
+}
+
+fn is_binary_schema(schema: &openapi_node::DataModelNode) -> bool {
+    matches!(
+        schema,
+        openapi_node::DataModelNode::String(openapi_node::StringNode {
+            format: Some(openapi_node::StringFormat::Binary),
+            ..
+        })
+    )
+}
+
+// NOTE: encoding の contentType はカンマ区切りで複数指定できるので union にする
+fn build_content_types_node(content_type: &str) -> type_spec_node::ModelContentNode {
+    let mut content_types = content_type
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| type_spec_node::ModelContentNode::StringLiteral(s.to_string()))
+        .collect::<Vec<_>>();
+
+    if content_types.len() == 1 {
+        content_types.remove(0)
+    } else {
+        type_spec_node::ModelContentNode::Union(content_types)
+    }
+}
+
+fn build_file_node(content_type: Option<&String>) -> type_spec_node::ModelContentNode {
+    type_spec_node::ModelContentNode::Template(
+        "File".to_string(),
+        content_type
+            .map(|content_type| build_content_types_node(content_type))
+            .into_iter()
+            .collect(),
+    )
+}
+
+fn build_http_part_node(
+    schema: &openapi_node::DataModelNode,
+    content_type: Option<&String>,
+) -> type_spec_node::ModelContentNode {
+    let part = if is_binary_schema(schema) {
+        build_file_node(content_type)
+    } else if let Some(content_type) = content_type {
+        type_spec_node::ModelContentNode::Record(type_spec_node::RecordModelNode {
+            properties: Box::new(vec![
+                type_spec_node::RecordPropertyNode {
+                    decorators: Box::new(vec![Box::new(
+                        type_spec_node::decorators::HeaderDecorator {
+                            name: None,
+                            explode: None,
+                        },
+                    )]),
+                    key: type_spec_node::RecordPropertyKey::Identifier(
+                        type_spec_node::IdentifierNode::from("contentType"),
+                    ),
+                    value: build_content_types_node(content_type),
+                    required: true,
+                },
+                build_body_property_node(schema),
+            ]),
+        })
+    } else {
+        build_model_content_node(schema)
+    };
+
+    type_spec_node::ModelContentNode::Template("HttpPart".to_string(), vec![part])
+}
+
+// NOTE: multipart は各プロパティを HttpPart で包み、ファイルの配列は HttpPart<File>[] にする
+fn build_multipart_body_node(
+    request_body: &openapi_node::RequestBodyNode,
+) -> Option<type_spec_node::ModelContentNode> {
+    let object = match &request_body.schema {
+        openapi_node::DataModelNode::Object(object) if !object.properties.is_empty() => object,
+        _ => {
+            warning!("multipart request body without inline properties is emitted as @body");
+            return None;
+        }
+    };
+
+    let properties = object
+        .properties
+        .iter()
+        .map(|property| {
+            let content_type = request_body
+                .get_encoding(&property.key)
+                .and_then(|encoding| encoding.content_type.as_ref());
+            let value = match &property.value {
+                openapi_node::DataModelNode::Array(array) => {
+                    type_spec_node::ModelContentNode::Array(type_spec_node::ArrayModelNode {
+                        item_type: Box::new(build_http_part_node(&array.items, content_type)),
+                    })
+                }
+                value => build_http_part_node(value, content_type),
+            };
+
+            type_spec_node::RecordPropertyNode {
+                decorators: Box::new(vec![]),
+                key: type_spec_node::RecordPropertyKey::from(&property.key),
+                value,
+                required: property.required,
+            }
+        })
+        .collect();
+
+    Some(type_spec_node::ModelContentNode::Record(
+        type_spec_node::RecordModelNode {
+            properties: Box::new(properties),
+        },
+    ))
+}
+
+enum RequestBodyDecorator {
+    Header,
+    Body,
+    MultipartBody,
+}
+
+struct RequestBodyMember {
+    decorator: RequestBodyDecorator,
+    name: &'static str,
+    value: type_spec_node::ModelContentNode,
+    required: bool,
+}
+
+// NOTE: request body は operation の引数にも requestBody の component の model のプロパティにもなるので、
+//       decorator の種類だけを決めておき、それぞれのノードへの変換は呼び出す側で行う
+fn build_request_body_members(
+    request_body: &openapi_node::RequestBodyNode,
+) -> Vec<RequestBodyMember> {
+    let mut members = vec![];
+
+    if !is_default_content_type(&request_body.content_type) {
+        members.push(RequestBodyMember {
+            decorator: RequestBodyDecorator::Header,
+            name: "contentType",
+            value: type_spec_node::ModelContentNode::StringLiteral(
+                request_body.content_type.to_string(),
             ),
-            value: build_model_content_node(body),
             required: true,
         });
     }
+    let multipart_body = request_body
+        .is_multipart()
+        .then(|| build_multipart_body_node(request_body))
+        .flatten();
+    let (decorator, value) = match multipart_body {
+        Some(body) => (RequestBodyDecorator::MultipartBody, body),
+        None => (
+            RequestBodyDecorator::Body,
+            build_model_content_node(&request_body.schema),
+        ),
+    };
+    members.push(RequestBodyMember {
+        decorator,
+        name: "body",
+        value,
+        required: request_body.required,
+    });
+
+    members
+}
 
-    type_spec_node::ModelContentNode::Record(type_spec_node::RecordModelNode {
-        properties: Box::new(properties),
+pub fn build_request_body_property_nodes(
+    request_body: &openapi_node::RequestBodyNode,
+) -> Vec<type_spec_node::RecordPropertyNode> {
+    build_request_body_members(request_body)
+        .into_iter()
+        .map(|member| {
+            let decorator: Box<dyn type_spec_node::RecordPropertyDecorator> = match member.decorator
+            {
+                RequestBodyDecorator::Header => {
+                    Box::new(type_spec_node::decorators::HeaderDecorator {
+                        name: None,
+                        explode: None,
+                    })
+                }
+                RequestBodyDecorator::Body => {
+                    Box::new(type_spec_node::decorators::BodyDecorator {})
+                }
+                RequestBodyDecorator::MultipartBody => {
+                    Box::new(type_spec_node::decorators::MultipartBodyDecorator {})
+                }
+            };
+
+            type_spec_node::RecordPropertyNode {
+                decorators: Box::new(vec![decorator]),
+                key: type_spec_node::RecordPropertyKey::Identifier(
+                    type_spec_node::IdentifierNode::from(member.name),
+                ),
+                value: member.value,
+                required: member.required,
+            }
+        })
+        .collect()
+}
+
+fn build_component_ref_content_node(
+    kind: &openapi_node::ComponentKind,
+    ref_node: &openapi_node::ComponentRefNode,
+) -> type_spec_node::ModelContentNode {
+    type_spec_node::ModelContentNode::ModelRef(type_spec_node::IdentifierNode {
+        name: build_component_model_name(kind, &ref_node.component_name),
+        namespace: ref_node.namespace.clone(),
     })
 }
 
-fn get_operation_name<'a>(operation: &openapi_node::Operation) -> &'a str {
-    match operation {
-        openapi_node::Operation::Get => "list",
-        openapi_node::Operation::Post => "create",
-        openapi_node::Operation::Patch => "update",
-        openapi_node::Operation::Put => "update",
-        openapi_node::Operation::Delete => "delete",
+fn build_response_node(response: &openapi_node::ResponseNode) -> type_spec_node::ModelContentNode {
+    let status_code = build_status_code_property_node(&response.status);
+
+    match &response.response {
+        openapi_node::ReferenceOr::Item(response_object) => {
+            let mut properties = status_code.into_iter().collect::<Vec<_>>();
+            properties.extend(build_response_property_nodes(response_object));
+
+            type_spec_node::ModelContentNode::Record(type_spec_node::RecordModelNode {
+                properties: Box::new(properties),
+            })
+        }
+        openapi_node::ReferenceOr::Ref(ref_node) if response.is_named_response => {
+            build_component_ref_content_node(&openapi_node::ComponentKind::Response, ref_node)
+        }
+        // NOTE: 共通の response model にはステータスコードを持たせず、参照する側で付け足す
+        openapi_node::ReferenceOr::Ref(ref_node) => {
+            let ref_content =
+                build_component_ref_content_node(&openapi_node::ComponentKind::Response, ref_node);
+            match status_code {
+                Some(status_code) => type_spec_node::ModelContentNode::Intersect(vec![
+                    type_spec_node::ModelContentNode::Record(type_spec_node::RecordModelNode {
+                        properties: Box::new(vec![status_code]),
+                    }),
+                    ref_content,
+                ]),
+                None => ref_content,
+            }
+        }
+    }
+}
+
+fn build_operation_parameter_node(
+    parameter: &openapi_node::ReferenceOr<openapi_node::ParameterNode>,
+) -> type_spec_node::OperationParameterNode {
+    match parameter {
+        openapi_node::ReferenceOr::Item(parameter) => {
+            type_spec_node::OperationParameterNode::Parameter(type_spec_node::ParameterNode::from(
+                parameter,
+            ))
+        }
+        openapi_node::ReferenceOr::Ref(ref_node) => type_spec_node::OperationParameterNode::Spread(
+            build_component_ref_content_node(&openapi_node::ComponentKind::Parameter, ref_node),
+        ),
     }
 }
 
+fn build_request_body_parameter_nodes(
+    request_body: &openapi_node::ReferenceOr<openapi_node::RequestBodyNode>,
+) -> Vec<type_spec_node::OperationParameterNode> {
+    match request_body {
+        openapi_node::ReferenceOr::Item(request_body) => build_request_body_members(request_body)
+            .into_iter()
+            .map(|member| {
+                let decorator: Box<dyn type_spec_node::ParameterDecorator> = match member.decorator
+                {
+                    RequestBodyDecorator::Header => {
+                        Box::new(type_spec_node::decorators::HeaderDecorator {
+                            name: None,
+                            explode: None,
+                        })
+                    }
+                    RequestBodyDecorator::Body => {
+                        Box::new(type_spec_node::decorators::BodyDecorator {})
+                    }
+                    RequestBodyDecorator::MultipartBody => {
+                        Box::new(type_spec_node::decorators::MultipartBodyDecorator {})
+                    }
+                };
+
+                type_spec_node::OperationParameterNode::Parameter(type_spec_node::ParameterNode {
+                    decorators: Box::new(vec![decorator]),
+                    name: member.name.to_string(),
+                    type_model: member.value,
+                    required: member.required,
+                })
+            })
+            .collect(),
+        openapi_node::ReferenceOr::Ref(ref_node) => {
+            vec![type_spec_node::OperationParameterNode::Spread(
+                build_component_ref_content_node(
+                    &openapi_node::ComponentKind::RequestBody,
+                    ref_node,
+                ),
+            )]
+        }
+    }
+}
+
+fn build_link_value_node(
+    link: &openapi_node::LinkNode,
+) -> type_spec_node::decorators::ExtensionValueNode {
+    let string = |s: &String| type_spec_node::decorators::ExtensionValueNode::String(s.clone());
+    let mut properties = vec![];
+
+    if let Some(operation_id) = &link.operation_id {
+        properties.push(("operationId".to_string(), string(operation_id)));
+    }
+    if let Some(operation_ref) = &link.operation_ref {
+        properties.push(("operationRef".to_string(), string(operation_ref)));
+    }
+    if !link.parameters.is_empty() {
+        let parameters = link
+            .parameters
+            .iter()
+            .map(|(name, value)| (name.clone(), string(value)))
+            .collect();
+        properties.push((
+            "parameters".to_string(),
+            type_spec_node::decorators::ExtensionValueNode::Object(parameters),
+        ));
+    }
+    if let Some(request_body) = &link.request_body {
+        properties.push(("requestBody".to_string(), string(request_body)));
+    }
+    if let Some(description) = &link.description {
+        properties.push(("description".to_string(), string(description)));
+    }
+
+    type_spec_node::decorators::ExtensionValueNode::Object(properties)
+}
+
+// NOTE: TypeSpec には link に相当する仕組みがないので、x-links 拡張として情報を残す
+pub fn build_links_extension_value_node(
+    links: &[openapi_node::LinkNode],
+) -> Option<type_spec_node::decorators::ExtensionValueNode> {
+    if links.is_empty() {
+        return None;
+    }
+
+    Some(type_spec_node::decorators::ExtensionValueNode::Object(
+        links
+            .iter()
+            .map(|link| (link.name.clone(), build_link_value_node(link)))
+            .collect(),
+    ))
+}
+
+fn build_operation_links_decorator(
+    operation_node: &openapi_node::OperationNode,
+) -> Option<type_spec_node::decorators::ExtensionDecorator> {
+    let links = operation_node
+        .responses
+        .iter()
+        .filter_map(|response| match &response.response {
+            openapi_node::ReferenceOr::Item(response_object) => {
+                build_links_extension_value_node(&response_object.links)
+                    .map(|value| (response.status.to_string(), value))
+            }
+            openapi_node::ReferenceOr::Ref(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    if links.is_empty() {
+        None
+    } else {
+        Some(type_spec_node::decorators::ExtensionDecorator {
+            key: "x-links".to_string(),
+            value: type_spec_node::decorators::ExtensionValueNode::Object(links),
+        })
+    }
+}
+
+pub fn get_operation_name<'a>(
+    operation: &openapi_node::Operation,
+    env: &'a CompilerEnv,
+) -> &'a str {
+    env.options.naming.operations.get_name(operation)
+}
+
 pub fn build_operation_node(
     operation_node: &openapi_node::OperationNode,
+    env: &CompilerEnv,
 ) -> type_spec_node::OperationNode {
-    let decorators: Vec<Box<dyn OperationDecorator>> = vec![Box::new(
+    let mut decorators: Vec<Box<dyn OperationDecorator>> = vec![Box::new(
         type_spec_node::decorators::MethodDecoratorNode::from(&operation_node.op),
     )];
-    let parameters = operation_node
+    if let Some(decorator) = build_operation_links_decorator(operation_node) {
+        decorators.push(Box::new(decorator));
+    }
+    let mut parameters = operation_node
         .parameters
         .iter()
-        .map(|p| type_spec_node::ParameterNode::from(p))
+        .map(build_operation_parameter_node)
         .collect::<Vec<_>>();
+    if let Some(request_body) = &operation_node.request_body {
+        parameters.extend(build_request_body_parameter_nodes(request_body));
+    }
 
-    let responses = operation_node
-        .responses
-        .iter()
-        .map(|res| build_response_node(res))
+    // NOTE: 戻り値の union は成功のレスポンスを先に、エラーのレスポンスを後に並べる
+    let mut responses = operation_node.responses.iter().collect::<Vec<_>>();
+    responses.sort_by_key(|res| res.status.is_error());
+    let responses = responses
+        .into_iter()
+        .map(build_response_node)
         .collect::<Vec<_>>();
 
     type_spec_node::OperationNode {
-        name: get_operation_name(&operation_node.op).to_string(),
+        name: get_operation_name(&operation_node.op, env).to_string(),
         decorators: Box::new(decorators),
         parameters: Box::new(parameters),
         responses: Box::new(responses),
@@ -155,10 +814,16 @@ pub fn build_operation_node(
 }
 
 fn build_import_lib_nodes_from_parameter_node(
-    parameter_node: &type_spec_node::ParameterNode,
+    parameter_node: &type_spec_node::OperationParameterNode,
     current_file_path: &PathBuf,
     env: &CompilerEnv,
 ) -> Vec<type_spec_node::ImportLibNode> {
+    let parameter_node = match parameter_node {
+        type_spec_node::OperationParameterNode::Parameter(parameter_node) => parameter_node,
+        type_spec_node::OperationParameterNode::Spread(node) => {
+            return build_import_lib_nodes_from_model_content_node(node, current_file_path, env)
+        }
+    };
     let mut imports = vec![];
 
     imports.extend(
@@ -213,3 +878,35 @@ pub fn build_import_lib_nodes_from_operation_node(
 
     imports
 }
+
+pub fn build_using_namespace_nodes_from_operation_node(
+    operation_node: &type_spec_node::OperationNode,
+) -> Vec<type_spec_node::UsingNamespaceNode> {
+    let mut namespaces = vec![];
+
+    operation_node
+        .decorators
+        .iter()
+        .filter_map(|node| node.get_namespace())
+        .for_each(|namespace| {
+            namespaces.push(type_spec_node::UsingNamespaceNode::new(
+                namespace.to_string(),
+            ))
+        });
+
+    operation_node.parameters.iter().for_each(|parameter| {
+        if let type_spec_node::OperationParameterNode::Parameter(parameter) = parameter {
+            parameter
+                .decorators
+                .iter()
+                .filter_map(|node| node.get_namespace())
+                .for_each(|namespace| {
+                    namespaces.push(type_spec_node::UsingNamespaceNode::new(
+                        namespace.to_string(),
+                    ))
+                })
+        }
+    });
+
+    namespaces
+}
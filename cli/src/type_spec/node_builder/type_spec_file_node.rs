@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 use crate::compiler::CompilerEnv;
@@ -10,23 +10,23 @@ use crate::type_spec::node_builder::type_spec_node::{
 };
 
 fn build_import_and_name_spaces(
-    contents: &Vec<type_spec_node::TypeSpecNode>,
+    contents: &[type_spec_node::TypeSpecNode],
     current_file_path: &PathBuf,
     env: &CompilerEnv,
 ) -> (
     Vec<type_spec_node::ImportLibNode>,
     Vec<type_spec_node::UsingNamespaceNode>,
 ) {
-    let mut s1 = HashSet::new();
-    let mut s2 = HashSet::new();
+    let mut s1 = BTreeSet::new();
+    let mut s2 = BTreeSet::new();
 
     contents.iter().for_each(|node| {
         s1.extend(build_import_lib_nodes_from_type_spec_node(
-            &node,
+            node,
             current_file_path,
             env,
         ));
-        s2.extend(build_using_namespace_nodes_from_type_spec_node(&node, env));
+        s2.extend(build_using_namespace_nodes_from_type_spec_node(node));
     });
 
     (
@@ -53,23 +53,83 @@ pub fn build_type_spec_file_node(
     let mut contents = build_contents(contents.into_iter().collect(), file_name, env);
     let (mut imports, namespaces) = build_import_and_name_spaces(&contents, &path, env);
 
-    let imports_node = contents.iter().find(|node| {
-        if let type_spec_node::TypeSpecNode::Imports(_) = node {
-            true
-        } else {
-            false
+    contents.iter().for_each(|node| {
+        if let type_spec_node::TypeSpecNode::Imports(node) = node {
+            imports.extend(node.items.iter().cloned());
         }
     });
-    if let Some(type_spec_node::TypeSpecNode::Imports(node)) = imports_node {
-        imports.extend(node.items.clone().into_iter());
-        contents.retain(|node| {
-            if let type_spec_node::TypeSpecNode::Imports(_) = node {
-                false
-            } else {
-                true
-            }
-        });
+    imports.sort();
+    imports.dedup();
+    contents.retain(|node| !matches!(node, type_spec_node::TypeSpecNode::Imports(_)));
+
+    if env.options.nested_namespaces {
+        contents = wrap_contents_in_file_namespace(contents, &path, env);
     }
 
     type_spec_node::TypeSpecFileNode::new(path_str.into(), imports, namespaces, contents)
 }
+
+// NOTE: nested_namespaces の場合は、ファイルの宣言をまとめてディレクトリから決まる名前空間に入れる
+//       @service を付けた名前空間はそのまま残す
+fn wrap_contents_in_file_namespace(
+    contents: Vec<type_spec_node::TypeSpecNode>,
+    path: &Path,
+    env: &CompilerEnv,
+) -> Vec<type_spec_node::TypeSpecNode> {
+    let mut result = vec![];
+    let mut declarations = vec![];
+    for node in contents.into_iter() {
+        match node {
+            type_spec_node::TypeSpecNode::NameSpace(namespace_node)
+                if !namespace_node.decorators.is_empty() =>
+            {
+                result.push(type_spec_node::TypeSpecNode::NameSpace(namespace_node))
+            }
+            type_spec_node::TypeSpecNode::NameSpace(namespace_node)
+                if namespace_node.name == env.namespace =>
+            {
+                declarations.extend(*namespace_node.contents)
+            }
+            node => declarations.push(node),
+        }
+    }
+    if !declarations.is_empty() {
+        result.push(type_spec_node::TypeSpecNode::NameSpace(
+            type_spec_node::NamespaceNode {
+                decorators: Box::new(vec![]),
+                name: env.get_file_namespace(path),
+                contents: Box::new(declarations),
+            },
+        ));
+    }
+
+    result
+}
+
+// NOTE: 複数のファイルを 1 つの文書にまとめる。ファイル間の相対 import は不要になるので取り除く
+pub fn merge_type_spec_file_nodes(
+    path: PathBuf,
+    file_nodes: Vec<type_spec_node::TypeSpecFileNode>,
+) -> type_spec_node::TypeSpecFileNode {
+    let mut imports = BTreeSet::new();
+    let mut namespaces = BTreeSet::new();
+    let mut contents = vec![];
+
+    for file_node in file_nodes.into_iter() {
+        imports.extend(
+            file_node
+                .imports
+                .into_iter()
+                .filter(|import| !import.is_relative()),
+        );
+        namespaces.extend(*file_node.namespaces);
+        contents.extend(*file_node.contents);
+    }
+
+    type_spec_node::TypeSpecFileNode::new(
+        path,
+        imports.into_iter().collect(),
+        namespaces.into_iter().collect(),
+        contents,
+    )
+}
@@ -1,20 +1,24 @@
 use std::path::PathBuf;
 
 use crate::compiler::CompilerEnv;
-use crate::openapi_parser::{node as openapi_node, OperationNode};
+use crate::openapi_parser::node as openapi_node;
 use crate::type_spec::node as type_spec_node;
 use crate::type_spec::node_builder::build_namespace_node;
-use crate::type_spec::node_builder::enum_node::build_enum_node;
+use crate::type_spec::node_builder::component_node::build_component_node;
+use crate::type_spec::node_builder::enum_node::{
+    build_enum_node, build_open_enum_node, is_enum_data_model_node,
+};
 use crate::type_spec::node_builder::import_lib_node::build_import_lib_nodes;
 use crate::type_spec::node_builder::interface_node::{
     build_import_lib_nodes_from_interface_node, build_using_namespace_nodes_from_interface_node,
-    build_wrapped_interface_node,
+    build_webhook_namespace_node, build_wrapped_interface_node,
 };
 use crate::type_spec::node_builder::model_alias_node::{
     build_import_lib_nodes_model_alias_node, build_model_alias_node,
 };
 use crate::type_spec::node_builder::model_node::{
     build_import_lib_nodes_from_model_node, build_model_node,
+    build_using_namespace_nodes_from_model_node,
 };
 use crate::type_spec::node_builder::namespace_node::{
     build_import_lib_nodes_from_namespace_node, build_using_namespace_nodes_from_namespace_node,
@@ -30,7 +34,10 @@ fn build_content_import_nodes(
     _current_file_name: &str,
     _env: &CompilerEnv,
 ) -> BuildContentResult {
-    if let Some(openapi_node::OpenAPINode::Paths(paths)) = contents.get(0) {
+    if let Some(
+        openapi_node::OpenAPINode::Paths(paths) | openapi_node::OpenAPINode::Webhooks(paths),
+    ) = contents.first()
+    {
         let import_lib_node = build_import_lib_nodes(paths);
         contents.remove(0);
         (
@@ -47,7 +54,7 @@ fn build_content_namespace_node(
     _current_file_name: &str,
     env: &CompilerEnv,
 ) -> BuildContentResult {
-    if let Some(openapi_node::OpenAPINode::Info(info_node)) = contents.get(0) {
+    if let Some(openapi_node::OpenAPINode::Info(info_node)) = contents.first() {
         let namespace_node = build_namespace_node(info_node, env);
         contents.remove(0);
         (
@@ -62,12 +69,12 @@ fn build_content_namespace_node(
 fn build_content_model_node(
     mut contents: Vec<openapi_node::OpenAPINode>,
     _current_file_name: &str,
-    _env: &CompilerEnv,
+    env: &CompilerEnv,
 ) -> BuildContentResult {
     if let Some(openapi_node::OpenAPINode::DataModel(openapi_node::DataModelNode::Object(ojb))) =
-        contents.get(0)
+        contents.first()
     {
-        let model_node = build_model_node(ojb);
+        let model_node = build_model_node(ojb, env);
         contents.remove(0);
         (
             Some(type_spec_node::TypeSpecNode::Model(model_node)),
@@ -81,18 +88,37 @@ fn build_content_model_node(
 fn build_content_enum_node(
     mut contents: Vec<openapi_node::OpenAPINode>,
     current_file_name: &str,
-    _env: &CompilerEnv,
+    env: &CompilerEnv,
 ) -> BuildContentResult {
-    if let Some(openapi_node::OpenAPINode::DataModel(openapi_node::DataModelNode::String(
-        string_node,
-    ))) = contents.get(0)
-    {
-        let enum_node = build_enum_node(string_node, current_file_name);
+    match contents.first() {
+        Some(openapi_node::OpenAPINode::DataModel(node)) if is_enum_data_model_node(node) => {
+            let node = if env.options.open_enums {
+                type_spec_node::TypeSpecNode::Union(build_open_enum_node(node, current_file_name))
+            } else {
+                type_spec_node::TypeSpecNode::Enum(build_enum_node(node, current_file_name))
+            };
+            contents.remove(0);
+            (Some(node), contents)
+        }
+        _ => (None, contents),
+    }
+}
+
+fn build_content_component_node(
+    mut contents: Vec<openapi_node::OpenAPINode>,
+    _current_file_name: &str,
+    env: &CompilerEnv,
+) -> BuildContentResult {
+    // NOTE: 他のファイルの component を参照しているだけのものは何も出力しないので unknown として捨てる
+    if let Some(node) = contents.first().and_then(|content| {
+        if let openapi_node::OpenAPINode::Component(component) = content {
+            build_component_node(component, env)
+        } else {
+            None
+        }
+    }) {
         contents.remove(0);
-        (
-            Some(type_spec_node::TypeSpecNode::Enum(enum_node)),
-            contents,
-        )
+        (Some(node), contents)
     } else {
         (None, contents)
     }
@@ -101,10 +127,10 @@ fn build_content_enum_node(
 fn build_content_model_alias_node(
     mut contents: Vec<openapi_node::OpenAPINode>,
     _current_file_name: &str,
-    _env: &CompilerEnv,
+    env: &CompilerEnv,
 ) -> BuildContentResult {
-    if let Some(openapi_node::OpenAPINode::DataModel(data_mmodel)) = contents.get(0) {
-        let model_alias = build_model_alias_node(data_mmodel);
+    if let Some(openapi_node::OpenAPINode::DataModel(data_mmodel)) = contents.first() {
+        let model_alias = build_model_alias_node(data_mmodel, env);
         contents.remove(0);
 
         (
@@ -131,15 +157,26 @@ fn build_content_interface_node(
             }
         })
         .collect::<Vec<_>>();
-    if operations.len() > 0 {
+    if !operations.is_empty() {
         let namespace_node = build_wrapped_interface_node(&operations, current_file_name, env);
-        contents.retain(|content| {
-            if let openapi_node::OpenAPINode::Operation(_) = content {
-                false
-            } else {
-                true
-            }
-        });
+        contents.retain(|content| !matches!(content, openapi_node::OpenAPINode::Operation(_)));
+        (
+            Some(type_spec_node::TypeSpecNode::NameSpace(namespace_node)),
+            contents,
+        )
+    } else {
+        (None, contents)
+    }
+}
+
+fn build_content_webhook_node(
+    mut contents: Vec<openapi_node::OpenAPINode>,
+    _current_file_name: &str,
+    env: &CompilerEnv,
+) -> BuildContentResult {
+    if let Some(openapi_node::OpenAPINode::Webhook(webhook)) = contents.first() {
+        let namespace_node = build_webhook_namespace_node(webhook, env);
+        contents.remove(0);
         (
             Some(type_spec_node::TypeSpecNode::NameSpace(namespace_node)),
             contents,
@@ -159,17 +196,19 @@ fn build_content_unknown_node(
 }
 
 fn build_content(
-    mut contents: Vec<openapi_node::OpenAPINode>,
+    contents: Vec<openapi_node::OpenAPINode>,
     current_file_name: &str,
     env: &CompilerEnv,
 ) -> BuildContentResult {
-    vec![
+    [
         build_content_import_nodes,
         build_content_namespace_node,
         build_content_enum_node,
         build_content_model_node,
+        build_content_component_node,
         build_content_model_alias_node,
         build_content_interface_node,
+        build_content_webhook_node,
         build_content_unknown_node,
     ]
     .iter()
@@ -189,7 +228,7 @@ pub fn build_contents(
 ) -> Vec<type_spec_node::TypeSpecNode> {
     let mut result = Vec::new();
 
-    while contents.len() > 0 {
+    while !contents.is_empty() {
         let len = contents.len();
         let (node, new_contents) = build_content(contents, current_file_name, env);
         if let Some(node) = node {
@@ -233,7 +272,7 @@ pub fn build_import_lib_nodes_from_type_spec_node(
             build_import_lib_nodes_from_interface_node(interface_node, current_file_path, env),
         ),
         type_spec_node::TypeSpecNode::ModelAlias(alias_node) => imports.extend(
-            build_import_lib_nodes_model_alias_node(&alias_node, current_file_path, env),
+            build_import_lib_nodes_model_alias_node(alias_node, current_file_path, env),
         ),
         _ => {}
     }
@@ -243,7 +282,6 @@ pub fn build_import_lib_nodes_from_type_spec_node(
 
 pub fn build_using_namespace_nodes_from_type_spec_node(
     content: &type_spec_node::TypeSpecNode,
-    env: &CompilerEnv,
 ) -> Vec<type_spec_node::UsingNamespaceNode> {
     let mut namespaces = vec![];
 
@@ -253,14 +291,15 @@ pub fn build_using_namespace_nodes_from_type_spec_node(
                 namespace_node,
             ));
             for content in namespace_node.contents.iter() {
-                namespaces.extend(build_using_namespace_nodes_from_type_spec_node(
-                    content, env,
-                ));
+                namespaces.extend(build_using_namespace_nodes_from_type_spec_node(content));
             }
         }
         type_spec_node::TypeSpecNode::Interface(interface_node) => namespaces.extend(
             build_using_namespace_nodes_from_interface_node(interface_node),
         ),
+        type_spec_node::TypeSpecNode::Model(model_node) => {
+            namespaces.extend(build_using_namespace_nodes_from_model_node(model_node))
+        }
         _ => {}
     }
 
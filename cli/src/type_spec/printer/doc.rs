@@ -0,0 +1,183 @@
+// NOTE: Wadler の pretty printer と同じ考え方で、group が 1 行に収まるなら Line を空白として、
+//       収まらないなら改行として出力する
+#[derive(Debug, Clone)]
+pub enum Doc {
+    Text(String),
+    // NOTE: soft の場合は 1 行に収まるときに何も出力しない
+    Line { soft: bool },
+    HardLine,
+    Indent(Box<Doc>),
+    // NOTE: インデント幅の設定に関係なく、指定した桁数だけ字下げする
+    Align(usize, Box<Doc>),
+    Group(Box<Doc>),
+    Concat(Vec<Doc>),
+    // NOTE: group が改行されたときだけ出力する (末尾のカンマなど)
+    IfBreak(String),
+}
+
+pub fn text(s: &str) -> Doc {
+    Doc::Text(s.to_string())
+}
+
+pub fn line() -> Doc {
+    Doc::Line { soft: false }
+}
+
+pub fn softline() -> Doc {
+    Doc::Line { soft: true }
+}
+
+pub fn indent(doc: Doc) -> Doc {
+    Doc::Indent(Box::new(doc))
+}
+
+pub fn align(width: usize, doc: Doc) -> Doc {
+    Doc::Align(width, Box::new(doc))
+}
+
+pub fn group(doc: Doc) -> Doc {
+    Doc::Group(Box::new(doc))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+fn fits(doc: &Doc, rest: &[(usize, Mode, &Doc)], width: usize) -> bool {
+    let mut width = width as isize;
+    let mut stack = vec![(Mode::Flat, doc)];
+    let mut rest_index = rest.len();
+
+    loop {
+        let (mode, doc) = if let Some(next) = stack.pop() {
+            next
+        } else if rest_index > 0 {
+            rest_index -= 1;
+            (rest[rest_index].1, rest[rest_index].2)
+        } else {
+            return true;
+        };
+
+        match doc {
+            Doc::Text(s) => width -= s.chars().count() as isize,
+            Doc::Line { soft } => match mode {
+                Mode::Flat if !soft => width -= 1,
+                Mode::Flat => {}
+                Mode::Break => return true,
+            },
+            // NOTE: 強制的な改行を含む group は 1 行には収まらない
+            Doc::HardLine => return mode == Mode::Break,
+            Doc::Indent(doc) | Doc::Align(_, doc) | Doc::Group(doc) => stack.push((mode, doc)),
+            Doc::Concat(docs) => docs.iter().rev().for_each(|doc| stack.push((mode, doc))),
+            Doc::IfBreak(s) => {
+                if mode == Mode::Break {
+                    width -= s.chars().count() as isize;
+                }
+            }
+        }
+
+        if width < 0 {
+            return false;
+        }
+    }
+}
+
+fn push_new_line(output: &mut String, indent: usize) {
+    let trimmed_len = output.trim_end_matches(' ').len();
+    output.truncate(trimmed_len);
+    output.push('\n');
+    output.push_str(&" ".repeat(indent));
+}
+
+pub fn print_doc(doc: &Doc, indent_width: usize, max_line_width: usize) -> String {
+    let mut output = String::new();
+    let mut column = 0;
+    let mut stack = vec![(0, Mode::Break, doc)];
+
+    while let Some((indent, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(s) => {
+                output.push_str(s);
+                column += s.chars().count();
+            }
+            Doc::Line { soft } => match mode {
+                Mode::Flat => {
+                    if !soft {
+                        output.push(' ');
+                        column += 1;
+                    }
+                }
+                Mode::Break => {
+                    push_new_line(&mut output, indent);
+                    column = indent;
+                }
+            },
+            Doc::HardLine => {
+                push_new_line(&mut output, indent);
+                column = indent;
+            }
+            Doc::Indent(doc) => stack.push((indent + indent_width, mode, doc)),
+            Doc::Align(width, doc) => stack.push((indent + width, mode, doc)),
+            Doc::Group(doc) => {
+                let mode = if mode == Mode::Flat
+                    || fits(doc, &stack, max_line_width.saturating_sub(column))
+                {
+                    Mode::Flat
+                } else {
+                    Mode::Break
+                };
+                stack.push((indent, mode, doc));
+            }
+            Doc::Concat(docs) => docs
+                .iter()
+                .rev()
+                .for_each(|doc| stack.push((indent, mode, doc))),
+            Doc::IfBreak(s) => {
+                if mode == Mode::Break {
+                    output.push_str(s);
+                    column += s.chars().count();
+                }
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_list_doc() -> Doc {
+        group(Doc::Concat(vec![
+            text("("),
+            indent(Doc::Concat(vec![softline(), text("a,"), line(), text("b")])),
+            Doc::IfBreak(",".to_string()),
+            softline(),
+            text(")"),
+        ]))
+    }
+
+    #[test]
+    fn test_print_doc() {
+        assert_eq!(print_doc(&build_list_doc(), 2, 80), "(a, b)");
+        assert_eq!(print_doc(&build_list_doc(), 2, 5), "(\n  a,\n  b,\n)");
+
+        // NOTE: 強制的な改行を含む group は幅に関係なく改行される
+        let doc = group(Doc::Concat(vec![
+            text("a"),
+            line(),
+            Doc::HardLine,
+            text("b"),
+        ]));
+        assert_eq!(print_doc(&doc, 2, 80), "a\n\nb");
+
+        let doc = Doc::Concat(vec![
+            text("| "),
+            align(2, Doc::Concat(vec![text("{"), Doc::HardLine, text("}")])),
+        ]);
+        assert_eq!(print_doc(&doc, 4, 80), "| {\n  }");
+    }
+}
@@ -0,0 +1,249 @@
+use crate::type_spec::printer::doc::*;
+
+pub fn join(docs: Vec<Doc>, separator: Doc) -> Doc {
+    let mut result = vec![];
+    for (i, doc) in docs.into_iter().enumerate() {
+        if i > 0 {
+            result.push(separator.clone());
+        }
+        result.push(doc);
+    }
+
+    Doc::Concat(result)
+}
+
+// NOTE: 宣言の decorator は 1 行に 1 つずつ並べる
+pub fn decorated_doc(decorators: Vec<Doc>, body: Doc) -> Doc {
+    let mut docs = vec![];
+    for decorator in decorators {
+        docs.push(decorator);
+        docs.push(Doc::HardLine);
+    }
+    docs.push(body);
+
+    Doc::Concat(docs)
+}
+
+pub struct Member {
+    pub doc: Doc,
+    // NOTE: decorator があって複数行になるメンバー
+    pub is_multiline: bool,
+}
+
+// NOTE: tsp format と同じく、decorator で複数行になるメンバーの前後には空行を入れる
+//       enum や union のメンバーは最後のメンバーにもカンマを付ける
+pub fn statements_doc(members: Vec<Member>, blank_lines: bool, comma_separated: bool) -> Doc {
+    let mut docs = vec![];
+    let mut previous_is_multiline = false;
+    for (i, member) in members.into_iter().enumerate() {
+        if i > 0 {
+            docs.push(Doc::HardLine);
+            if blank_lines || member.is_multiline || previous_is_multiline {
+                docs.push(Doc::HardLine);
+            }
+        }
+        previous_is_multiline = member.is_multiline;
+        docs.push(member.doc);
+        if comma_separated {
+            docs.push(text(","));
+        }
+    }
+
+    Doc::Concat(docs)
+}
+
+pub fn block_doc(statements: Option<Doc>) -> Doc {
+    match statements {
+        Some(statements) => Doc::Concat(vec![
+            text("{"),
+            indent(Doc::Concat(vec![Doc::HardLine, statements])),
+            Doc::HardLine,
+            text("}"),
+        ]),
+        None => text("{}"),
+    }
+}
+
+// NOTE: 収まらない union は tsp format と同じく、先頭に | を付けて 1 行に 1 つずつ並べる
+//       `status?:` や `alias Status =` の後ろに続く場合は、その後ろで改行する
+pub fn union_doc(variants: Vec<Doc>, is_prefixed: bool) -> Doc {
+    let mut docs = vec![
+        if is_prefixed { line() } else { softline() },
+        Doc::IfBreak("| ".to_string()),
+    ];
+    for (i, variant) in variants.into_iter().enumerate() {
+        if i > 0 {
+            docs.push(line());
+            docs.push(text("| "));
+        }
+        // NOTE: `| ` の後ろから始まるので、variant の中の改行は `| ` の分だけ字下げする
+        docs.push(align(2, variant));
+    }
+
+    group(indent(Doc::Concat(docs)))
+}
+
+// NOTE: `key?: value;` のように prefix の後ろに型を続ける。型が union の場合は prefix の後ろで改行できるようにする
+pub fn typed_doc(prefix: Doc, mut variants: Vec<Doc>, suffix: &str) -> Doc {
+    let value = if variants.len() == 1 {
+        Doc::Concat(vec![text(" "), variants.remove(0)])
+    } else {
+        union_doc(variants, true)
+    };
+
+    Doc::Concat(vec![prefix, value, text(suffix)])
+}
+
+pub struct ListItem {
+    pub doc: Doc,
+    // NOTE: decorator が 3 つ以上ある引数は常に改行し、前後に空行を入れる
+    pub is_wrapped: bool,
+}
+
+impl From<Doc> for ListItem {
+    fn from(doc: Doc) -> Self {
+        ListItem {
+            doc,
+            is_wrapped: false,
+        }
+    }
+}
+
+// NOTE: 括弧の中のカンマ区切りのリストは、収まらないときに 1 行に 1 つずつ並べる
+pub fn list_doc(open: &str, close: &str, items: Vec<ListItem>, trailing_comma: bool) -> Doc {
+    if items.is_empty() {
+        return Doc::Concat(vec![text(open), text(close)]);
+    }
+
+    // NOTE: object の場合は括弧の内側に空白を入れる
+    let bracket_line = if open.ends_with('{') {
+        line()
+    } else {
+        softline()
+    };
+    let mut docs = vec![];
+    let mut blank_after = false;
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            docs.push(text(","));
+            docs.push(line());
+            if item.is_wrapped || blank_after {
+                docs.push(softline());
+            }
+        }
+        blank_after = item.is_wrapped;
+        docs.push(item.doc);
+    }
+
+    group(Doc::Concat(vec![
+        text(open),
+        indent(Doc::Concat(vec![bracket_line.clone(), Doc::Concat(docs)])),
+        Doc::IfBreak(if trailing_comma { "," } else { "" }.to_string()),
+        bracket_line,
+        text(close),
+    ]))
+}
+
+pub fn object_doc(properties: Vec<(String, Doc)>) -> Doc {
+    list_doc(
+        "#{",
+        "}",
+        properties
+            .into_iter()
+            .map(|(key, value)| Doc::Concat(vec![text(&key), text(": "), value]).into())
+            .collect(),
+        true,
+    )
+}
+
+// NOTE: 引数が 1 つだけの場合は括弧の中で改行しない
+//       object の場合は括弧ではなく object の方を改行する
+pub fn decorator_doc(name: &str, mut arguments: Vec<Doc>) -> Doc {
+    let name = text(&format!("@{}", name));
+    match arguments.len() {
+        0 => name,
+        1 => Doc::Concat(vec![name, text("("), arguments.remove(0), text(")")]),
+        _ => Doc::Concat(vec![
+            name,
+            list_doc(
+                "(",
+                ")",
+                arguments.into_iter().map(ListItem::from).collect(),
+                true,
+            ),
+        ]),
+    }
+}
+
+// NOTE: 引数の decorator は収まらないときに 1 つずつ改行する
+pub fn parameter_doc(decorators: Vec<Doc>, body: Doc) -> ListItem {
+    if decorators.is_empty() {
+        return body.into();
+    }
+
+    let is_wrapped = decorators.len() >= 3;
+    let separator = if is_wrapped { Doc::HardLine } else { line() };
+    let mut docs = vec![];
+    for decorator in decorators {
+        docs.push(decorator);
+        docs.push(separator.clone());
+    }
+
+    ListItem {
+        doc: Doc::Concat(vec![group(Doc::Concat(docs)), body]),
+        is_wrapped,
+    }
+}
+
+// NOTE: 改行せずに 1 行で出力する (decorator の Display など)
+pub fn print_flat(doc: &Doc) -> String {
+    print_doc(doc, 0, isize::MAX as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_statements_doc() {
+        let members = vec![
+            Member {
+                doc: decorated_doc(vec![text("@doc(\"a\")")], text("a: string;")),
+                is_multiline: true,
+            },
+            Member {
+                doc: text("b: string;"),
+                is_multiline: false,
+            },
+            Member {
+                doc: text("c: string;"),
+                is_multiline: false,
+            },
+        ];
+
+        assert_eq!(
+            print_doc(&statements_doc(members, false, false), 2, 80),
+            "@doc(\"a\")\na: string;\n\nb: string;\nc: string;"
+        );
+    }
+
+    #[test]
+    fn test_decorator_doc() {
+        let doc = decorator_doc(
+            "extension",
+            vec![
+                text("\"x-key\""),
+                object_doc(vec![("name".to_string(), text("\"value\""))]),
+            ],
+        );
+
+        assert_eq!(
+            print_flat(&doc),
+            "@extension(\"x-key\", #{ name: \"value\" })"
+        );
+        assert_eq!(
+            print_doc(&doc, 2, 24),
+            "@extension(\n  \"x-key\",\n  #{ name: \"value\" },\n)"
+        );
+    }
+}
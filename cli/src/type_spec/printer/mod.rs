@@ -0,0 +1,472 @@
+mod doc;
+mod layout;
+
+pub use crate::type_spec::printer::doc::*;
+pub use crate::type_spec::printer::layout::*;
+
+// NOTE: ノードから Doc を組み立て、インデントと折り返しは print_doc で決める
+pub trait ToDoc {
+    fn to_doc(&self) -> Doc;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintOptions {
+    pub indent_width: usize,
+    pub max_line_width: usize,
+    pub trailing_newline: bool,
+}
+
+// NOTE: tsp format (prettier) の既定値に合わせる
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            indent_width: 2,
+            max_line_width: 80,
+            trailing_newline: true,
+        }
+    }
+}
+
+pub fn format(doc: &Doc, options: &PrintOptions) -> String {
+    let output = print_doc(doc, options.indent_width, options.max_line_width);
+    let output = output.trim_end();
+
+    if options.trailing_newline {
+        format!("{}\n", output)
+    } else {
+        output.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::type_spec::node::decorators::*;
+    use crate::type_spec::node::*;
+
+    fn build_file_node(contents: Vec<TypeSpecNode>) -> TypeSpecFileNode {
+        TypeSpecFileNode::new(PathBuf::from("main.tsp"), vec![], vec![], contents)
+    }
+
+    fn build_property(
+        key: &str,
+        value: ModelContentNode,
+        required: bool,
+        decorators: Vec<Box<dyn RecordPropertyDecorator>>,
+    ) -> RecordPropertyNode {
+        RecordPropertyNode {
+            decorators: Box::new(decorators),
+            key: RecordPropertyKey::from(&key.to_string()),
+            value,
+            required,
+        }
+    }
+
+    fn build_parameter(
+        name: &str,
+        type_model: ModelContentNode,
+        required: bool,
+        decorators: Vec<Box<dyn ParameterDecorator>>,
+    ) -> OperationParameterNode {
+        OperationParameterNode::Parameter(ParameterNode {
+            decorators: Box::new(decorators),
+            name: name.to_string(),
+            type_model,
+            required,
+        })
+    }
+
+    fn build_operation(
+        name: &str,
+        method: Method,
+        parameters: Vec<OperationParameterNode>,
+        responses: Vec<ModelContentNode>,
+    ) -> OperationNode {
+        OperationNode {
+            name: name.to_string(),
+            decorators: Box::new(vec![Box::new(MethodDecoratorNode::new(method))]),
+            parameters: Box::new(parameters),
+            responses: Box::new(responses),
+        }
+    }
+
+    fn build_interface(operations: Vec<OperationNode>) -> TypeSpecNode {
+        TypeSpecNode::Interface(InterfaceNode {
+            name: "Pets".to_string(),
+            decorators: Box::new(vec![]),
+            operations: Box::new(operations),
+        })
+    }
+
+    fn model_ref(name: &str) -> ModelContentNode {
+        ModelContentNode::ModelRef(IdentifierNode::from(name))
+    }
+
+    fn string_union(values: &[&str]) -> ModelContentNode {
+        ModelContentNode::Union(
+            values
+                .iter()
+                .map(|value| ModelContentNode::StringLiteral(value.to_string()))
+                .collect(),
+        )
+    }
+
+    fn doc_decorator(value: &str) -> DocDecorator {
+        DocDecorator {
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_blocks() {
+        let status = EnumNode {
+            decorators: Box::new(vec![]),
+            title: IdentifierNode::from("Status"),
+            items: Box::new(
+                ["available", "sold"]
+                    .iter()
+                    .map(|value| EnumMemberNode {
+                        decorators: Box::new(vec![]),
+                        name: IdentifierNode::from(*value),
+                        value: EnumMemberValue::String(value.to_string()),
+                    })
+                    .collect(),
+            ),
+        };
+        let owner = RecordModelNode {
+            properties: Box::new(vec![build_property(
+                "name",
+                ModelContentNode::Type(TypeNode::String),
+                false,
+                vec![],
+            )]),
+        };
+        let pet = ModelNode {
+            decorators: Box::new(vec![]),
+            name: IdentifierNode::from("Pet"),
+            record: RecordModelNode {
+                properties: Box::new(vec![
+                    build_property(
+                        "id",
+                        ModelContentNode::Type(TypeNode::Int64),
+                        true,
+                        vec![Box::new(doc_decorator("id"))],
+                    ),
+                    build_property(
+                        "name",
+                        ModelContentNode::Type(TypeNode::String),
+                        true,
+                        vec![],
+                    ),
+                    build_property("owner", ModelContentNode::Record(owner), false, vec![]),
+                ]),
+            },
+        };
+        let empty = ModelNode {
+            decorators: Box::new(vec![]),
+            name: IdentifierNode::from("Empty"),
+            record: RecordModelNode {
+                properties: Box::new(vec![]),
+            },
+        };
+        let file = build_file_node(vec![
+            TypeSpecNode::Enum(status),
+            TypeSpecNode::Model(pet),
+            TypeSpecNode::Model(empty),
+        ]);
+
+        assert_eq!(
+            file.print(&PrintOptions::default()),
+            r#"enum Status {
+  available: "available",
+  sold: "sold",
+}
+
+model Pet {
+  @doc("id")
+  id: int64;
+
+  name: string;
+  owner?: {
+    name?: string;
+  };
+}
+
+model Empty {}
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_wrapping() {
+        let list = build_operation(
+            "list",
+            Method::Get,
+            vec![
+                build_parameter(
+                    "limit",
+                    ModelContentNode::Type(TypeNode::Int32),
+                    false,
+                    vec![Box::new(QueryDecorator {
+                        name: None,
+                        explode: None,
+                    })],
+                ),
+                build_parameter(
+                    "requestId",
+                    ModelContentNode::Type(TypeNode::String),
+                    false,
+                    vec![Box::new(HeaderDecorator {
+                        name: Some("X-Request-ID".to_string()),
+                        explode: None,
+                    })],
+                ),
+            ],
+            vec![
+                ModelContentNode::Array(ArrayModelNode {
+                    item_type: Box::new(model_ref("Pet")),
+                }),
+                model_ref("NotFound"),
+                model_ref("Unauthorized"),
+            ],
+        );
+        let options = PrintOptions {
+            indent_width: 4,
+            max_line_width: 40,
+            trailing_newline: false,
+        };
+
+        assert_eq!(
+            build_file_node(vec![build_interface(vec![list])]).print(&options),
+            r#"interface Pets {
+    @get
+    list(
+        @query limit?: int32,
+        @header("X-Request-ID")
+        requestId?: string,
+    ): Pet[] | NotFound | Unauthorized;
+}"#
+        );
+    }
+
+    #[test]
+    fn test_format_union_and_object() {
+        let namespace = NamespaceNode {
+            decorators: Box::new(vec![
+                Box::new(ServiceDecorator {
+                    title: "Petstore".to_string(),
+                }),
+                Box::new(AdditionalInfoNode {
+                    version: "1.0.0".to_string(),
+                    contact: None,
+                    license: None,
+                    terms_of_service: None,
+                    key_order: vec![],
+                }),
+            ]),
+            name: "PetstoreService".to_string(),
+            contents: Box::new(vec![TypeSpecNode::ModelAlias(ModelAliasNode {
+                identifier: IdentifierNode::from("Status"),
+                alias_type: string_union(&["available", "pending", "sold"]),
+            })]),
+        };
+        let options = PrintOptions {
+            max_line_width: 30,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            build_file_node(vec![TypeSpecNode::NameSpace(namespace)]).print(&options),
+            r#"@service(#{
+  title: "Petstore",
+})
+@info(#{ version: "1.0.0" })
+namespace PetstoreService {
+  alias Status =
+    | "available"
+    | "pending"
+    | "sold";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_wrapped_union_property() {
+        let pet = ModelNode {
+            decorators: Box::new(vec![]),
+            name: IdentifierNode::from("Pet"),
+            record: RecordModelNode {
+                properties: Box::new(vec![
+                    build_property(
+                        "status",
+                        string_union(&[
+                            "available",
+                            "pending",
+                            "sold",
+                            "discontinued",
+                            "reserved",
+                            "out-of-stock",
+                        ]),
+                        false,
+                        vec![],
+                    ),
+                    build_property("kind", string_union(&["dog", "cat"]), true, vec![]),
+                ]),
+            },
+        };
+
+        assert_eq!(
+            build_file_node(vec![TypeSpecNode::Model(pet)]).print(&PrintOptions::default()),
+            r#"model Pet {
+  status?:
+    | "available"
+    | "pending"
+    | "sold"
+    | "discontinued"
+    | "reserved"
+    | "out-of-stock";
+  kind: "dog" | "cat";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_parameter_decorators() {
+        let create = build_operation(
+            "create",
+            Method::Post,
+            vec![
+                build_parameter(
+                    "callbackUrl",
+                    ModelContentNode::Type(TypeNode::String),
+                    true,
+                    vec![
+                        Box::new(doc_decorator("$request.body#/callbackUrl")),
+                        Box::new(PathDecorator {
+                            name: None,
+                            style: None,
+                            explode: None,
+                            allow_reserved: Some(true),
+                        }),
+                    ],
+                ),
+                build_parameter(
+                    "petId",
+                    ModelContentNode::Type(TypeNode::String),
+                    true,
+                    vec![Box::new(PathDecorator {
+                        name: None,
+                        style: Some("label".to_string()),
+                        explode: None,
+                        allow_reserved: None,
+                    })],
+                ),
+                build_parameter(
+                    "requestId",
+                    ModelContentNode::Type(TypeNode::String),
+                    true,
+                    vec![
+                        Box::new(HeaderDecorator {
+                            name: None,
+                            explode: None,
+                        }),
+                        Box::new(doc_decorator("id")),
+                        Box::new(MinLengthDecorator { value: 1 }),
+                    ],
+                ),
+            ],
+            vec![ModelContentNode::Type(TypeNode::Mapped("void".to_string()))],
+        );
+
+        assert_eq!(
+            build_file_node(vec![build_interface(vec![create])]).print(&PrintOptions::default()),
+            r#"interface Pets {
+  @post
+  create(
+    @doc("$request.body#/callbackUrl")
+    @path(#{ allowReserved: true })
+    callbackUrl: string,
+    @path(#{ style: "label" }) petId: string,
+
+    @header
+    @doc("id")
+    @minLength(1)
+    requestId: string,
+  ): void;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_parameter_list() {
+        let list = build_operation(
+            "list",
+            Method::Get,
+            vec![build_parameter(
+                "limit",
+                ModelContentNode::Type(TypeNode::Int32),
+                false,
+                vec![Box::new(QueryDecorator {
+                    name: None,
+                    explode: None,
+                })],
+            )],
+            vec![ModelContentNode::Array(ArrayModelNode {
+                item_type: Box::new(model_ref("Pet")),
+            })],
+        );
+        let body = RecordModelNode {
+            properties: Box::new(vec![build_property(
+                "name",
+                ModelContentNode::Type(TypeNode::String),
+                true,
+                vec![],
+            )]),
+        };
+        let create = build_operation(
+            "create",
+            Method::Post,
+            vec![
+                build_parameter(
+                    "requestId",
+                    ModelContentNode::Type(TypeNode::String),
+                    true,
+                    vec![Box::new(HeaderDecorator {
+                        name: Some("X-Request-ID".to_string()),
+                        explode: None,
+                    })],
+                ),
+                build_parameter(
+                    "body",
+                    ModelContentNode::Record(body),
+                    true,
+                    vec![Box::new(BodyDecorator)],
+                ),
+            ],
+            vec![model_ref("Pet")],
+        );
+
+        assert_eq!(
+            build_file_node(vec![build_interface(vec![list, create])])
+                .print(&PrintOptions::default()),
+            r#"interface Pets {
+  @get
+  list(@query limit?: int32): Pet[];
+
+  @post
+  create(
+    @header("X-Request-ID") requestId: string,
+    @body body: {
+      name: string;
+    },
+  ): Pet;
+}
+"#
+        );
+    }
+}
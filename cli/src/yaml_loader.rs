@@ -1,20 +1,12 @@
-use std::fmt::Display;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 
+use rayon::prelude::*;
 use yaml_rust::{Yaml, YamlLoader};
 
-#[derive(Debug)]
-pub enum YamlNode {
-    YamlDirectory(YamlDirectory),
-    YamlFile(YamlFile),
-}
-
-#[derive(Debug)]
-pub struct YamlDirectory {
-    pub path: PathBuf,
-    pub children: Box<Vec<YamlNode>>,
-}
+use crate::compiler::is_config_file;
+use crate::file_source::{normalize_path, FileSource};
 
 #[derive(Debug, Clone)]
 pub struct YamlFile {
@@ -22,111 +14,147 @@ pub struct YamlFile {
     pub content: Box<Vec<Yaml>>,
 }
 
-fn build_nest_str(nest: usize) -> String {
-    vec!["  "; nest].join("")
+fn parse_file_content(path: &Path, content: &str) -> io::Result<Vec<Yaml>> {
+    YamlLoader::load_from_str(content).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("failed to parse yaml {}: {}", path.display(), error),
+        )
+    })
 }
 
-impl YamlNode {
-    fn to_str(&self, nest: usize) -> String {
-        match self {
-            YamlNode::YamlDirectory(d) => d.to_str(nest),
-            YamlNode::YamlFile(y) => y.to_str(nest),
-        }
-    }
-    pub fn flatten_files(self) -> Vec<YamlFile> {
-        match self {
-            YamlNode::YamlDirectory(d) => {
-                let mut files = vec![];
-                for child in d.children.into_iter() {
-                    files.append(&mut child.flatten_files());
-                }
-                files
-            }
-            YamlNode::YamlFile(y) => vec![y],
-        }
-    }
-}
+// NOTE: read_dir の順序は保証されないので、出力が実行ごとに変わらないようにパス順に並べる
+fn read_dir_paths(dir: &PathBuf) -> Vec<PathBuf> {
+    let entries = fs::read_dir(dir).expect("read_dir call failed");
 
-impl YamlDirectory {
-    fn to_str(&self, nest: usize) -> String {
-        let nest_str = build_nest_str(nest);
+    let mut paths = entries
+        .into_iter()
+        .map(|entry| entry.expect("failed to read_entry").path())
+        // NOTE: emit_project で出力したプロジェクトで npm install すると node_modules に yaml が置かれる
+        .filter(|path| {
+            (path.is_dir() && !path.ends_with("node_modules"))
+                || path.to_str().map(|s| s.ends_with(".yaml")).unwrap_or(false)
+        })
+        .filter(|path| !is_config_file(path))
+        .collect::<Vec<_>>();
+    paths.sort();
 
-        let children = self
-            .children
-            .iter()
-            .map(|c| c.to_str(nest + 1))
-            .collect::<Vec<_>>();
-
-        format!(
-            "{}{}\n{}",
-            nest_str,
-            self.path.to_str().expect("failed to convert path to str"),
-            children.join("\n")
-        )
-    }
+    paths
 }
 
-impl YamlFile {
-    fn to_str(&self, nest: usize) -> String {
-        let nest_str = build_nest_str(nest);
-
-        format!(
-            "{}{}",
-            nest_str,
-            self.path.to_str().expect("failed to convert path to str")
-        )
+// NOTE: 変換対象の yaml ファイルを、中身を読まずにパス順で列挙する
+pub fn list_yaml_files(root: &PathBuf) -> Vec<PathBuf> {
+    if root.is_file() {
+        return vec![root.clone()];
     }
-}
 
-impl Display for YamlNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_str(0))
-    }
+    read_dir_paths(root)
+        .into_iter()
+        .flat_map(|path| list_yaml_files(&path))
+        .collect()
 }
 
-impl Display for YamlDirectory {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_str(0))
-    }
-}
+// NOTE: 読み込みや parse に失敗した場合は、どのファイルかわかるようにパスを含めたエラーを返す
+pub fn read_yaml_file(source: &dyn FileSource, path: &Path) -> io::Result<YamlFile> {
+    let content = source.read_to_string(path).map_err(|error| {
+        io::Error::new(
+            error.kind(),
+            format!("failed to read {}: {}", path.display(), error),
+        )
+    })?;
 
-impl Display for YamlFile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_str(0))
-    }
+    Ok(YamlFile {
+        path: path.to_path_buf(),
+        content: Box::new(parse_file_content(path, &content)?),
+    })
 }
 
-fn load_file(path: &PathBuf) -> Vec<Yaml> {
-    let content = fs::read_to_string(path).expect("failed to read file");
-
-    YamlLoader::load_from_str(&content).expect("failed to parse yaml")
+pub fn load_yaml_files(source: &dyn FileSource, root: &Path) -> io::Result<Vec<YamlFile>> {
+    source
+        .list_yaml_files(root)
+        .into_par_iter()
+        .map(|path| read_yaml_file(source, &path))
+        .collect()
 }
 
-pub fn load_yaml(root: &PathBuf) -> YamlNode {
-    if root.is_file() {
-        return YamlNode::YamlFile(YamlFile {
-            path: root.clone(),
-            content: Box::new(load_file(root)),
-        });
+fn collect_refs<'a>(yaml: &'a Yaml, refs: &mut Vec<&'a str>) {
+    match yaml {
+        Yaml::Hash(hash) => hash
+            .iter()
+            .for_each(|(key, value)| match (key.as_str(), value) {
+                (Some("$ref"), Yaml::String(reference)) => refs.push(reference),
+                _ => collect_refs(value, refs),
+            }),
+        Yaml::Array(array) => array.iter().for_each(|value| collect_refs(value, refs)),
+        _ => {}
     }
+}
 
-    let entries = fs::read_dir(root).expect("read_dir call failed");
+// NOTE: `../schemas/Pet.yaml#/properties/id` のようなファイルへの $ref を、参照先のパスにして返す
+pub fn collect_file_refs(path: &Path, content: &[Yaml]) -> Vec<PathBuf> {
+    let base_dir = path.parent().expect("Failed to get parent dir");
+    let mut refs = vec![];
+    content
+        .iter()
+        .for_each(|yaml| collect_refs(yaml, &mut refs));
 
-    let paths = entries
+    let mut paths = refs
         .into_iter()
-        .map(|entry| entry.expect("failed to read_entry").path())
-        .filter(|path| {
-            path.is_dir() || path.to_str().map(|s| s.ends_with(".yaml")).unwrap_or(false)
+        .filter_map(|reference| {
+            let file_path = reference.split('#').next().unwrap_or_default();
+            if file_path.is_empty() {
+                None
+            } else {
+                Some(normalize_path(&base_dir.join(file_path)))
+            }
         })
+        .filter(|ref_path| ref_path != path)
         .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
 
-    let nodes = paths
-        .into_iter()
-        .map(|path| load_yaml(&path))
-        .collect::<Vec<_>>();
+    paths
+}
 
-    YamlNode::YamlDirectory(YamlDirectory {
-        path: root.clone(),
-        children: Box::new(nodes),
-    })
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_collect_refs() {
+        let yaml = YamlLoader::load_from_str(
+            r##"
+responses:
+  "200":
+    content:
+      application/json:
+        schema:
+          $ref: ../schemas/Pet.yaml
+  "404":
+    $ref: "#/components/responses/NotFound"
+parameters:
+  - $ref: ./parameters.yaml#/limit
+"##,
+        )
+        .unwrap();
+        let mut refs = vec![];
+        collect_refs(&yaml[0], &mut refs);
+        refs.sort();
+
+        assert_eq!(
+            refs,
+            vec![
+                "#/components/responses/NotFound",
+                "../schemas/Pet.yaml",
+                "./parameters.yaml#/limit",
+            ]
+        );
+        assert_eq!(
+            collect_file_refs(Path::new("/spec/paths/pets.yaml"), &yaml),
+            vec![
+                PathBuf::from("/spec/paths/parameters.yaml"),
+                PathBuf::from("/spec/schemas/Pet.yaml"),
+            ]
+        );
+    }
 }